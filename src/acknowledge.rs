@@ -0,0 +1,235 @@
+//! `lintscout-ignore` comments approving suppressions. Findings they match
+//! stay out of the counts but are listed as acknowledged.
+//!
+//! ```text
+//! @SuppressWarnings("unchecked") // lintscout-ignore java-suppress -- generic array
+//! # lintscout-ignore-next-line flake8/E501 -- generated table
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::finding::Finding;
+use crate::syntax::{Span, TokenKind};
+
+const MARKER: &str = "lintscout-ignore";
+const NEXT_LINE: &str = "-next-line";
+
+/// The `lintscout-ignore` comment approving a finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Acknowledgement {
+    /// Line of the comment.
+    pub line: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// A parsed `lintscout-ignore` comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directive {
+    line: usize,
+    /// Line whose findings it approves.
+    target_line: usize,
+    /// `scout` or `scout/rule` names; empty approves every finding.
+    targets: Vec<String>,
+    reason: Option<String>,
+}
+
+impl Directive {
+    fn parse(line_text: &str, line: usize) -> Option<Self> {
+        let start = line_text.find(MARKER)? + MARKER.len();
+        let mut rest = &line_text[start..];
+        let mut target_line = line;
+        if let Some(r) = rest.strip_prefix(NEXT_LINE) {
+            rest = r;
+            target_line += 1;
+        }
+        if !(rest.is_empty() || rest.starts_with(char::is_whitespace)) {
+            return None;
+        }
+        let rest = rest.trim_end();
+        let rest = rest
+            .strip_suffix("*/")
+            .or_else(|| rest.strip_suffix("-->"))
+            .unwrap_or(rest);
+        let (targets, reason) = match rest.split_once(" --") {
+            Some((targets, reason)) => (targets, Some(reason.trim())),
+            None => (rest, None),
+        };
+        Some(Directive {
+            line,
+            target_line,
+            targets: targets
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect(),
+            reason: reason.filter(|r| !r.is_empty()).map(String::from),
+        })
+    }
+
+    fn matches(&self, finding: &Finding) -> bool {
+        finding.line_number == self.target_line
+            && (self.targets.is_empty() || self.targets.iter().any(|t| target_matches(t, finding)))
+    }
+}
+
+/// Match `scout` or `scout/rule` against a finding's scout or linter, and
+/// its rule ID or any rule it suppresses.
+fn target_matches(target: &str, finding: &Finding) -> bool {
+    let (scout, rule) = match target.split_once('/') {
+        Some((scout, rule)) => (scout, Some(rule)),
+        None => (target, None),
+    };
+    (scout == finding.scout_name || scout == finding.linter)
+        && rule.map_or(true, |rule| {
+            rule == finding.rule_id
+                || finding
+                    .suppressed_rules
+                    .as_ref()
+                    .is_some_and(|rules| rules.iter().any(|r| r == rule))
+        })
+}
+
+/// The `lintscout-ignore` comments among `lines`, looked for only in the
+/// comment spans of each line when the file was tokenized.
+pub fn directives(lines: &[&str], spans: Option<&[Vec<Span>]>) -> Vec<Directive> {
+    let Some(spans) = spans else {
+        return lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| Directive::parse(line, index + 1))
+            .collect();
+    };
+    lines
+        .iter()
+        .zip(spans)
+        .enumerate()
+        .flat_map(|(index, (line, spans))| {
+            spans
+                .iter()
+                .filter(|s| s.kind == TokenKind::Comment)
+                .filter_map(move |s| Directive::parse(&line[s.start..s.end], index + 1))
+        })
+        .collect()
+}
+
+/// Split the findings of a file into those counted and those approved by one
+/// of the `lintscout-ignore` comments `directives` returns for them.
+pub fn partition<'a>(
+    findings: Vec<Finding>,
+    directives: impl Fn(&Finding) -> &'a [Directive],
+) -> (Vec<Finding>, Vec<Finding>) {
+    let mut counted = Vec::new();
+    let mut acknowledged = Vec::new();
+    for mut finding in findings {
+        match directives(&finding).iter().find(|d| d.matches(&finding)) {
+            Some(d) => {
+                finding.acknowledgement = Some(Acknowledgement {
+                    line: d.line,
+                    reason: d.reason.clone(),
+                });
+                acknowledged.push(finding);
+            }
+            None => counted.push(finding),
+        }
+    }
+    (counted, acknowledged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::CommentSyntax;
+
+    fn finding(line_number: usize, scout_name: &str, rules: &[&str]) -> Finding {
+        Finding {
+            path: "a.py".into(),
+            line_number,
+            scout_name: scout_name.into(),
+            linter: scout_name.into(),
+            rule_id: "noqa".into(),
+            blanket: rules.is_empty(),
+            suppressed_rules: Some(rules.iter().map(|r| r.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_directives() {
+        assert_eq!(
+            Directive::parse("x  # noqa: E501  # lintscout-ignore -- long URL", 3),
+            Some(Directive {
+                line: 3,
+                target_line: 3,
+                targets: Vec::new(),
+                reason: Some("long URL".into()),
+            })
+        );
+        assert_eq!(
+            Directive::parse("/* lintscout-ignore-next-line eslint/no-eval, flake8 */", 7),
+            Some(Directive {
+                line: 7,
+                target_line: 8,
+                targets: vec!["eslint/no-eval".into(), "flake8".into()],
+                reason: None,
+            })
+        );
+        assert_eq!(Directive::parse("// lintscout-ignored", 1), None);
+        assert_eq!(Directive::parse("// nothing here", 1), None);
+    }
+
+    #[test]
+    fn splits_acknowledged_findings() {
+        let lines = [
+            "import os  # noqa: F401  # lintscout-ignore flake8/F401 -- re-export",
+            "# lintscout-ignore-next-line mypy",
+            "x = f()  # type: ignore  # noqa",
+            "y = 1  # noqa: E501  # lintscout-ignore pylint",
+        ];
+        let findings = vec![
+            finding(1, "flake8", &["F401"]),
+            finding(3, "mypy", &[]),
+            finding(3, "flake8", &[]),
+            finding(4, "flake8", &["E501"]),
+        ];
+        let directives = directives(&lines, None);
+        let (counted, acknowledged) = partition(findings, |_| &directives);
+        let at = |f: &[Finding]| -> Vec<(usize, String)> {
+            f.iter()
+                .map(|f| (f.line_number, f.scout_name.clone()))
+                .collect()
+        };
+        assert_eq!(
+            at(&counted),
+            [(3, "flake8".to_string()), (4, "flake8".to_string())]
+        );
+        assert_eq!(
+            at(&acknowledged),
+            [(1, "flake8".to_string()), (3, "mypy".to_string())]
+        );
+        assert_eq!(
+            acknowledged[0].acknowledgement,
+            Some(Acknowledgement {
+                line: 1,
+                reason: Some("re-export".into()),
+            })
+        );
+        assert_eq!(acknowledged[1].acknowledgement.as_ref().unwrap().line, 2);
+    }
+
+    #[test]
+    fn ignores_markers_outside_comments() {
+        let content =
+            "msg = \"lintscout-ignore -- fine\"  # noqa\nx = 1  # noqa  # lintscout-ignore\n";
+        let lines: Vec<&str> = content.lines().collect();
+        let spans = CommentSyntax::PYTHON.tokenize(content);
+        let directives = directives(&lines, Some(&spans));
+        assert_eq!(directives.iter().map(|d| d.line).collect::<Vec<_>>(), [2]);
+        let (counted, acknowledged) = partition(
+            vec![finding(1, "flake8", &[]), finding(2, "flake8", &[])],
+            |_| &directives,
+        );
+        assert_eq!(counted[0].line_number, 1);
+        assert_eq!(acknowledged[0].line_number, 2);
+    }
+}
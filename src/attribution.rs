@@ -0,0 +1,310 @@
+//! Directives honored by several linters, such as `# noqa` by Flake8 and
+//! Ruff, attributed to the linter the scanned tree configures, with findings
+//! for the same directive from several scouts merged into one.
+
+use std::collections::HashMap;
+
+use crate::catalog::Catalog;
+use crate::finding::Finding;
+use crate::orphan::ConfiguredLinters;
+use crate::scanner::ScanResult;
+use crate::scope::Scope;
+
+/// Directives of a builtin scout that other linters honor too.
+struct Shared {
+    linter: &'static str,
+    /// Rule IDs with a description naming the directive rather than the
+    /// linter, for findings attributed to another one.
+    rules: &'static [(&'static str, &'static str)],
+    /// The other linters that honor them, in order of preference.
+    others: &'static [&'static str],
+    /// Whether the rules they name are sub-linters, which honor them too.
+    names_linters: bool,
+}
+
+const SHARED: &[Shared] = &[
+    Shared {
+        linter: "flake8",
+        rules: &[
+            ("noqa", "noqa directive"),
+            ("flake8-noqa-file", "File-level flake8: noqa directive"),
+        ],
+        others: &["ruff"],
+        names_linters: false,
+    },
+    Shared {
+        linter: "eslint",
+        rules: &[
+            ("eslint-disable", "eslint-disable block directive"),
+            (
+                "eslint-disable-next-line",
+                "eslint-disable-next-line directive",
+            ),
+            ("eslint-disable-line", "eslint-disable-line directive"),
+        ],
+        others: &["oxlint"],
+        names_linters: false,
+    },
+    Shared {
+        linter: "mypy",
+        rules: &[("type-ignore", "type: ignore directive")],
+        others: &["pyright"],
+        names_linters: false,
+    },
+    Shared {
+        linter: "golangci-lint",
+        rules: &[("nolint", "nolint directive")],
+        others: &[],
+        names_linters: true,
+    },
+];
+
+/// The shared directive a finding reports, with its neutral description.
+fn shared(finding: &Finding) -> Option<(&'static Shared, &'static str)> {
+    SHARED
+        .iter()
+        .filter(|s| s.linter == finding.linter)
+        .find_map(|s| {
+            s.rules
+                .iter()
+                .find(|(id, _)| *id == finding.rule_id)
+                .map(|(_, description)| (s, *description))
+        })
+}
+
+/// Every linter honoring the directive of a finding, its own first.
+fn honored_by(finding: &Finding) -> Vec<String> {
+    let mut linters = vec![finding.linter.clone()];
+    let Some((shared, _)) = shared(finding) else {
+        return linters;
+    };
+    linters.extend(shared.others.iter().map(|l| l.to_string()));
+    if shared.names_linters {
+        // Only names the catalog knows, so that typos honor nothing
+        linters.extend(
+            finding
+                .rule_details
+                .iter()
+                .filter(|d| d.known)
+                .map(|d| d.rule.clone()),
+        );
+    }
+    let mut seen = Vec::new();
+    linters.retain(|l| {
+        let first = !seen.contains(l);
+        seen.push(l.clone());
+        first
+    });
+    linters
+}
+
+/// Attribute a finding to its own linter when the tree configures it, or
+/// else to the first other linter honoring it that the tree configures.
+fn attribute_finding(finding: &mut Finding, configured: &ConfiguredLinters) {
+    let mut linters = honored_by(finding);
+    if !configured.is_configured(&linters[0]) {
+        if let Some(i) = linters.iter().position(|l| configured.is_configured(l)) {
+            let linter = linters.remove(i);
+            linters.insert(0, linter);
+        }
+    }
+    let linter = linters.remove(0);
+    if linter != finding.linter {
+        // The scout's description names the linter the finding no longer has
+        if let Some((_, description)) = shared(finding) {
+            finding.rule_description = description.to_string();
+        }
+        finding.rule_details = Catalog::builtin().details(
+            &linter,
+            finding.suppressed_rules.as_deref().unwrap_or_default(),
+        );
+        finding.linter = linter;
+    }
+    finding.also_honored_by = linters;
+}
+
+/// Merge the findings for one directive: on the same line, attributed to the
+/// same linter, with the same scope and suppressed rules. The first is kept,
+/// with the linters the others name.
+fn dedupe(findings: &mut Vec<Finding>) {
+    type Key = (
+        String,
+        usize,
+        String,
+        Scope,
+        Option<Vec<String>>,
+        Option<String>,
+    );
+    let mut seen: HashMap<Key, usize> = HashMap::new();
+    let mut kept: Vec<Finding> = Vec::with_capacity(findings.len());
+    for finding in findings.drain(..) {
+        let key = (
+            finding.path.clone(),
+            finding.line_number,
+            finding.linter.clone(),
+            finding.scope,
+            finding.suppressed_rules.clone(),
+            finding.key_path.clone(),
+        );
+        let Some(&i) = seen.get(&key) else {
+            seen.insert(key, kept.len());
+            kept.push(finding);
+            continue;
+        };
+        for linter in finding.also_honored_by {
+            if !kept[i].also_honored_by.contains(&linter) {
+                kept[i].also_honored_by.push(linter);
+            }
+        }
+    }
+    *findings = kept;
+}
+
+/// Attribute the findings, acknowledged ones included, to the linters the
+/// tree configures, and merge duplicates.
+pub fn attribute(result: &mut ScanResult, configured: &ConfiguredLinters) {
+    for findings in [&mut result.findings, &mut result.acknowledged] {
+        for finding in findings.iter_mut() {
+            attribute_finding(finding, configured);
+        }
+        dedupe(findings);
+    }
+    result.recount();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(scout: &str, rule_id: &str, line_number: usize, rules: &[&str]) -> Finding {
+        let suppressed_rules =
+            (!rules.is_empty()).then(|| rules.iter().map(|r| r.to_string()).collect::<Vec<_>>());
+        Finding {
+            path: "./app.py".into(),
+            line_number,
+            scout_name: scout.into(),
+            linter: scout.into(),
+            rule_id: rule_id.into(),
+            rule_description: match scout {
+                "flake8" => "Flake8 noqa directive",
+                "mypy" => "Mypy type ignore directive",
+                _ => "",
+            }
+            .into(),
+            blanket: rules.is_empty(),
+            rule_details: Catalog::builtin()
+                .details(scout, suppressed_rules.as_deref().unwrap_or_default()),
+            suppressed_rules,
+            ..Default::default()
+        }
+    }
+
+    fn result(findings: Vec<Finding>) -> ScanResult {
+        ScanResult {
+            findings,
+            ..Default::default()
+        }
+    }
+
+    fn configured(files: &[(&str, &str)]) -> ConfiguredLinters {
+        let mut configured = ConfiguredLinters::default();
+        for (name, content) in files {
+            configured.record(name, content);
+        }
+        configured
+    }
+
+    fn attributed(result: &ScanResult) -> Vec<(&str, Vec<&str>)> {
+        result
+            .findings
+            .iter()
+            .map(|f| {
+                (
+                    f.linter.as_str(),
+                    f.also_honored_by.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn attributes_to_configured_linters() {
+        let findings = || {
+            vec![
+                finding("flake8", "noqa", 1, &["F401"]),
+                finding("mypy", "type-ignore", 2, &[]),
+                finding("pylint", "pylint-disable", 3, &[]),
+            ]
+        };
+
+        let mut ruff = result(findings());
+        attribute(&mut ruff, &configured(&[("ruff.toml", "")]));
+        assert_eq!(
+            attributed(&ruff),
+            [
+                ("ruff", vec!["flake8"]),
+                ("mypy", vec!["pyright"]),
+                ("pylint", vec![]),
+            ]
+        );
+        assert_eq!(ruff.findings[0].rule_details[0].rule, "F401");
+        assert_eq!(ruff.findings[0].rule_description, "noqa directive");
+        assert_eq!(
+            ruff.findings[1].rule_description,
+            "Mypy type ignore directive"
+        );
+
+        let mut both = result(findings());
+        attribute(
+            &mut both,
+            &configured(&[
+                (".flake8", ""),
+                ("pyrightconfig.json", ""),
+                ("ruff.toml", ""),
+            ]),
+        );
+        assert_eq!(
+            attributed(&both),
+            [
+                ("flake8", vec!["ruff"]),
+                ("pyright", vec!["mypy"]),
+                ("pylint", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn nolint_is_honored_by_the_sub_linters_it_names() {
+        let mut result = result(vec![finding(
+            "golangci-lint",
+            "nolint",
+            1,
+            &["gosec", "errchek"],
+        )]);
+        attribute(&mut result, &configured(&[]));
+        assert_eq!(attributed(&result), [("golangci-lint", vec!["gosec"])]);
+    }
+
+    #[test]
+    fn merges_findings_for_the_same_directive() {
+        let mut custom = finding("ruff", "ruff-compat-noqa", 1, &["F401"]);
+        custom.scout_name = "ruff-compat".into();
+        let mut result = result(vec![
+            finding("flake8", "noqa", 1, &["F401"]),
+            custom,
+            finding("flake8", "noqa", 2, &["F401"]),
+            finding("ruff", "ruff-noqa", 2, &["E501"]),
+        ]);
+        attribute(&mut result, &configured(&[("ruff.toml", "")]));
+        assert_eq!(
+            attributed(&result),
+            [
+                ("ruff", vec!["flake8"]),
+                ("ruff", vec!["flake8"]),
+                ("ruff", vec![]),
+            ]
+        );
+        assert_eq!(result.findings[0].scout_name, "flake8");
+        assert_eq!(result.stats.findings_count, 3);
+    }
+}
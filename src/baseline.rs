@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::diff::DiffSummary;
+use crate::error::{LintScoutError, Result};
+use crate::scanner::ScanResult;
+use crate::scope::Scope;
+
+const BASELINE_VERSION: u32 = 1;
+
+/// Findings accepted as pre-existing, so only new ones fail a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// A recorded finding. Only the fingerprint is used for matching; the other
+/// fields make the file reviewable and describe entries that disappear.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub path: String,
+    pub line_number: usize,
+    pub scout_name: String,
+    pub rule_id: String,
+    pub line_text: String,
+    /// Absent from baselines written before scopes were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scope>,
+}
+
+impl BaselineEntry {
+    /// Whether a scan of `scanned`, limited to `scopes` and to the lines of
+    /// `diff`, could have found this entry. Entries it could not have found
+    /// are not reported as disappeared.
+    fn covered(
+        &self,
+        scanned: &Path,
+        scopes: Option<&[Scope]>,
+        diff: Option<&DiffSummary>,
+    ) -> bool {
+        let path = Path::new(&self.path);
+        let under = lexical(path).starts_with(lexical(scanned));
+        let scoped = match (scopes, self.scope) {
+            (Some(scopes), Some(scope)) => scopes.contains(&scope),
+            _ => true,
+        };
+        // A diff scan only sees changed lines, so an entry is gone only when
+        // the diff removes its suppression
+        let removed = diff.map_or(true, |diff| {
+            diff.removed.iter().any(|f| {
+                f.scout_name == self.scout_name
+                    && f.rule_id == self.rule_id
+                    && f.line_text.trim() == self.line_text
+                    && path.ends_with(&f.path)
+            })
+        });
+        under && scoped && removed
+    }
+}
+
+/// `path` without `.` components, so `./src` and `src` compare equal.
+fn lexical(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| *c != Component::CurDir)
+        .collect()
+}
+
+/// How a scan compared to the baseline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineSummary {
+    /// Findings hidden because the baseline already records them.
+    pub matched: u64,
+    /// Baseline entries no longer found, which can be pruned.
+    pub disappeared: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Record every finding of a scan.
+    pub fn from_result(result: &ScanResult) -> Self {
+        let entries = result
+            .findings
+            .iter()
+            .map(|f| BaselineEntry {
+                fingerprint: f.fingerprint.clone(),
+                path: f.path.clone(),
+                line_number: f.line_number,
+                scout_name: f.scout_name.clone(),
+                rule_id: f.rule_id.clone(),
+                line_text: f.line_text.trim().to_string(),
+                scope: Some(f.scope),
+            })
+            .collect();
+        Baseline {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| LintScoutError::FileRead {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        let baseline: Baseline =
+            serde_json::from_str(&content).map_err(|e| LintScoutError::BaselineLoad {
+                path: path.to_path_buf(),
+                source: Box::new(e),
+            })?;
+        if baseline.version > BASELINE_VERSION {
+            return Err(LintScoutError::BaselineLoad {
+                path: path.to_path_buf(),
+                source: format!("unsupported baseline version {}", baseline.version).into(),
+            });
+        }
+        Ok(baseline)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Drop findings recorded in the baseline and summarize the comparison.
+    ///
+    /// Each entry matches at most one finding, so duplicating a recorded
+    /// suppression still reports the copy. Only entries the scan of `scanned`
+    /// with `scopes`, and the diff applied to `result`, if any, could have
+    /// found are reported as disappeared.
+    pub fn apply(&self, result: &mut ScanResult, scanned: &Path, scopes: Option<&[Scope]>) {
+        let mut remaining: HashMap<&str, Vec<&BaselineEntry>> = HashMap::new();
+        for entry in &self.entries {
+            remaining
+                .entry(entry.fingerprint.as_str())
+                .or_default()
+                .push(entry);
+        }
+
+        let before = result.findings.len();
+        result.retain(|f| {
+            remaining
+                .get_mut(f.fingerprint.as_str())
+                .and_then(|entries| entries.pop())
+                .is_none()
+        });
+
+        let mut disappeared: Vec<BaselineEntry> = remaining
+            .into_values()
+            .flatten()
+            .filter(|entry| entry.covered(scanned, scopes, result.diff.as_ref()))
+            .cloned()
+            .collect();
+        disappeared.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then(a.line_number.cmp(&b.line_number))
+                .then(a.rule_id.cmp(&b.rule_id))
+        });
+        result.baseline = Some(BaselineSummary {
+            matched: (before - result.findings.len()) as u64,
+            disappeared,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Finding;
+    use crate::scope::Scope;
+
+    fn finding(line_number: usize, fingerprint: &str) -> Finding {
+        Finding {
+            path: "a.py".into(),
+            line_number,
+            line_text: "import os  # noqa".into(),
+            scout_name: "flake8".into(),
+            linter: "flake8".into(),
+            rule_id: "flake8-noqa".into(),
+            rule_description: "Flake8 noqa".into(),
+            blanket: true,
+            fingerprint: fingerprint.into(),
+            ..Default::default()
+        }
+    }
+
+    fn result(findings: Vec<Finding>) -> ScanResult {
+        let mut result = ScanResult {
+            findings,
+            ..Default::default()
+        };
+        result.recount();
+        result
+    }
+
+    #[test]
+    fn apply_hides_known_and_reports_disappeared() {
+        let baseline = Baseline::from_result(&result(vec![finding(1, "aa"), finding(5, "bb")]));
+        let mut current = result(vec![finding(3, "aa"), finding(9, "cc")]);
+        baseline.apply(&mut current, Path::new("."), None);
+
+        assert_eq!(current.findings.len(), 1);
+        assert_eq!(current.findings[0].fingerprint, "cc");
+        assert_eq!(current.stats.findings_count, 1);
+        let summary = current.baseline.unwrap();
+        assert_eq!(summary.matched, 1);
+        assert_eq!(summary.disappeared.len(), 1);
+        assert_eq!(summary.disappeared[0].fingerprint, "bb");
+    }
+
+    #[test]
+    fn disappeared_is_limited_to_what_the_scan_covers() {
+        let mut other = finding(2, "bb");
+        other.path = "lib/b.py".into();
+        let mut file = finding(4, "cc");
+        file.scope = Scope::WholeFile;
+        file.line_text = "# flake8: noqa".into();
+        let baseline = Baseline::from_result(&result(vec![finding(1, "aa"), other, file]));
+        let disappeared = |scanned: &str, scopes: Option<&[Scope]>, diff: Option<DiffSummary>| {
+            let mut current = result(Vec::new());
+            current.diff = diff;
+            baseline.apply(&mut current, Path::new(scanned), scopes);
+            current
+                .baseline
+                .unwrap()
+                .disappeared
+                .into_iter()
+                .map(|e| e.fingerprint)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(disappeared(".", None, None), ["aa", "cc", "bb"]);
+        assert_eq!(disappeared("./lib", None, None), ["bb"]);
+        assert_eq!(disappeared(".", Some(&[Scope::WholeFile]), None), ["cc"]);
+        let diff = DiffSummary {
+            base: "main".into(),
+            added: 0,
+            removed: vec![finding(1, "other")],
+        };
+        assert_eq!(disappeared(".", None, Some(diff)), ["aa"]);
+    }
+
+    #[test]
+    fn duplicates_are_matched_one_to_one() {
+        let baseline = Baseline::from_result(&result(vec![finding(1, "aa")]));
+        let mut current = result(vec![finding(1, "aa"), finding(2, "aa")]);
+        baseline.apply(&mut current, Path::new("."), None);
+        assert_eq!(current.findings.len(), 1);
+        assert!(current.baseline.unwrap().disappeared.is_empty());
+    }
+
+    #[test]
+    fn round_trips_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        let baseline = Baseline::from_result(&result(vec![finding(1, "aa")]));
+        baseline.write(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+        assert_eq!(loaded.entries, baseline.entries);
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        fs::write(&path, r#"{"version": 99, "entries": []}"#).unwrap();
+        assert!(Baseline::load(&path).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::date;
+use crate::error::{LintScoutError, Result};
+use crate::git;
+use crate::scanner::ScanResult;
+
+/// Who last changed a line, from `git blame`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blame {
+    pub commit: String,
+    pub author: String,
+    pub author_mail: String,
+    /// Author time in seconds since the Unix epoch.
+    pub author_time: i64,
+    /// Author date as `YYYY-MM-DD` (UTC).
+    pub date: String,
+}
+
+/// The content findings were scanned from, which blame must follow.
+#[derive(Debug, Clone, Copy)]
+pub enum Source<'a> {
+    /// The working tree
+    WorkTree,
+    /// The tree of a revision
+    Rev(&'a str),
+    /// The content staged in the index
+    Index,
+}
+
+#[derive(Default)]
+struct CommitInfo {
+    author: String,
+    author_mail: String,
+    author_time: i64,
+}
+
+/// Attach blame information to every finding, running one `git blame` per
+/// file for just the lines with findings. Paths are resolved relative to
+/// `dir`, and lines are blamed as they are in `source`. Files that cannot be
+/// blamed, e.g. untracked ones, are recorded as errors.
+pub fn annotate(result: &mut ScanResult, dir: &Path, source: Source) {
+    let mut by_path: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for f in &result.findings {
+        by_path.entry(&f.path).or_default().push(f.line_number);
+    }
+
+    let mut staged = match source {
+        Source::Index => match staged_contents(dir, by_path.keys().copied()) {
+            Ok(staged) => staged,
+            Err(e) => {
+                result.errors.push(format!("{}: {e}", dir.display()));
+                result.stats.errors_count += 1;
+                return;
+            }
+        },
+        _ => HashMap::new(),
+    };
+    let rev = match source {
+        Source::Rev(rev) => Some(rev),
+        _ => None,
+    };
+
+    let files: Vec<_> = by_path
+        .into_iter()
+        .map(|(path, lines)| (path, lines, staged.remove(&relative(dir, path))))
+        .collect();
+    let blamed: Vec<(String, Result<HashMap<usize, Blame>>)> = files
+        .into_par_iter()
+        .map(|(path, mut lines, contents)| {
+            lines.sort_unstable();
+            lines.dedup();
+            let relative = relative(dir, path);
+            let blame = match (source, contents) {
+                (Source::Index, None) => Err(LintScoutError::Git("not staged in the index".into())),
+                (_, contents) => blame_lines(dir, &relative, rev, contents, &lines),
+            };
+            (path.to_string(), blame)
+        })
+        .collect();
+
+    let mut by_path = HashMap::new();
+    for (path, blame) in blamed {
+        match blame {
+            Ok(lines) => {
+                by_path.insert(path, lines);
+            }
+            Err(e) => {
+                result.errors.push(format!("{path}: {e}"));
+                result.stats.errors_count += 1;
+            }
+        }
+    }
+    for f in &mut result.findings {
+        f.blame = by_path
+            .get(&f.path)
+            .and_then(|lines| lines.get(&f.line_number))
+            .cloned();
+    }
+}
+
+/// A finding's path relative to the directory git runs in.
+fn relative(dir: &Path, path: &str) -> PathBuf {
+    Path::new(path)
+        .strip_prefix(dir)
+        .unwrap_or(Path::new(path))
+        .to_path_buf()
+}
+
+/// The staged contents of the given files, by path relative to `dir`.
+fn staged_contents<'a>(
+    dir: &Path,
+    paths: impl Iterator<Item = &'a str>,
+) -> Result<HashMap<PathBuf, Vec<u8>>> {
+    let wanted: Vec<PathBuf> = paths.map(|path| relative(dir, path)).collect();
+    let (paths, oids): (Vec<PathBuf>, Vec<String>) = git::staged_files(dir, ".")?
+        .into_iter()
+        .filter(|(path, _)| wanted.contains(path))
+        .unzip();
+    let blobs = git::read_blobs(dir, &oids)?;
+    Ok(paths.into_iter().zip(blobs).collect())
+}
+
+/// Blame the given lines of one file, as they are in `contents` when given,
+/// e.g. the staged blob, rather than in the working tree.
+pub fn blame_lines(
+    dir: &Path,
+    path: &Path,
+    rev: Option<&str>,
+    contents: Option<Vec<u8>>,
+    lines: &[usize],
+) -> Result<HashMap<usize, Blame>> {
+    let ranges: Vec<String> = lines.iter().map(|l| format!("-L{l},{l}")).collect();
+    let path = path.to_string_lossy();
+    let mut args = vec!["blame", "--porcelain"];
+    args.extend(ranges.iter().map(String::as_str));
+    args.extend(rev);
+    let out = match contents {
+        Some(contents) => {
+            args.extend(["--contents", "-", "--", &path]);
+            git::run_with_input(dir, &args, contents)?
+        }
+        None => {
+            args.extend(["--", &path]);
+            git::run(dir, &args)?
+        }
+    };
+    Ok(parse_porcelain(&out))
+}
+
+/// Parse `git blame --porcelain` output into blame info by final line number.
+fn parse_porcelain(out: &str) -> HashMap<usize, Blame> {
+    let mut commits: HashMap<String, CommitInfo> = HashMap::new();
+    let mut blamed = HashMap::new();
+    let mut current: Option<(String, usize)> = None;
+
+    for line in out.lines() {
+        if line.starts_with('\t') {
+            // The line's content ends each entry
+            let Some((commit, line_number)) = current.take() else {
+                continue;
+            };
+            if let Some(info) = commits.get(&commit) {
+                blamed.insert(
+                    line_number,
+                    Blame {
+                        commit,
+                        author: info.author.clone(),
+                        author_mail: info.author_mail.clone(),
+                        author_time: info.author_time,
+                        date: date::format_date(info.author_time),
+                    },
+                );
+            }
+            continue;
+        }
+
+        if let Some((commit, line_number)) = parse_header(line) {
+            current = Some((commit.to_string(), line_number));
+            commits.entry(commit.to_string()).or_default();
+            continue;
+        }
+
+        let Some((ref commit, _)) = current else {
+            continue;
+        };
+        let Some(info) = commits.get_mut(commit) else {
+            continue;
+        };
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "author" => info.author = value.to_string(),
+            "author-mail" => {
+                info.author_mail = value.trim_start_matches('<').trim_end_matches('>').into()
+            }
+            "author-time" => info.author_time = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    blamed
+}
+
+/// Parse an entry header: `<commit> <original line> <final line> [<count>]`.
+fn parse_header(line: &str) -> Option<(&str, usize)> {
+    let mut fields = line.split(' ');
+    let commit = fields.next()?;
+    if commit.len() != 40 || !commit.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    fields.next()?;
+    Some((commit, fields.next()?.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORCELAIN: &str = "\
+8f3a1b2c4d5e6f708192a3b4c5d6e7f8091a2b3c 3 3 1
+author Ada Lovelace
+author-mail <ada@example.com>
+author-time 1700000000
+author-tz +0000
+summary Add parser
+filename src/app.py
+\timport os  # noqa
+8f3a1b2c4d5e6f708192a3b4c5d6e7f8091a2b3c 9 10 1
+filename src/app.py
+\tx = f()  # type: ignore
+0000000000000000000000000000000000000000 12 12 1
+author Not Committed Yet
+author-mail <not.committed.yet>
+author-time 1710000000
+author-tz +0000
+summary Version of src/app.py from src/app.py
+filename src/app.py
+\ty = g()  # noqa
+";
+
+    #[test]
+    fn parses_porcelain_and_reuses_commit_info() {
+        let blamed = parse_porcelain(PORCELAIN);
+        assert_eq!(blamed.len(), 3);
+        let first = &blamed[&3];
+        assert_eq!(first.author, "Ada Lovelace");
+        assert_eq!(first.author_mail, "ada@example.com");
+        assert_eq!(first.date, "2023-11-14");
+        assert_eq!(blamed[&10].author, "Ada Lovelace");
+        assert_eq!(blamed[&10].commit, first.commit);
+        assert_eq!(blamed[&12].author, "Not Committed Yet");
+    }
+}
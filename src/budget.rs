@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::config::BudgetConfig;
+use crate::error::{LintScoutError, Result};
+use crate::scanner::ScanResult;
+
+/// How much of a budget the reported findings use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scouts: Option<Vec<String>>,
+    pub max: u64,
+    pub used: u64,
+    pub exceeded: bool,
+}
+
+/// Checks findings against per-path budgets.
+pub struct Budgets {
+    budgets: Vec<(BudgetConfig, Gitignore)>,
+}
+
+impl Budgets {
+    pub fn new(budgets: &[BudgetConfig]) -> Result<Self> {
+        let budgets = budgets
+            .iter()
+            .map(|budget| {
+                let mut builder = GitignoreBuilder::new("");
+                builder
+                    .add_line(None, &budget.path)
+                    .map_err(|e| invalid(budget, e))?;
+                let matcher = builder.build().map_err(|e| invalid(budget, e))?;
+                Ok((budget.clone(), matcher))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Budgets { budgets })
+    }
+
+    /// Record the usage of every budget by the findings, whose paths lie
+    /// under `root`. Rule policy violations do not count.
+    pub fn apply(&self, result: &mut ScanResult, root: &Path) {
+        let usage: Vec<BudgetUsage> = self
+            .budgets
+            .iter()
+            .map(|(budget, matcher)| {
+                let used = result
+                    .findings
+                    .iter()
+                    .filter(|f| f.policy_violation.is_none())
+                    .filter(|f| {
+                        budget
+                            .scouts
+                            .as_ref()
+                            .map_or(true, |s| s.contains(&f.scout_name))
+                    })
+                    .filter(|f| {
+                        let path = Path::new(&f.path);
+                        let relative = path.strip_prefix(root).unwrap_or(path);
+                        matcher
+                            .matched_path_or_any_parents(relative, false)
+                            .is_ignore()
+                    })
+                    .count() as u64;
+                BudgetUsage {
+                    path: budget.path.clone(),
+                    scouts: budget.scouts.clone(),
+                    max: budget.max,
+                    used,
+                    exceeded: used > budget.max,
+                }
+            })
+            .collect();
+        result.stats.budgets_exceeded = usage.iter().filter(|u| u.exceeded).count() as u64;
+        result.budgets = usage;
+    }
+}
+
+fn invalid(budget: &BudgetConfig, error: ignore::Error) -> LintScoutError {
+    LintScoutError::Config(format!("invalid budget path '{}': {error}", budget.path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Finding;
+
+    fn finding(path: &str, scout_name: &str) -> Finding {
+        Finding {
+            path: path.into(),
+            line_number: 1,
+            scout_name: scout_name.into(),
+            linter: scout_name.into(),
+            ..Default::default()
+        }
+    }
+
+    fn budget(path: &str, scouts: Option<&[&str]>, max: u64) -> BudgetConfig {
+        BudgetConfig {
+            path: path.into(),
+            scouts: scouts.map(|s| s.iter().map(|s| s.to_string()).collect()),
+            max,
+        }
+    }
+
+    #[test]
+    fn counts_findings_per_budget() {
+        let mut result = ScanResult {
+            findings: vec![
+                finding("./legacy/a.py", "flake8"),
+                finding("./legacy/deep/b.ts", "eslint"),
+                finding("./services/payments/c.ts", "eslint"),
+                finding("./services/users/d.ts", "typescript"),
+            ],
+            ..Default::default()
+        };
+        let budgets = Budgets::new(&[
+            budget("legacy/", None, 500),
+            budget("services/payments/**", None, 0),
+            budget("*.ts", Some(&["eslint"]), 1),
+        ])
+        .unwrap();
+        budgets.apply(&mut result, Path::new("."));
+
+        let used: Vec<(u64, bool)> = result
+            .budgets
+            .iter()
+            .map(|b| (b.used, b.exceeded))
+            .collect();
+        assert_eq!(used, [(2, false), (1, true), (2, true)]);
+        assert_eq!(result.stats.budgets_exceeded, 2);
+    }
+}
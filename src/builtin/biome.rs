@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -17,8 +19,11 @@ pub fn scout() -> Result<Scout> {
         ],
         rules: vec![
             Rule::new("biome-ignore", "Biome ignore directive", r"biome-ignore")?
-                .with_capture(r"biome-ignore\s+([^:]+)")?,
+                .with_capture(r"biome-ignore\s+([^:]+)")?
+                .with_scope(Scope::NextLine)
+                .with_justification(r":\s*(.*)")?,
         ],
+        syntax: Some(CommentSyntax::JAVASCRIPT),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -18,21 +20,29 @@ pub fn scout() -> Result<Scout> {
         ],
         rules: vec![
             Rule::new("nolint", "clang-tidy NOLINT directive", r"NOLINT(\(|$|\s)")?
-                .with_capture(r"NOLINT\(([^)]+)\)")?,
+                .with_capture(r"NOLINT\(([^)]+)\)")?
+                .with_justification(r"NOLINT(?:\([^)]*\))?:?\s+(.*)")?,
             Rule::new(
                 "nolintnextline",
                 "clang-tidy NOLINTNEXTLINE directive",
                 r"NOLINTNEXTLINE",
             )?
-            .with_capture(r"NOLINTNEXTLINE\(([^)]+)\)")?,
+            .with_capture(r"NOLINTNEXTLINE\(([^)]+)\)")?
+            .with_scope(Scope::NextLine)
+            .with_justification(r"NOLINTNEXTLINE(?:\([^)]*\))?:?\s+(.*)")?,
             Rule::new(
                 "nolintbegin",
                 "clang-tidy NOLINTBEGIN directive",
                 r"NOLINTBEGIN",
             )?
-            .with_capture(r"NOLINTBEGIN\(([^)]+)\)")?,
-            Rule::new("nolintend", "clang-tidy NOLINTEND directive", r"NOLINTEND")?,
+            .with_capture(r"NOLINTBEGIN\(([^)]+)\)")?
+            .opens_region("nolint")
+            .with_justification(r"NOLINTBEGIN(?:\([^)]*\))?:?\s+(.*)")?,
+            Rule::new("nolintend", "clang-tidy NOLINTEND directive", r"NOLINTEND")?
+                .with_capture(r"NOLINTEND\(([^)]+)\)")?
+                .closes_region("nolint"),
         ],
+        syntax: Some(CommentSyntax::C),
     })
 }
 
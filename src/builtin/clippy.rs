@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -14,14 +16,21 @@ pub fn scout() -> Result<Scout> {
                 "Clippy allow attribute",
                 r"#\[allow\(clippy::",
             )?
-            .with_capture(r"#\[allow\(clippy::([^)]+)\)")?,
+            .with_capture(r"#\[allow\(clippy::([^)]+)\)")?
+            .in_code()
+            .with_scope(Scope::Declaration)
+            .with_justification(r#"reason\s*=\s*"([^"]*)""#)?,
             Rule::new(
                 "allow-clippy-file",
                 "Clippy file-level allow attribute",
                 r"#!\[allow\(clippy::",
             )?
-            .with_capture(r"#!\[allow\(clippy::([^)]+)\)")?,
+            .with_capture(r"#!\[allow\(clippy::([^)]+)\)")?
+            .in_code()
+            .with_scope(Scope::WholeFile)
+            .with_justification(r#"reason\s*=\s*"([^"]*)""#)?,
         ],
+        syntax: Some(CommentSyntax::RUST),
     })
 }
 
@@ -51,5 +60,8 @@ mod tests {
         let s = scout().unwrap();
         assert!(s.find_matches("#[allow(dead_code)]").is_empty());
         assert!(s.find_matches("use clippy;").is_empty());
+        assert!(s
+            .find_matches("// #[allow(clippy::needless_return)]")
+            .is_empty());
     }
 }
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -21,7 +23,9 @@ pub fn scout() -> Result<Scout> {
             "cppcheck suppress directive",
             r"cppcheck-suppress",
         )?
-        .with_capture(r"cppcheck-suppress\s+(.+)")?],
+        .with_capture(r"cppcheck-suppress\s+(.+)")?
+        .with_standalone_scope(Scope::NextLine)],
+        syntax: Some(CommentSyntax::C),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -14,14 +16,21 @@ pub fn scout() -> Result<Scout> {
                 "Kotlin/Detekt Suppress annotation",
                 r"@Suppress\(",
             )?
-            .with_capture(r#"@Suppress\("([^"]*)"#)?,
+            .with_capture(r#"@Suppress\("([^"]*)"#)?
+            .in_code()
+            .with_scope(Scope::Declaration)
+            .with_justification(r"//\s*(.*)")?,
             Rule::new(
                 "file-suppress",
                 "Kotlin/Detekt file-level Suppress",
                 r"@file:Suppress",
             )?
-            .with_capture(r#"@file:Suppress\("([^"]*)"#)?,
+            .with_capture(r#"@file:Suppress\("([^"]*)"#)?
+            .in_code()
+            .with_scope(Scope::WholeFile)
+            .with_justification(r"//\s*(.*)")?,
         ],
+        syntax: Some(CommentSyntax::JAVA),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -23,26 +25,33 @@ pub fn scout() -> Result<Scout> {
                 "ESLint disable block directive",
                 r"eslint-disable(?:\s|$)",
             )?
-            .with_capture(r"eslint-disable\s+([^*]+)")?,
+            .with_capture(r"eslint-disable\s+([^*]+)")?
+            .opens_region("eslint")
+            .with_justification(r"--\s*(.*)")?,
             Rule::new(
                 "eslint-disable-next-line",
                 "ESLint disable next line",
                 r"eslint-disable-next-line",
             )?
-            .with_capture(r"eslint-disable-next-line\s+([^*]+)")?,
+            .with_capture(r"eslint-disable-next-line\s+([^*]+)")?
+            .with_scope(Scope::NextLine)
+            .with_justification(r"--\s*(.*)")?,
             Rule::new(
                 "eslint-disable-line",
                 "ESLint disable current line",
                 r"eslint-disable-line",
             )?
-            .with_capture(r"eslint-disable-line\s+([^*]+)")?,
+            .with_capture(r"eslint-disable-line\s+([^*]+)")?
+            .with_justification(r"--\s*(.*)")?,
             Rule::new(
                 "eslint-enable",
                 "ESLint re-enable directive",
                 r"eslint-enable",
             )?
-            .with_capture(r"eslint-enable\s+([^*]+)")?,
+            .with_capture(r"eslint-enable\s+([^*]+)")?
+            .closes_region("eslint"),
         ],
+        syntax: Some(CommentSyntax::JAVASCRIPT),
     })
 }
 
@@ -73,5 +82,8 @@ mod tests {
             .find_matches("const eslint = require('eslint');")
             .is_empty());
         assert!(s.find_matches("normal code").is_empty());
+        assert!(s
+            .find_matches(r#"const msg = "use eslint-disable-next-line here";"#)
+            .is_empty());
     }
 }
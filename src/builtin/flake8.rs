@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -8,8 +10,19 @@ pub fn scout() -> Result<Scout> {
         linter: "flake8".into(),
         language: "python".into(),
         extensions: vec!["py".into()],
-        rules: vec![Rule::new("noqa", "Flake8 noqa directive", r"#\s*noqa")?
-            .with_capture(r"#\s*noqa:\s*(.+)")?],
+        rules: vec![
+            Rule::new("noqa", "Flake8 noqa directive", r"#\s*noqa")?
+                .with_capture(r"#\s*noqa:\s*(.+)")?
+                .with_justification(r"\s#\s*(.*)")?,
+            Rule::new(
+                "flake8-noqa-file",
+                "Flake8 file-level noqa directive",
+                r"#\s*flake8:\s*noqa",
+            )?
+            .with_scope(Scope::WholeFile)
+            .with_justification(r"\s#\s*(.*)")?,
+        ],
+        syntax: Some(CommentSyntax::PYTHON),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -10,8 +12,11 @@ pub fn scout() -> Result<Scout> {
         extensions: vec!["go".into()],
         rules: vec![
             Rule::new("nolint", "golangci-lint nolint directive", r"//\s*nolint")?
-                .with_capture(r"//\s*nolint:([^\s]+)")?,
+                .with_capture(r"//\s*nolint:([^\s]+)")?
+                .with_standalone_scope(Scope::Declaration)
+                .with_justification(r"\s//\s*(.*)")?,
         ],
+        syntax: Some(CommentSyntax::GO),
     })
 }
 
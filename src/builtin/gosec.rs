@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::rule::Rule;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -9,7 +10,9 @@ pub fn scout() -> Result<Scout> {
         language: "go".into(),
         extensions: vec!["go".into()],
         rules: vec![Rule::new("nosec", "gosec nosec directive", r"//\s*#nosec")?
-            .with_capture(r"//\s*#nosec\s+(.+)")?],
+            .with_capture(r"//\s*#nosec\s+(.+)")?
+            .with_justification(r"--\s*(.*)")?],
+        syntax: Some(CommentSyntax::GO),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -13,7 +15,9 @@ pub fn scout() -> Result<Scout> {
             "Hadolint ignore directive",
             r"hadolint\s+ignore=",
         )?
-        .with_capture(r"hadolint\s+ignore=(.+)")?],
+        .with_capture(r"hadolint\s+ignore=(.+)")?
+        .with_scope(Scope::NextLine)],
+        syntax: Some(CommentSyntax::HASH),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -14,20 +16,35 @@ pub fn scout() -> Result<Scout> {
                 "Java SuppressWarnings annotation",
                 r"@SuppressWarnings",
             )?
-            .with_capture(r#"@SuppressWarnings\(\{?"([^"]*)"#)?,
+            .with_capture(r#"@SuppressWarnings\(\{?"([^"]*)"#)?
+            .in_code()
+            .with_scope(Scope::Declaration)
+            .with_justification(r"//\s*(.*)")?,
             Rule::new(
                 "checkstyle-off",
-                "Checkstyle toggle directive",
-                r"CHECKSTYLE:\s*(OFF|ON)",
-            )?,
-            Rule::new("nopmd", "PMD suppression directive", r"NOPMD")?,
+                "Checkstyle disable directive",
+                r"CHECKSTYLE:\s*OFF",
+            )?
+            .opens_region("checkstyle"),
+            Rule::new(
+                "checkstyle-on",
+                "Checkstyle re-enable directive",
+                r"CHECKSTYLE:\s*ON",
+            )?
+            .closes_region("checkstyle"),
+            Rule::new("nopmd", "PMD suppression directive", r"NOPMD")?
+                .with_justification(r"NOPMD\s*-?\s*(.+)")?,
             Rule::new(
                 "suppress-fb-warnings",
                 "FindBugs/SpotBugs suppression",
                 r"@SuppressFBWarnings",
             )?
-            .with_capture(r#"@SuppressFBWarnings\("([^"]*)""#)?,
+            .with_capture(r#"@SuppressFBWarnings\("([^"]*)""#)?
+            .in_code()
+            .with_scope(Scope::Declaration)
+            .with_justification(r#"justification\s*=\s*"([^"]*)""#)?,
         ],
+        syntax: Some(CommentSyntax::JAVA),
     })
 }
 
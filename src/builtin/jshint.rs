@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::rule::Rule;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -8,11 +9,26 @@ pub fn scout() -> Result<Scout> {
         linter: "jshint".into(),
         language: "javascript".into(),
         extensions: vec!["js".into()],
-        rules: vec![Rule::new(
-            "jshint-ignore",
-            "JSHint ignore directive",
-            r"jshint\s+ignore",
-        )?],
+        rules: vec![
+            Rule::new(
+                "jshint-ignore",
+                "JSHint ignore:start directive",
+                r"jshint\s+ignore:start",
+            )?
+            .opens_region("jshint"),
+            Rule::new(
+                "jshint-ignore-line",
+                "JSHint ignore:line directive",
+                r"jshint\s+ignore:line",
+            )?,
+            Rule::new(
+                "jshint-ignore-end",
+                "JSHint ignore:end directive",
+                r"jshint\s+ignore:end",
+            )?
+            .closes_region("jshint"),
+        ],
+        syntax: Some(CommentSyntax::JAVASCRIPT),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -14,14 +16,27 @@ pub fn scout() -> Result<Scout> {
                 "ktlint disable directive",
                 r"ktlint-disable",
             )?
-            .with_capture(r"ktlint-disable\s+(.+)")?,
+            .with_capture(r"ktlint-disable\s+([^*]+)")?
+            .opens_region("ktlint")
+            .inline_if_trailing(),
+            Rule::new(
+                "ktlint-enable",
+                "ktlint re-enable directive",
+                r"ktlint-enable",
+            )?
+            .with_capture(r"ktlint-enable\s+([^*]+)")?
+            .closes_region("ktlint"),
             Rule::new(
                 "suppress-ktlint",
                 "Kotlin Suppress annotation for ktlint",
                 r#"@Suppress\("ktlint:"#,
             )?
-            .with_capture(r#"@Suppress\("ktlint:([^"]+)"#)?,
+            .with_capture(r#"@Suppress\("ktlint:([^"]+)"#)?
+            .in_code()
+            .with_scope(Scope::Declaration)
+            .with_justification(r"//\s*(.*)")?,
         ],
+        syntax: Some(CommentSyntax::JAVA),
     })
 }
 
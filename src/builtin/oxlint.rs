@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -23,26 +25,33 @@ pub fn scout() -> Result<Scout> {
                 "oxlint disable block directive",
                 r"oxlint-disable(?:\s|$)",
             )?
-            .with_capture(r"oxlint-disable\s+([^*]+)")?,
+            .with_capture(r"oxlint-disable\s+([^*]+)")?
+            .opens_region("oxlint")
+            .with_justification(r"--\s*(.*)")?,
             Rule::new(
                 "oxlint-disable-next-line",
                 "oxlint disable next line",
                 r"oxlint-disable-next-line",
             )?
-            .with_capture(r"oxlint-disable-next-line\s+([^*]+)")?,
+            .with_capture(r"oxlint-disable-next-line\s+([^*]+)")?
+            .with_scope(Scope::NextLine)
+            .with_justification(r"--\s*(.*)")?,
             Rule::new(
                 "oxlint-disable-line",
                 "oxlint disable current line",
                 r"oxlint-disable-line",
             )?
-            .with_capture(r"oxlint-disable-line\s+([^*]+)")?,
+            .with_capture(r"oxlint-disable-line\s+([^*]+)")?
+            .with_justification(r"--\s*(.*)")?,
             Rule::new(
                 "oxlint-enable",
                 "oxlint re-enable directive",
                 r"oxlint-enable",
             )?
-            .with_capture(r"oxlint-enable\s+([^*]+)")?,
+            .with_capture(r"oxlint-enable\s+([^*]+)")?
+            .closes_region("oxlint"),
         ],
+        syntax: Some(CommentSyntax::JAVASCRIPT),
     })
 }
 
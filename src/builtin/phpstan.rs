@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -13,7 +15,8 @@ pub fn scout() -> Result<Scout> {
                 "phpstan-ignore-next-line",
                 "PHPStan ignore next line directive",
                 r"@phpstan-ignore-next-line",
-            )?,
+            )?
+            .with_scope(Scope::NextLine),
             Rule::new(
                 "phpstan-ignore-line",
                 "PHPStan ignore current line directive",
@@ -24,8 +27,11 @@ pub fn scout() -> Result<Scout> {
                 "PHPStan ignore directive",
                 r"@phpstan-ignore\s",
             )?
-            .with_capture(r"@phpstan-ignore\s+(.+)")?,
+            .with_capture(r"@phpstan-ignore\s+(.+)")?
+            .with_standalone_scope(Scope::NextLine)
+            .with_justification(r"\(([^)]*)\)")?,
         ],
+        syntax: Some(CommentSyntax::PHP),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -26,7 +28,9 @@ pub fn scout() -> Result<Scout> {
             "prettier-ignore",
             "Prettier ignore directive",
             r"prettier-ignore",
-        )?],
+        )?
+        .with_scope(Scope::NextLine)],
+        syntax: Some(CommentSyntax::POLYGLOT),
     })
 }
 
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -12,16 +14,29 @@ pub fn scout() -> Result<Scout> {
             Rule::new(
                 "pylint-disable",
                 "Pylint disable directive",
-                r"pylint:\s*disable",
+                r"pylint:\s*disable=",
             )?
-            .with_capture(r"pylint:\s*disable=(.+)")?,
+            .with_capture(r"pylint:\s*disable=(.+)")?
+            .opens_region("pylint")
+            .inline_if_trailing()
+            .with_justification(r"\s#\s*(.*)")?,
             Rule::new(
                 "pylint-disable-next",
                 "Pylint disable-next directive",
                 r"pylint:\s*disable-next",
             )?
-            .with_capture(r"pylint:\s*disable-next=(.+)")?,
+            .with_capture(r"pylint:\s*disable-next=(.+)")?
+            .with_scope(Scope::NextLine)
+            .with_justification(r"\s#\s*(.*)")?,
+            Rule::new(
+                "pylint-enable",
+                "Pylint re-enable directive",
+                r"pylint:\s*enable=",
+            )?
+            .with_capture(r"pylint:\s*enable=(.+)")?
+            .closes_region("pylint"),
         ],
+        syntax: Some(CommentSyntax::PYTHON),
     })
 }
 
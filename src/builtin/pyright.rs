@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::rule::Rule;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -13,7 +14,9 @@ pub fn scout() -> Result<Scout> {
             "Pyright ignore directive",
             r"#\s*pyright:\s*ignore",
         )?
-        .with_capture(r"#\s*pyright:\s*ignore\[([^\]]+)\]")?],
+        .with_capture(r"#\s*pyright:\s*ignore\[([^\]]+)\]")?
+        .with_justification(r"\s#\s*(.*)")?],
+        syntax: Some(CommentSyntax::PYTHON),
     })
 }
 
@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::rule::Rule;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -8,12 +9,25 @@ pub fn scout() -> Result<Scout> {
         linter: "rubocop".into(),
         language: "ruby".into(),
         extensions: vec!["rb".into(), "rake".into(), "gemspec".into()],
-        rules: vec![Rule::new(
-            "rubocop-directive",
-            "RuboCop disable/enable/todo directive",
-            r"rubocop:(disable|enable|todo)",
-        )?
-        .with_capture(r"rubocop:(?:disable|enable|todo)\s+(.+)")?],
+        rules: vec![
+            Rule::new(
+                "rubocop-disable",
+                "RuboCop disable/todo directive",
+                r"rubocop:(disable|todo)",
+            )?
+            .with_capture(r"rubocop:(?:disable|todo)\s+(.+)")?
+            .opens_region("rubocop")
+            .inline_if_trailing()
+            .with_justification(r"--\s*(.*)")?,
+            Rule::new(
+                "rubocop-enable",
+                "RuboCop re-enable directive",
+                r"rubocop:enable",
+            )?
+            .with_capture(r"rubocop:enable\s+(.+)")?
+            .closes_region("rubocop"),
+        ],
+        syntax: Some(CommentSyntax::HASH),
     })
 }
 
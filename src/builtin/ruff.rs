@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -10,8 +12,11 @@ pub fn scout() -> Result<Scout> {
         extensions: vec!["py".into()],
         rules: vec![
             Rule::new("ruff-noqa", "Ruff noqa directive", r"#\s*ruff:\s*noqa")?
-                .with_capture(r"#\s*ruff:\s*noqa:\s*(.+)")?,
+                .with_capture(r"#\s*ruff:\s*noqa:\s*(.+)")?
+                .with_scope(Scope::WholeFile)
+                .with_justification(r"\s#\s*(.*)")?,
         ],
+        syntax: Some(CommentSyntax::PYTHON),
     })
 }
 
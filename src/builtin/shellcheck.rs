@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -13,7 +15,10 @@ pub fn scout() -> Result<Scout> {
             "ShellCheck disable directive",
             r"shellcheck\s+disable=",
         )?
-        .with_capture(r"shellcheck\s+disable=(.+)")?],
+        .with_capture(r"shellcheck\s+disable=(.+)")?
+        .with_scope(Scope::NextLine)
+        .with_justification(r"\s#\s*(.*)")?],
+        syntax: Some(CommentSyntax::HASH),
     })
 }
 
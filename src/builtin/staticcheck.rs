@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -8,12 +10,25 @@ pub fn scout() -> Result<Scout> {
         linter: "staticcheck".into(),
         language: "go".into(),
         extensions: vec!["go".into()],
-        rules: vec![Rule::new(
-            "lint-ignore",
-            "staticcheck file-ignore or ignore directive",
-            r"//lint:(file-)?ignore",
-        )?
-        .with_capture(r"//lint:(?:file-)?ignore\s+(\S+)")?],
+        rules: vec![
+            Rule::new(
+                "lint-ignore",
+                "staticcheck ignore directive",
+                r"//lint:ignore",
+            )?
+            .with_capture(r"//lint:ignore\s+(\S+)")?
+            .with_scope(Scope::NextLine)
+            .with_justification(r"//lint:ignore\s+\S+\s+(.*)")?,
+            Rule::new(
+                "lint-file-ignore",
+                "staticcheck file-ignore directive",
+                r"//lint:file-ignore",
+            )?
+            .with_capture(r"//lint:file-ignore\s+(\S+)")?
+            .with_scope(Scope::WholeFile)
+            .with_justification(r"//lint:file-ignore\s+\S+\s+(.*)")?,
+        ],
+        syntax: Some(CommentSyntax::GO),
     })
 }
 
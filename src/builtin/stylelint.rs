@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -19,16 +21,35 @@ pub fn scout() -> Result<Scout> {
             Rule::new(
                 "stylelint-disable",
                 "Stylelint disable directive",
-                r"stylelint-disable",
+                r"stylelint-disable(?:\s|$)",
             )?
-            .with_capture(r"stylelint-disable\s+([^*]+)")?,
+            .with_capture(r"stylelint-disable\s+([^*]+)")?
+            .opens_region("stylelint")
+            .with_justification(r"--\s*(.*)")?,
+            Rule::new(
+                "stylelint-disable-next-line",
+                "Stylelint disable next line",
+                r"stylelint-disable-next-line",
+            )?
+            .with_capture(r"stylelint-disable-next-line\s+([^*]+)")?
+            .with_scope(Scope::NextLine)
+            .with_justification(r"--\s*(.*)")?,
+            Rule::new(
+                "stylelint-disable-line",
+                "Stylelint disable current line",
+                r"stylelint-disable-line",
+            )?
+            .with_capture(r"stylelint-disable-line\s+([^*]+)")?
+            .with_justification(r"--\s*(.*)")?,
             Rule::new(
                 "stylelint-enable",
                 "Stylelint re-enable directive",
                 r"stylelint-enable",
             )?
-            .with_capture(r"stylelint-enable\s+([^*]+)")?,
+            .with_capture(r"stylelint-enable\s+([^*]+)")?
+            .closes_region("stylelint"),
         ],
+        syntax: Some(CommentSyntax::CSS),
     })
 }
 
@@ -49,4 +70,12 @@ mod tests {
             .is_empty());
         assert!(!s.find_matches("/* stylelint-enable */").is_empty());
     }
+
+    #[test]
+    fn line_variants_do_not_open_regions() {
+        let s = scout().unwrap();
+        let m = s.find_matches("/* stylelint-disable-next-line color-named */");
+        assert_eq!(m.len(), 1);
+        assert_eq!(m[0].id, "stylelint-disable-next-line");
+    }
 }
@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -8,12 +10,36 @@ pub fn scout() -> Result<Scout> {
         linter: "swiftlint".into(),
         language: "swift".into(),
         extensions: vec!["swift".into()],
-        rules: vec![Rule::new(
-            "swiftlint-directive",
-            "SwiftLint disable/enable directive",
-            r"swiftlint:(disable|enable)",
-        )?
-        .with_capture(r"swiftlint:(?:disable|enable)\s+(.+)")?],
+        rules: vec![
+            Rule::new(
+                "swiftlint-disable",
+                "SwiftLint disable directive",
+                r"swiftlint:disable(?:\s|$)",
+            )?
+            .with_capture(r"swiftlint:disable\s+(.+)")?
+            .opens_region("swiftlint"),
+            Rule::new(
+                "swiftlint-disable-next-line",
+                "SwiftLint disable:next directive",
+                r"swiftlint:disable:next",
+            )?
+            .with_capture(r"swiftlint:disable:next\s+(.+)")?
+            .with_scope(Scope::NextLine),
+            Rule::new(
+                "swiftlint-disable-line",
+                "SwiftLint disable:this/previous directive",
+                r"swiftlint:disable:(?:this|previous)",
+            )?
+            .with_capture(r"swiftlint:disable:\w+\s+(.+)")?,
+            Rule::new(
+                "swiftlint-enable",
+                "SwiftLint re-enable directive",
+                r"swiftlint:enable(?:\s|$)",
+            )?
+            .with_capture(r"swiftlint:enable\s+(.+)")?
+            .closes_region("swiftlint"),
+        ],
+        syntax: Some(CommentSyntax::SWIFT),
     })
 }
 
@@ -32,4 +58,16 @@ mod tests {
         assert!(!s.find_matches("// swiftlint:disable force_cast").is_empty());
         assert!(!s.find_matches("// swiftlint:enable force_cast").is_empty());
     }
+
+    #[test]
+    fn line_variants_do_not_open_regions() {
+        let s = scout().unwrap();
+        let m = s.find_matches("// swiftlint:disable:next force_cast");
+        assert_eq!(m.len(), 1);
+        assert_eq!(m[0].id, "swiftlint-disable-next-line");
+        assert!(m[0].region.is_none());
+        assert!(s
+            .find_matches("// swiftlint:enable:next force_cast")
+            .is_empty());
+    }
 }
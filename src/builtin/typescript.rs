@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::rule::Rule;
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
 
 pub fn scout() -> Result<Scout> {
     Ok(Scout {
@@ -9,14 +11,21 @@ pub fn scout() -> Result<Scout> {
         language: "typescript".into(),
         extensions: vec!["ts".into(), "tsx".into()],
         rules: vec![
-            Rule::new("ts-ignore", "TypeScript ignore directive", r"@ts-ignore")?,
-            Rule::new("ts-nocheck", "TypeScript nocheck directive", r"@ts-nocheck")?,
+            Rule::new("ts-ignore", "TypeScript ignore directive", r"@ts-ignore")?
+                .with_scope(Scope::NextLine)
+                .with_justification(r"@ts-ignore:?\s*(?:--\s*)?(.+)")?,
+            Rule::new("ts-nocheck", "TypeScript nocheck directive", r"@ts-nocheck")?
+                .with_scope(Scope::WholeFile)
+                .with_justification(r"@ts-nocheck:?\s*(?:--\s*)?(.+)")?,
             Rule::new(
                 "ts-expect-error",
                 "TypeScript expect-error directive",
                 r"@ts-expect-error",
-            )?,
+            )?
+            .with_scope(Scope::NextLine)
+            .with_justification(r"@ts-expect-error:?\s*(?:--\s*)?(.+)")?,
         ],
+        syntax: Some(CommentSyntax::JAVASCRIPT),
     })
 }
 
@@ -0,0 +1,387 @@
+//! Bundled catalog of the rule IDs the builtin linters know, used to flag
+//! directives suppressing unknown rules (usually typos, which suppress
+//! nothing), suggest the closest valid ID, and describe known rules.
+//!
+//! Each rule set is a data file in `data/`: `# key: value` headers, then one
+//! rule per line as `id[,alias...]<TAB>description`, or `FIRST..LAST` for a
+//! range of numbered codes without descriptions.
+//!
+//! | Header      | Meaning                                                    |
+//! |-------------|------------------------------------------------------------|
+//! | `name`      | Name of the rule set                                       |
+//! | `version`   | Upstream versions the rules were taken from                |
+//! | `linters`   | Comma-separated linters whose directives it covers         |
+//! | `prefix`    | Prefix a rule must have to belong to the set, e.g. `@typescript-eslint/` |
+//! | `strip`     | Prefix removed when present, e.g. `clippy::`               |
+//! | `namespace` | Regex of the IDs the set is complete for                   |
+//! | `docs`      | URL template for the following rules; `{id}` is the first alias, `{name}` the last |
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Version of the bundled catalog.
+pub const VERSION: &str = "2026.10";
+
+const DATA: [&str; 8] = [
+    include_str!("data/eslint.tsv"),
+    include_str!("data/typescript-eslint.tsv"),
+    include_str!("data/pylint.tsv"),
+    include_str!("data/pycodestyle.tsv"),
+    include_str!("data/clippy.tsv"),
+    include_str!("data/shellcheck.tsv"),
+    include_str!("data/hadolint.tsv"),
+    include_str!("data/golangci.tsv"),
+];
+
+/// What the catalog knows about a suppressed rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleDetail {
+    pub rule: String,
+    /// Whether the linter has a rule with this ID.
+    pub known: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+    /// The closest known ID, for unknown rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+struct Entry {
+    description: Option<String>,
+    docs_url: Option<String>,
+}
+
+/// The rule IDs of one rule set, e.g. ESLint core rules or ShellCheck codes.
+pub struct RuleSet {
+    pub name: String,
+    pub version: String,
+    linters: Vec<String>,
+    prefix: Option<String>,
+    strip: Option<String>,
+    namespace: Regex,
+    entries: Vec<Entry>,
+    /// Entry index by ID and alias.
+    index: HashMap<String, usize>,
+}
+
+impl RuleSet {
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut headers: HashMap<&str, &str> = HashMap::new();
+        let mut docs: Option<&str> = None;
+        let mut entries = Vec::new();
+        let mut index = HashMap::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some((key, value)) = comment.trim().split_once(": ") {
+                    if key == "docs" {
+                        docs = Some(value.trim());
+                    } else {
+                        headers.insert(key, value.trim());
+                    }
+                }
+                continue;
+            }
+            let (ids, description) = match line.split_once('\t') {
+                Some((ids, description)) => (ids, Some(description.trim().to_string())),
+                None => (line, None),
+            };
+            let ids: Vec<String> = match ids.split_once("..") {
+                Some((first, last)) => expand_range(first, last)
+                    .ok_or_else(|| format!("line {}: invalid range '{ids}'", number + 1))?,
+                None => vec![ids.to_string()],
+            };
+            for id in ids {
+                let aliases: Vec<&str> = id.split(',').collect();
+                let docs_url = docs.map(|t| {
+                    t.replace("{id}", aliases[0])
+                        .replace("{name}", aliases[aliases.len() - 1])
+                });
+                let entry = Entry {
+                    description: description.clone(),
+                    docs_url,
+                };
+                // A described entry replaces one from a range
+                let slot = match index.get(aliases[0]) {
+                    Some(&slot) => {
+                        entries[slot] = entry;
+                        slot
+                    }
+                    None => {
+                        entries.push(entry);
+                        entries.len() - 1
+                    }
+                };
+                for alias in aliases {
+                    index.insert(alias.to_string(), slot);
+                }
+            }
+        }
+
+        let header = |key: &str| {
+            headers
+                .get(key)
+                .map(|v| v.to_string())
+                .ok_or_else(|| format!("missing '{key}' header"))
+        };
+        let namespace = header("namespace")?;
+        Ok(RuleSet {
+            name: header("name")?,
+            version: header("version")?,
+            linters: header("linters")?
+                .split(',')
+                .map(|l| l.trim().to_string())
+                .collect(),
+            prefix: headers.get("prefix").map(|p| p.to_string()),
+            strip: headers.get("strip").map(|p| p.to_string()),
+            namespace: Regex::new(&namespace).map_err(|e| e.to_string())?,
+            entries,
+            index,
+        })
+    }
+
+    /// The rule's ID within the set, if the set covers it.
+    fn local<'a>(&self, rule: &'a str) -> Option<&'a str> {
+        let mut rule = match self.prefix {
+            Some(ref prefix) => rule.strip_prefix(prefix.as_str())?,
+            None => rule,
+        };
+        if let Some(ref strip) = self.strip {
+            rule = rule.strip_prefix(strip.as_str()).unwrap_or(rule);
+        }
+        self.namespace.is_match(rule).then_some(rule)
+    }
+
+    /// The closest ID to an unknown one, with its edit distance.
+    fn closest(&self, rule: &str) -> Option<(usize, String)> {
+        let limit = (rule.chars().count() / 3).max(1);
+        self.index
+            .keys()
+            .map(|id| (edit_distance(rule, id), id))
+            .filter(|(distance, _)| *distance <= limit)
+            .min()
+            .map(|(distance, id)| {
+                let id = match self.prefix {
+                    Some(ref prefix) => format!("{prefix}{id}"),
+                    None => id.clone(),
+                };
+                (distance, id)
+            })
+    }
+}
+
+/// The bundled rule sets.
+pub struct Catalog {
+    pub sets: Vec<RuleSet>,
+}
+
+impl Catalog {
+    pub fn builtin() -> &'static Catalog {
+        static CATALOG: OnceLock<Catalog> = OnceLock::new();
+        CATALOG.get_or_init(|| Catalog {
+            sets: DATA
+                .iter()
+                .map(|text| RuleSet::parse(text).expect("valid bundled rule catalog"))
+                .collect(),
+        })
+    }
+
+    /// What the catalog knows about a rule suppressed by a directive of
+    /// `linter`; `None` when no rule set covers it.
+    pub fn lookup(&self, linter: &str, rule: &str) -> Option<RuleDetail> {
+        let mut covered = false;
+        let mut suggestion: Option<(usize, String)> = None;
+        for set in self
+            .sets
+            .iter()
+            .filter(|s| s.linters.iter().any(|l| l == linter))
+        {
+            let Some(local) = set.local(rule) else {
+                continue;
+            };
+            covered = true;
+            if let Some(&slot) = set.index.get(local) {
+                let entry = &set.entries[slot];
+                return Some(RuleDetail {
+                    rule: rule.to_string(),
+                    known: true,
+                    description: entry.description.clone(),
+                    docs_url: entry.docs_url.clone(),
+                    suggestion: None,
+                });
+            }
+            if let Some(closest) = set.closest(local) {
+                if suggestion.as_ref().map_or(true, |s| closest.0 < s.0) {
+                    suggestion = Some(closest);
+                }
+            }
+        }
+        covered.then(|| RuleDetail {
+            rule: rule.to_string(),
+            known: false,
+            description: None,
+            docs_url: None,
+            suggestion: suggestion.map(|(_, id)| id),
+        })
+    }
+
+    /// Details of the rules a directive of `linter` suppresses.
+    pub fn details(&self, linter: &str, rules: &[String]) -> Vec<RuleDetail> {
+        rules
+            .iter()
+            .filter_map(|rule| self.lookup(linter, rule))
+            .collect()
+    }
+}
+
+/// Expand `SC2000..SC2010` into every code in between, keeping the width.
+fn expand_range(first: &str, last: &str) -> Option<Vec<String>> {
+    let digits = first.find(|c: char| c.is_ascii_digit())?;
+    let (prefix, start) = first.split_at(digits);
+    let end = last.strip_prefix(prefix)?;
+    let width = start.len();
+    let (start, end): (u32, u32) = (start.parse().ok()?, end.parse().ok()?);
+    Some(
+        (start..=end)
+            .map(|n| format!("{prefix}{n:0width$}"))
+            .collect(),
+    )
+}
+
+/// Optimal string alignment distance: edits, counting a swap of adjacent
+/// characters as one.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(linter: &str, rule: &str) -> Option<RuleDetail> {
+        Catalog::builtin().lookup(linter, rule)
+    }
+
+    fn suggestion(linter: &str, rule: &str) -> Option<String> {
+        let detail = lookup(linter, rule).expect("covered rule");
+        assert!(!detail.known, "{rule} should be unknown");
+        detail.suggestion
+    }
+
+    #[test]
+    fn bundled_sets_parse() {
+        let catalog = Catalog::builtin();
+        assert_eq!(catalog.sets.len(), DATA.len());
+        for set in &catalog.sets {
+            assert!(!set.entries.is_empty(), "{} is empty", set.name);
+            for id in set.index.keys() {
+                assert!(
+                    set.local(&format!("{}{id}", set.prefix.as_deref().unwrap_or("")))
+                        .is_some(),
+                    "{id} lies outside the {} namespace",
+                    set.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn describes_known_rules() {
+        let detail = lookup("eslint", "no-unused-vars").unwrap();
+        assert!(detail.known);
+        assert_eq!(
+            detail.docs_url.as_deref(),
+            Some("https://eslint.org/docs/latest/rules/no-unused-vars")
+        );
+        assert!(detail.description.is_some());
+
+        let detail = lookup("eslint", "@typescript-eslint/no-explicit-any").unwrap();
+        assert_eq!(
+            detail.docs_url.as_deref(),
+            Some("https://typescript-eslint.io/rules/no-explicit-any")
+        );
+        let detail = lookup("pylint", "C0114").unwrap();
+        assert_eq!(
+            detail.docs_url.as_deref(),
+            Some("https://pylint.readthedocs.io/en/stable/user_guide/messages/convention/missing-module-docstring.html")
+        );
+        assert!(lookup("pylint", "missing-module-docstring").unwrap().known);
+        assert!(lookup("clippy", "clippy::needless_return").unwrap().known);
+        assert!(lookup("clippy", "too_many_arguments").unwrap().known);
+        assert!(lookup("shellcheck", "SC2086").unwrap().known);
+        assert!(lookup("shellcheck", "SC2317").unwrap().known);
+        assert!(lookup("hadolint", "DL3008").unwrap().known);
+        assert!(lookup("golangci-lint", "errcheck").unwrap().known);
+        assert!(lookup("ruff", "E501").unwrap().known);
+    }
+
+    #[test]
+    fn suggests_closest_rule() {
+        assert_eq!(suggestion("flake8", "E50l").as_deref(), Some("E501"));
+        assert_eq!(
+            suggestion("eslint", "no-unused-var").as_deref(),
+            Some("no-unused-vars")
+        );
+        assert_eq!(
+            suggestion("eslint", "@typescript-eslint/no-explicit-nay").as_deref(),
+            Some("@typescript-eslint/no-explicit-any")
+        );
+        assert_eq!(
+            suggestion("pylint", "missing-docstrin").as_deref(),
+            Some("missing-docstring")
+        );
+        assert_eq!(
+            suggestion("golangci-lint", "errchek").as_deref(),
+            Some("errcheck")
+        );
+        assert_eq!(suggestion("eslint", "qqqqqqqqqqqqqqq"), None);
+    }
+
+    #[test]
+    fn ignores_uncovered_rules() {
+        // Plugin rules and codes of flake8 plugins are not catalogued
+        assert!(lookup("eslint", "react/jsx-key").is_none());
+        assert!(lookup("flake8", "B008").is_none());
+        assert!(lookup("flake8", "because").is_none());
+        assert!(lookup("rubocop", "Style/Foo").is_none());
+    }
+
+    #[test]
+    fn ranges_and_distance() {
+        assert_eq!(
+            expand_range("DL3000", "DL3002").unwrap(),
+            ["DL3000", "DL3001", "DL3002"]
+        );
+        assert_eq!(edit_distance("E501", "E510"), 1);
+        assert_eq!(edit_distance("no-var", "no-vars"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+
+use crate::date;
+use crate::history::Interval;
+use crate::scope::Scope;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -8,7 +12,11 @@ use clap::Parser;
     about = "Detect linter ignore directives in source code"
 )]
 pub struct Cli {
-    /// Path to scan
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path to scan; a directory named like a subcommand must be written
+    /// as ./<dir>, e.g. ./baseline
     #[arg(default_value = ".")]
     pub path: PathBuf,
 
@@ -17,30 +25,196 @@ pub struct Cli {
     pub format: String,
 
     /// Path to config file
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub config: Option<PathBuf>,
 
     /// Maximum allowed findings before non-zero exit
     #[arg(long)]
     pub pass_threshold: Option<u64>,
 
+    /// Maximum allowed blanket suppressions before non-zero exit
+    #[arg(long)]
+    pub blanket_threshold: Option<u64>,
+
+    /// Maximum allowed suppressions per 1000 non-blank lines before non-zero
+    /// exit
+    #[arg(long, value_name = "PER_KLOC")]
+    pub density_threshold: Option<f64>,
+
+    /// Fail on any blanket suppression (same as --blanket-threshold 0)
+    #[arg(long, conflicts_with = "blanket_threshold")]
+    pub fail_on_blanket: bool,
+
+    /// Fail on suppressions without an acceptable justification
+    #[arg(long)]
+    pub require_justification: bool,
+
+    /// Fail on directives suppressing rule IDs missing from the rule catalog
+    #[arg(long)]
+    pub fail_on_unknown_rules: bool,
+
+    /// Fail on directives of linters not configured in the scanned tree
+    #[arg(long)]
+    pub fail_on_orphaned: bool,
+
+    /// Scan the content staged in the git index instead of the working tree
+    #[arg(long, conflicts_with_all = ["diff_base", "diff_file"])]
+    pub staged: bool,
+
+    /// Annotate findings with git blame and apply age policies
+    #[arg(long)]
+    pub blame: bool,
+
+    /// Scan the tree of a git revision, e.g. a release tag, without checking
+    /// it out
+    #[arg(
+        long,
+        value_name = "REV",
+        conflicts_with_all = ["staged", "diff_base", "diff_file"]
+    )]
+    pub rev: Option<String>,
+
+    /// Only report suppressions on lines added since this git ref, and list
+    /// those removed
+    #[arg(long, value_name = "REF")]
+    pub diff_base: Option<String>,
+
+    /// Like --diff-base, but read a unified diff from a file ('-' for stdin)
+    #[arg(long, value_name = "FILE", conflicts_with = "diff_base")]
+    pub diff_file: Option<PathBuf>,
+
+    /// Only report findings not recorded in this baseline file
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
     /// Only run these scouts (comma-separated)
-    #[arg(long, value_delimiter = ',')]
+    #[arg(long, global = true, value_delimiter = ',')]
     pub scouts: Option<Vec<String>>,
 
     /// Exclude these scouts (comma-separated)
-    #[arg(long, value_delimiter = ',')]
+    #[arg(long, global = true, value_delimiter = ',')]
     pub exclude_scouts: Option<Vec<String>>,
 
+    /// Only report suppressions with these scopes (comma-separated)
+    #[arg(long, global = true, value_enum, value_delimiter = ',')]
+    pub scope: Option<Vec<Scope>>,
+
     /// Exclude these paths (comma-separated)
-    #[arg(long, value_delimiter = ',')]
+    #[arg(long, global = true, value_delimiter = ',')]
     pub exclude: Option<Vec<String>>,
 
     /// Do not respect .gitignore files
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub no_gitignore: bool,
 
     /// Suppress non-essential output
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub quiet: bool,
 }
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Record the current findings in a baseline file
+    Baseline {
+        /// Path to scan
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Baseline file to write
+        #[arg(long, value_name = "FILE")]
+        write: PathBuf,
+    },
+
+    /// Install a git pre-commit hook that runs `lintscout --staged`
+    InstallHook {
+        /// Replace an existing pre-commit hook
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Compare two reports saved with `--format json`
+    Compare {
+        /// The older report
+        old: PathBuf,
+
+        /// The newer report
+        new: PathBuf,
+
+        /// Output format: text, json, or markdown
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Rank files and directories by suppressions weighted by churn and size
+    Hotspots {
+        /// Path to scan
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Only count commits made since this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE", value_parser = parse_date)]
+        since: Option<i64>,
+
+        /// Number of files and directories to list
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Output format: text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Lower the thresholds in the config file that the codebase now beats
+    Ratchet {
+        /// Path to scan
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Only report, without editing the config file (the default when
+        /// the CI environment variable is set)
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Emit a time series of suppression counts across git history
+    History {
+        /// Path to scan
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// First date to sample (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE", value_parser = parse_date)]
+        since: i64,
+
+        /// Last date to sample (YYYY-MM-DD, default today)
+        #[arg(long, value_name = "DATE", value_parser = parse_date)]
+        until: Option<i64>,
+
+        /// Sampling interval
+        #[arg(long, value_enum, default_value = "week")]
+        every: Interval,
+
+        /// Branch whose first-parent history is sampled
+        #[arg(long, default_value = "HEAD")]
+        branch: String,
+
+        /// Output format: csv or json
+        #[arg(long, default_value = "csv")]
+        format: String,
+    },
+}
+
+fn parse_date(value: &str) -> Result<i64, String> {
+    date::parse_date(value).ok_or_else(|| format!("expected a YYYY-MM-DD date, got '{value}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+}
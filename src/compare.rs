@@ -0,0 +1,401 @@
+//! Comparison of two saved JSON reports.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::{LintScoutError, Result};
+use crate::finding::Finding;
+use crate::fingerprint;
+use crate::scanner::ScanResult;
+
+/// A suppression present in both reports at a different location.
+#[derive(Debug, Clone, Serialize)]
+pub struct Moved {
+    pub from_path: String,
+    pub from_line: usize,
+    /// The finding at its new location.
+    pub finding: Finding,
+}
+
+/// Finding counts of one scout in both reports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScoutDelta {
+    pub scout_name: String,
+    pub old: u64,
+    pub new: u64,
+    pub delta: i64,
+}
+
+/// How the findings of a newer report differ from an older one.
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    pub old_count: u64,
+    pub new_count: u64,
+    /// `new_count - old_count`.
+    pub net: i64,
+    pub unchanged: u64,
+    pub added: Vec<Finding>,
+    pub removed: Vec<Finding>,
+    pub moved: Vec<Moved>,
+    /// Scouts whose count changed, by name.
+    pub scouts: Vec<ScoutDelta>,
+}
+
+/// Load a report written by `--format json`.
+pub fn load(path: &Path) -> Result<ScanResult> {
+    let content = fs::read_to_string(path).map_err(|e| LintScoutError::FileRead {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    serde_json::from_str(&content).map_err(|e| LintScoutError::ReportLoad {
+        path: path.to_path_buf(),
+        source: Box::new(e),
+    })
+}
+
+impl Comparison {
+    /// Match findings by fingerprint, then pair what is left by scout, rule
+    /// and line text so suppressions whose surroundings or file name changed
+    /// still count as moved. Each finding matches at most once.
+    pub fn new(old: &ScanResult, new: &ScanResult) -> Self {
+        let mut old_left: Vec<Option<&Finding>> = old.findings.iter().map(Some).collect();
+        let mut pairs: Vec<(&Finding, &Finding)> = Vec::new();
+        let mut added = Vec::new();
+
+        let mut by_fingerprint: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, f) in old.findings.iter().enumerate().rev() {
+            if !f.fingerprint.is_empty() {
+                by_fingerprint.entry(&f.fingerprint).or_default().push(i);
+            }
+        }
+        let mut unmatched = Vec::new();
+        for f in &new.findings {
+            let index = by_fingerprint
+                .get_mut(f.fingerprint.as_str())
+                .and_then(|indexes| indexes.pop());
+            match index.and_then(|i| old_left[i].take()) {
+                Some(old) => pairs.push((old, f)),
+                None => unmatched.push(f),
+            }
+        }
+
+        let mut by_text: HashMap<(&str, &str, String), Vec<usize>> = HashMap::new();
+        for (i, f) in old_left.iter().enumerate().rev() {
+            if let Some(f) = f {
+                by_text.entry(text_key(f)).or_default().push(i);
+            }
+        }
+        for f in unmatched {
+            let index = by_text
+                .get_mut(&text_key(f))
+                .and_then(|indexes| indexes.pop());
+            match index.and_then(|i| old_left[i].take()) {
+                Some(old) => pairs.push((old, f)),
+                None => added.push(f.clone()),
+            }
+        }
+
+        let mut removed: Vec<Finding> = old_left.into_iter().flatten().cloned().collect();
+        let mut moved = Vec::new();
+        let mut unchanged = 0;
+        for (old, new) in pairs {
+            if old.path == new.path && old.line_number == new.line_number {
+                unchanged += 1;
+            } else {
+                moved.push(Moved {
+                    from_path: old.path.clone(),
+                    from_line: old.line_number,
+                    finding: new.clone(),
+                });
+            }
+        }
+        added.sort_by(location);
+        removed.sort_by(location);
+        moved.sort_by(|a, b| location(&a.finding, &b.finding));
+
+        let mut counts: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+        for f in &old.findings {
+            counts.entry(&f.scout_name).or_default().0 += 1;
+        }
+        for f in &new.findings {
+            counts.entry(&f.scout_name).or_default().1 += 1;
+        }
+        let scouts = counts
+            .into_iter()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(name, (old, new))| ScoutDelta {
+                scout_name: name.to_string(),
+                old,
+                new,
+                delta: new as i64 - old as i64,
+            })
+            .collect();
+
+        let old_count = old.findings.len() as u64;
+        let new_count = new.findings.len() as u64;
+        Comparison {
+            old_count,
+            new_count,
+            net: new_count as i64 - old_count as i64,
+            unchanged,
+            added,
+            removed,
+            moved,
+            scouts,
+        }
+    }
+
+    /// Format the comparison as `text`, `json` or `markdown`.
+    pub fn format(&self, format: &str) -> String {
+        match format {
+            "json" => serde_json::to_string_pretty(self)
+                .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+            "markdown" => self.to_markdown(),
+            _ => self.to_text(),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = format!(
+            "Findings: {} -> {} ({})\n",
+            self.old_count,
+            self.new_count,
+            signed(self.net)
+        );
+        out.push_str(&format!("Added: {}\n", self.added.len()));
+        for f in &self.added {
+            out.push_str(&format!("  + {}\n", describe(f)));
+        }
+        out.push_str(&format!("Removed: {}\n", self.removed.len()));
+        for f in &self.removed {
+            out.push_str(&format!("  - {}\n", describe(f)));
+        }
+        out.push_str(&format!("Moved: {}\n", self.moved.len()));
+        for m in &self.moved {
+            out.push_str(&format!(
+                "  ~ {}:{} -> {}\n",
+                m.from_path,
+                m.from_line,
+                describe(&m.finding)
+            ));
+        }
+        out.push_str(&format!("Unchanged: {}\n", self.unchanged));
+        if !self.scouts.is_empty() {
+            out.push_str("By scout:\n");
+            for s in &self.scouts {
+                out.push_str(&format!(
+                    "  {}: {} -> {} ({})\n",
+                    s.scout_name,
+                    s.old,
+                    s.new,
+                    signed(s.delta)
+                ));
+            }
+        }
+        out
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::from("## LintScout comparison\n\n");
+        out.push_str("| | Count |\n|---|---:|\n");
+        out.push_str(&format!(
+            "| Findings | {} → {} ({}) |\n",
+            self.old_count,
+            self.new_count,
+            signed(self.net)
+        ));
+        out.push_str(&format!("| Added | {} |\n", self.added.len()));
+        out.push_str(&format!("| Removed | {} |\n", self.removed.len()));
+        out.push_str(&format!("| Moved | {} |\n", self.moved.len()));
+        out.push_str(&format!("| Unchanged | {} |\n", self.unchanged));
+
+        if !self.scouts.is_empty() {
+            out.push_str("\n### By scout\n\n| Scout | Old | New | Delta |\n|---|---:|---:|---:|\n");
+            for s in &self.scouts {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    s.scout_name,
+                    s.old,
+                    s.new,
+                    signed(s.delta)
+                ));
+            }
+        }
+
+        let mut section = |title: &str, lines: Vec<String>| {
+            if !lines.is_empty() {
+                out.push_str(&format!("\n### {title}\n\n"));
+                for line in lines {
+                    out.push_str(&format!("- {line}\n"));
+                }
+            }
+        };
+        section("Added", self.added.iter().map(describe_markdown).collect());
+        section(
+            "Removed",
+            self.removed.iter().map(describe_markdown).collect(),
+        );
+        section(
+            "Moved",
+            self.moved
+                .iter()
+                .map(|m| {
+                    format!(
+                        "`{}:{}` → {}",
+                        m.from_path,
+                        m.from_line,
+                        describe_markdown(&m.finding)
+                    )
+                })
+                .collect(),
+        );
+        out
+    }
+}
+
+fn text_key(f: &Finding) -> (&str, &str, String) {
+    (
+        &f.scout_name,
+        &f.rule_id,
+        fingerprint::normalize(&f.line_text),
+    )
+}
+
+fn location(a: &Finding, b: &Finding) -> std::cmp::Ordering {
+    a.path
+        .cmp(&b.path)
+        .then(a.line_number.cmp(&b.line_number))
+        .then(a.rule_id.cmp(&b.rule_id))
+}
+
+fn signed(n: i64) -> String {
+    if n > 0 {
+        format!("+{n}")
+    } else {
+        n.to_string()
+    }
+}
+
+fn describe(f: &Finding) -> String {
+    format!(
+        "{}:{} [{}:{}] {}",
+        f.path,
+        f.line_number,
+        f.scout_name,
+        f.rule_id,
+        f.line_text.trim()
+    )
+}
+
+fn describe_markdown(f: &Finding) -> String {
+    let text = f.line_text.trim();
+    // A code span needs a longer run of backticks than it contains
+    let fence = if text.contains('`') { "``" } else { "`" };
+    format!(
+        "`{}:{}` **{}** {fence} {text} {fence}",
+        f.path, f.line_number, f.rule_id
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scope::Scope;
+
+    fn finding(path: &str, line_number: usize, text: &str, fingerprint: &str) -> Finding {
+        Finding {
+            path: path.into(),
+            line_number,
+            line_text: text.into(),
+            scout_name: "flake8".into(),
+            linter: "flake8".into(),
+            rule_id: "flake8-noqa".into(),
+            fingerprint: fingerprint.into(),
+            ..Default::default()
+        }
+    }
+
+    fn result(findings: Vec<Finding>) -> ScanResult {
+        ScanResult {
+            findings,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn classifies_findings() {
+        let old = result(vec![
+            finding("a.py", 1, "import os  # noqa", "1"),
+            finding("a.py", 5, "import sys  # noqa", "2"),
+            finding("b.py", 3, "x = 1  # noqa", "3"),
+            finding("c.py", 2, "y = 2  # noqa", "4"),
+        ]);
+        let new = result(vec![
+            finding("a.py", 1, "import os  # noqa", "1"),
+            // Shifted down by an edit above
+            finding("a.py", 8, "import sys  # noqa", "2"),
+            // Renamed, with a different fingerprint
+            finding("d.py", 3, "x = 1   # noqa", "9"),
+            finding("e.py", 1, "z = 3  # noqa", "5"),
+            finding("e.py", 2, "w = 4  # noqa", "6"),
+        ]);
+        let c = Comparison::new(&old, &new);
+        assert_eq!(c.net, 1);
+        assert_eq!(c.unchanged, 1);
+        assert_eq!(c.added.len(), 2);
+        assert_eq!(c.removed.len(), 1);
+        assert_eq!(c.removed[0].path, "c.py");
+        let moved: Vec<_> = c
+            .moved
+            .iter()
+            .map(|m| (m.from_path.as_str(), m.finding.path.as_str()))
+            .collect();
+        assert_eq!(moved, [("a.py", "a.py"), ("b.py", "d.py")]);
+        assert_eq!(
+            c.scouts,
+            [ScoutDelta {
+                scout_name: "flake8".into(),
+                old: 4,
+                new: 5,
+                delta: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn duplicates_match_once() {
+        let old = result(vec![finding("a.py", 1, "x  # noqa", "1")]);
+        let new = result(vec![
+            finding("a.py", 1, "x  # noqa", "1"),
+            finding("a.py", 1, "x  # noqa", "1"),
+        ]);
+        let c = Comparison::new(&old, &new);
+        assert_eq!((c.unchanged, c.added.len()), (1, 1));
+    }
+
+    #[test]
+    fn formats_text_and_markdown() {
+        let old = result(vec![finding("a.py", 1, "x  # noqa", "1")]);
+        let new = result(Vec::new());
+        let c = Comparison::new(&old, &new);
+        let text = c.format("text");
+        assert!(text.starts_with("Findings: 1 -> 0 (-1)\n"));
+        assert!(text.contains("  - a.py:1 [flake8:flake8-noqa] x  # noqa\n"));
+        assert!(text.contains("  flake8: 1 -> 0 (-1)\n"));
+        let markdown = c.format("markdown");
+        assert!(markdown.contains("| flake8 | 1 | 0 | -1 |\n"));
+        assert!(markdown.contains("### Removed\n\n- `a.py:1` **flake8-noqa** ` x  # noqa `\n"));
+    }
+
+    #[test]
+    fn reports_round_trip() {
+        let report = result(vec![finding("a.py", 1, "x  # noqa", "1")]);
+        let json = crate::output::json::format(&report);
+        let loaded: ScanResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.findings[0].fingerprint, "1");
+        assert_eq!(loaded.findings[0].scope, Scope::SameLine);
+    }
+}
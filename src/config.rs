@@ -1,10 +1,16 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::error::{LintScoutError, Result};
-use crate::rule::Rule;
+use crate::rule::{Placement, Rule};
+use crate::scope::Scope;
 use crate::scout::Scout;
+use crate::syntax::CommentSyntax;
+
+/// Names of config files, in order of preference.
+pub const FILE_NAMES: [&str; 2] = [".lintscout.yml", "lintscout.yml"];
 
 #[derive(Debug, Default, Deserialize)]
 pub struct Config {
@@ -12,6 +18,9 @@ pub struct Config {
     pub settings: Settings,
     #[serde(default)]
     pub scouts: Vec<CustomScoutConfig>,
+    /// Rule policies by linter or scout name.
+    #[serde(default)]
+    pub policy: BTreeMap<String, RulePolicyConfig>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,8 +33,37 @@ pub struct Settings {
     pub output: String,
     #[serde(default)]
     pub pass_threshold: Option<u64>,
+    /// Maximum allowed blanket suppressions, checked separately from
+    /// `pass_threshold`.
+    #[serde(default)]
+    pub blanket_threshold: Option<u64>,
+    /// Maximum allowed suppressions per 1000 non-blank lines. When set
+    /// without `pass_threshold`, it replaces the default of failing on any
+    /// finding.
+    #[serde(default)]
+    pub density_threshold: Option<f64>,
     #[serde(default)]
     pub disable: DisableConfig,
+    #[serde(default)]
+    pub justification: JustificationConfig,
+    /// Annotate findings with the author, commit and date from `git blame`.
+    #[serde(default)]
+    pub blame: bool,
+    /// Policies on the age of suppressions, evaluated when blaming.
+    #[serde(default)]
+    pub age_policies: Vec<AgePolicyConfig>,
+    /// Maximum suppressions under path globs, checked after the scan.
+    #[serde(default)]
+    pub budgets: Vec<BudgetConfig>,
+    #[serde(default)]
+    pub expiry: ExpiryConfig,
+    /// Fail on directives suppressing rule IDs missing from the bundled
+    /// rule catalog.
+    #[serde(default)]
+    pub fail_on_unknown_rules: bool,
+    /// Fail on directives of linters not configured in the scanned tree.
+    #[serde(default)]
+    pub fail_on_orphaned: bool,
 }
 
 impl Default for Settings {
@@ -35,17 +73,156 @@ impl Default for Settings {
             respect_gitignore: true,
             output: default_output(),
             pass_threshold: None,
+            blanket_threshold: None,
+            density_threshold: None,
             disable: DisableConfig::default(),
+            justification: JustificationConfig::default(),
+            blame: false,
+            age_policies: Vec::new(),
+            budgets: Vec::new(),
+            expiry: ExpiryConfig::default(),
+            fail_on_unknown_rules: false,
+            fail_on_orphaned: false,
         }
     }
 }
 
+/// A config file below the scan root, which applies to its subtree. Keys it
+/// does not support are an error rather than silently ignored.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubtreeConfig {
+    #[serde(default)]
+    pub settings: SubtreeSettings,
+    /// Custom scouts added in the subtree.
+    #[serde(default)]
+    pub scouts: Vec<CustomScoutConfig>,
+}
+
+/// The settings a nested config can change in its subtree. Unset ones are
+/// inherited from the configs above it.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubtreeSettings {
+    /// Exclude patterns relative to the config's directory, replacing the
+    /// inherited ones.
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    #[serde(default)]
+    pub pass_threshold: Option<u64>,
+    #[serde(default)]
+    pub blanket_threshold: Option<u64>,
+    #[serde(default)]
+    pub density_threshold: Option<f64>,
+    /// Scouts disabled in the subtree, on top of those disabled above it.
+    #[serde(default)]
+    pub disable: DisableConfig,
+}
+
 #[derive(Debug, Default, Deserialize)]
 pub struct DisableConfig {
     #[serde(default)]
     pub scouts: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct JustificationConfig {
+    /// Fail when a suppression has no acceptable justification.
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default = "default_min_length")]
+    pub min_length: usize,
+    /// Phrases that do not count as a justification, e.g. "temp" or
+    /// "fix later". Matched case-insensitively on word boundaries.
+    #[serde(default)]
+    pub forbidden_phrases: Vec<String>,
+}
+
+impl Default for JustificationConfig {
+    fn default() -> Self {
+        JustificationConfig {
+            required: false,
+            min_length: default_min_length(),
+            forbidden_phrases: Vec::new(),
+        }
+    }
+}
+
+/// How expiry markers are recognized and enforced. `until YYYY-MM-DD` and
+/// `TODO(TICKET)` are always recognized; expired suppressions always fail.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExpiryConfig {
+    /// Regex with named groups `expires` (a `YYYY-MM-DD` date) and/or
+    /// `ticket`, tried before the built-in markers.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Fail suppressions naming neither a ticket reference nor an expiry date.
+    #[serde(default)]
+    pub require_ticket: bool,
+    /// Pattern ticket references must match in full, e.g. `(JIRA|OPS)-\d+`.
+    #[serde(default)]
+    pub ticket_pattern: Option<String>,
+}
+
+/// A limit on the suppressions under a path, e.g. `legacy/` or
+/// `services/payments/**`, matched like a `.gitignore` pattern relative to
+/// the scan root. Without a path, the budget covers every file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BudgetConfig {
+    #[serde(default = "default_budget_path")]
+    pub path: String,
+    /// Only count findings of these scouts.
+    #[serde(default)]
+    pub scouts: Option<Vec<String>>,
+    pub max: u64,
+}
+
+/// Which rules a linter's directives may suppress. Rule names may use `*`
+/// wildcards, e.g. `security/*`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RulePolicyConfig {
+    /// Rules whose suppression always fails, even when also allowed. Blanket
+    /// directives suppress every denied rule.
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Rules whose suppression is harmless; directives suppressing only
+    /// these are not reported.
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Maximum number of suppressions per rule (or wildcard pattern).
+    #[serde(default)]
+    pub quota: BTreeMap<String, u64>,
+}
+
+/// Flags suppressions by age, e.g. unjustified ones younger than a week or
+/// any older than two years.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgePolicyConfig {
+    pub name: String,
+    /// Match suppressions last changed fewer than this many days ago.
+    #[serde(default)]
+    pub younger_than_days: Option<u64>,
+    /// Match suppressions last changed more than this many days ago.
+    #[serde(default)]
+    pub older_than_days: Option<u64>,
+    /// Only match suppressions failing the justification policy.
+    #[serde(default)]
+    pub unjustified: bool,
+    #[serde(default)]
+    pub action: AgeAction,
+}
+
+/// What happens to a suppression matched by an age policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgeAction {
+    /// List it for follow-up.
+    #[default]
+    Report,
+    /// Fail the run.
+    Fail,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CustomScoutConfig {
     pub name: String,
@@ -54,6 +231,10 @@ pub struct CustomScoutConfig {
     #[serde(default = "default_custom")]
     pub language: String,
     pub extensions: Vec<String>,
+    /// Comment syntax preset (e.g. `c`, `python`, `hash`). When unset, rules
+    /// match anywhere on the line.
+    #[serde(default)]
+    pub syntax: Option<String>,
     pub rules: Vec<CustomRuleConfig>,
 }
 
@@ -63,6 +244,11 @@ pub struct CustomRuleConfig {
     pub description: String,
     pub pattern: String,
     pub capture_pattern: Option<String>,
+    pub justification_pattern: Option<String>,
+    #[serde(default)]
+    pub placement: Placement,
+    #[serde(default)]
+    pub scope: Scope,
 }
 
 fn default_excludes() -> Vec<String> {
@@ -82,6 +268,14 @@ fn default_output() -> String {
     "text".into()
 }
 
+fn default_min_length() -> usize {
+    1
+}
+
+fn default_budget_path() -> String {
+    "*".into()
+}
+
 fn default_custom() -> String {
     "custom".into()
 }
@@ -102,63 +296,142 @@ impl Config {
     }
 
     pub fn find_and_load() -> Option<Result<Config>> {
-        let candidates = [".lintscout.yml", "lintscout.yml"];
-        for name in &candidates {
-            let path = PathBuf::from(name);
-            if path.exists() {
-                return Some(Config::load(&path));
-            }
-        }
-        None
+        Config::find().map(|path| Config::load(&path))
+    }
+
+    /// The config file in the current directory or the nearest directory
+    /// above it, up to the root of the git repository, if any.
+    pub fn find() -> Option<PathBuf> {
+        let cwd = std::env::current_dir().ok()?;
+        // Outside a repository, only the current directory is searched
+        let depth = cwd
+            .ancestors()
+            .position(|d| d.join(".git").exists())
+            .unwrap_or(0);
+        (0..=depth).find_map(|up| {
+            let dir: PathBuf = std::iter::repeat("..").take(up).collect();
+            FILE_NAMES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.exists())
+        })
     }
 
     fn validate(&self) -> Result<()> {
-        for scout_cfg in &self.scouts {
-            if scout_cfg.extensions.is_empty() {
+        validate_density_threshold(self.settings.density_threshold)?;
+        for policy in &self.settings.age_policies {
+            if policy.younger_than_days.is_none() && policy.older_than_days.is_none() {
                 return Err(LintScoutError::Config(format!(
-                    "custom scout '{}' must have at least one extension",
-                    scout_cfg.name
+                    "age policy '{}' must set younger_than_days or older_than_days",
+                    policy.name
                 )));
             }
-            if scout_cfg.rules.is_empty() {
+        }
+        validate_scouts(&self.scouts)
+    }
+
+    pub fn build_custom_scouts(&self) -> Result<Vec<Scout>> {
+        build_scouts(&self.scouts)
+    }
+}
+
+impl SubtreeConfig {
+    /// Load the config file at `path`, reporting any error against it.
+    pub fn load(path: &Path) -> Result<SubtreeConfig> {
+        let content = std::fs::read_to_string(path).map_err(|e| LintScoutError::ConfigLoad {
+            path: path.to_path_buf(),
+            source: Box::new(e),
+        })?;
+        SubtreeConfig::parse(path, &content)
+    }
+
+    /// Parse the content of the config file at `path`.
+    pub fn parse(path: &Path, content: &str) -> Result<SubtreeConfig> {
+        let invalid = |e: Box<dyn std::error::Error + Send + Sync>| LintScoutError::ConfigLoad {
+            path: path.to_path_buf(),
+            source: e,
+        };
+        let config: SubtreeConfig =
+            serde_yaml::from_str(content).map_err(|e| invalid(Box::new(e)))?;
+        validate_density_threshold(config.settings.density_threshold)
+            .and_then(|()| validate_scouts(&config.scouts))
+            .map_err(|e| invalid(Box::new(e)))?;
+        Ok(config)
+    }
+
+    pub fn build_custom_scouts(&self) -> Result<Vec<Scout>> {
+        build_scouts(&self.scouts)
+    }
+}
+
+fn validate_density_threshold(threshold: Option<f64>) -> Result<()> {
+    if threshold.is_some_and(|t| t < 0.0) {
+        return Err(LintScoutError::Config(
+            "density_threshold must not be negative".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_scouts(scouts: &[CustomScoutConfig]) -> Result<()> {
+    for scout_cfg in scouts {
+        if scout_cfg.extensions.is_empty() {
+            return Err(LintScoutError::Config(format!(
+                "custom scout '{}' must have at least one extension",
+                scout_cfg.name
+            )));
+        }
+        if let Some(ref name) = scout_cfg.syntax {
+            if CommentSyntax::by_name(name).is_none() {
                 return Err(LintScoutError::Config(format!(
-                    "custom scout '{}' must have at least one rule",
-                    scout_cfg.name
+                    "custom scout '{}' has unknown syntax '{}'",
+                    scout_cfg.name, name
                 )));
             }
-            for rule_cfg in &scout_cfg.rules {
-                regex::Regex::new(&rule_cfg.pattern).map_err(|e| {
-                    LintScoutError::InvalidPattern {
-                        pattern: rule_cfg.pattern.clone(),
-                        source: e,
-                    }
-                })?;
-            }
         }
-        Ok(())
+        if scout_cfg.rules.is_empty() {
+            return Err(LintScoutError::Config(format!(
+                "custom scout '{}' must have at least one rule",
+                scout_cfg.name
+            )));
+        }
+        for rule_cfg in &scout_cfg.rules {
+            regex::Regex::new(&rule_cfg.pattern).map_err(|e| LintScoutError::InvalidPattern {
+                pattern: rule_cfg.pattern.clone(),
+                source: e,
+            })?;
+        }
     }
+    Ok(())
+}
 
-    pub fn build_custom_scouts(&self) -> Result<Vec<Scout>> {
-        let mut scouts = Vec::new();
-        for cfg in &self.scouts {
-            let mut rules = Vec::new();
-            for r in &cfg.rules {
-                let mut rule = Rule::new(&r.id, &r.description, &r.pattern)?;
-                if let Some(ref cap) = r.capture_pattern {
-                    rule = rule.with_capture(cap)?;
-                }
-                rules.push(rule);
+fn build_scouts(configs: &[CustomScoutConfig]) -> Result<Vec<Scout>> {
+    let mut scouts = Vec::new();
+    for cfg in configs {
+        let mut rules = Vec::new();
+        for r in &cfg.rules {
+            let mut rule = Rule::new(&r.id, &r.description, &r.pattern)?.with_scope(r.scope);
+            if let Some(ref cap) = r.capture_pattern {
+                rule = rule.with_capture(cap)?;
+            }
+            if let Some(ref just) = r.justification_pattern {
+                rule = rule.with_justification(just)?;
             }
-            scouts.push(Scout {
-                name: cfg.name.clone(),
-                linter: cfg.linter.clone(),
-                language: cfg.language.clone(),
-                extensions: cfg.extensions.clone(),
-                rules,
-            });
+            if r.placement == Placement::Code {
+                rule = rule.in_code();
+            }
+            rules.push(rule);
         }
-        Ok(scouts)
+        scouts.push(Scout {
+            name: cfg.name.clone(),
+            linter: cfg.linter.clone(),
+            language: cfg.language.clone(),
+            extensions: cfg.extensions.clone(),
+            rules,
+            syntax: cfg.syntax.as_deref().and_then(CommentSyntax::by_name),
+        });
     }
+    Ok(scouts)
 }
 
 #[cfg(test)]
@@ -173,6 +446,7 @@ settings:
     - node_modules
   output: json
   pass_threshold: 5
+  blanket_threshold: 0
 scouts:
   - name: custom-test
     extensions: [ts]
@@ -184,10 +458,48 @@ scouts:
         let config: Config = serde_yaml::from_str(yaml).unwrap();
         assert_eq!(config.settings.output, "json");
         assert_eq!(config.settings.pass_threshold, Some(5));
+        assert_eq!(config.settings.blanket_threshold, Some(0));
         assert_eq!(config.scouts.len(), 1);
         assert_eq!(config.scouts[0].name, "custom-test");
     }
 
+    #[test]
+    fn age_policies() {
+        let yaml = r#"
+settings:
+  blame: true
+  age_policies:
+    - name: new-unjustified
+      younger_than_days: 7
+      unjustified: true
+      action: fail
+    - name: stale
+      older_than_days: 730
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+        assert!(config.settings.blame);
+        let policies = &config.settings.age_policies;
+        assert_eq!(policies[0].action, AgeAction::Fail);
+        assert!(policies[0].unjustified);
+        assert_eq!(policies[1].older_than_days, Some(730));
+        assert_eq!(policies[1].action, AgeAction::Report);
+
+        let yaml = "settings:\n  age_policies:\n    - name: empty\n";
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn density_threshold() {
+        let config: Config = serde_yaml::from_str("settings:\n  density_threshold: 2.5\n").unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.settings.density_threshold, Some(2.5));
+
+        let config: Config = serde_yaml::from_str("settings:\n  density_threshold: -1\n").unwrap();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn custom_scouts_build() {
         let yaml = r#"
@@ -205,6 +517,70 @@ scouts:
         assert_eq!(scouts[0].name, "my-scout");
     }
 
+    #[test]
+    fn custom_scout_syntax() {
+        let yaml = r#"
+scouts:
+  - name: my-scout
+    extensions: [py]
+    syntax: python
+    rules:
+      - id: r1
+        description: "Rule 1"
+        pattern: "custom-ignore"
+      - id: r2
+        description: "Rule 2"
+        pattern: "@approved"
+        placement: code
+        scope: declaration
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        config.validate().unwrap();
+        let scouts = config.build_custom_scouts().unwrap();
+        assert_eq!(scouts[0].syntax, Some(CommentSyntax::PYTHON));
+        assert_eq!(scouts[0].rules[1].placement, Placement::Code);
+        assert_eq!(scouts[0].rules[0].scope, Scope::SameLine);
+        assert_eq!(scouts[0].rules[1].scope, Scope::Declaration);
+        assert!(scouts[0].find_matches("s = 'custom-ignore'").is_empty());
+        assert!(!scouts[0].find_matches("x = 1  # custom-ignore").is_empty());
+    }
+
+    #[test]
+    fn unknown_syntax_error() {
+        let yaml = r#"
+scouts:
+  - name: bad
+    extensions: [py]
+    syntax: cobol
+    rules:
+      - id: r1
+        description: "Rule"
+        pattern: "foo"
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn justification_settings() {
+        let yaml = r#"
+settings:
+  justification:
+    required: true
+    min_length: 10
+    forbidden_phrases: [temp, "fix later"]
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        let j = &config.settings.justification;
+        assert!(j.required);
+        assert_eq!(j.min_length, 10);
+        assert_eq!(j.forbidden_phrases, vec!["temp", "fix later"]);
+
+        let config: Config = serde_yaml::from_str("settings: {}").unwrap();
+        assert!(!config.settings.justification.required);
+        assert_eq!(config.settings.justification.min_length, 1);
+    }
+
     #[test]
     fn invalid_regex_in_config() {
         let yaml = r#"
@@ -236,4 +612,22 @@ scouts:
         let result = config.validate();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn nested_config_rejects_unsupported_keys() {
+        let path = Path::new("pkg/.lintscout.yml");
+        assert!(SubtreeConfig::parse(path, "settings:\n  pass_threshold: 1\n").is_ok());
+        for (yaml, key) in [
+            ("settings:\n  budgets: []\n", "budgets"),
+            (
+                "settings:\n  justification: {required: true}\n",
+                "justification",
+            ),
+            ("policy: {}\n", "policy"),
+        ] {
+            let err = SubtreeConfig::parse(path, yaml).unwrap_err().to_string();
+            assert!(err.contains("pkg/.lintscout.yml"), "{err}");
+            assert!(err.contains(&format!("unknown field `{key}`")), "{err}");
+        }
+    }
 }
@@ -0,0 +1,174 @@
+//! Suppressions per 1000 non-blank lines, overall and per language, scout and
+//! top-level directory.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::finding::Finding;
+use crate::scope::Scope;
+
+/// Non-blank lines of one scanned file and what they count towards.
+#[derive(Debug, Clone, Default)]
+pub struct FileLines {
+    pub lines: u64,
+    /// Top-level directory of the file, `.` at the root.
+    pub directory: String,
+    /// Names and languages of the scouts that apply to the file.
+    pub scouts: Vec<(String, String)>,
+}
+
+/// Line counts of every scanned file, by finding path.
+#[derive(Debug, Clone, Default)]
+pub struct LineCounts {
+    pub files: HashMap<String, FileLines>,
+}
+
+/// Findings and lines of one group.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DensityGroup {
+    pub findings: u64,
+    pub lines: u64,
+    pub per_kloc: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Density {
+    pub lines: u64,
+    pub per_kloc: f64,
+    pub by_language: BTreeMap<String, DensityGroup>,
+    pub by_scout: BTreeMap<String, DensityGroup>,
+    pub by_directory: BTreeMap<String, DensityGroup>,
+}
+
+/// Findings per 1000 lines, rounded to two decimals.
+pub fn per_kloc(findings: u64, lines: u64) -> f64 {
+    if lines == 0 {
+        return 0.0;
+    }
+    (findings as f64 * 1000.0 / lines as f64 * 100.0).round() / 100.0
+}
+
+impl Density {
+    /// Compute the density of `findings` over the scanned files, leaving out
+    /// rule policy violations and suppressions in linter configs. A file's
+    /// lines count once towards each distinct language of its scouts.
+    pub fn compute(counts: &LineCounts, findings: &[Finding]) -> Self {
+        let mut density = Density::default();
+        let mut languages: HashMap<&str, &str> = HashMap::new();
+        for file in counts.files.values() {
+            density.lines += file.lines;
+            group(&mut density.by_directory, &file.directory).lines += file.lines;
+            let mut seen = Vec::new();
+            for (scout, language) in &file.scouts {
+                languages.insert(scout, language);
+                group(&mut density.by_scout, scout).lines += file.lines;
+                if !seen.contains(&language) {
+                    seen.push(language);
+                    group(&mut density.by_language, language).lines += file.lines;
+                }
+            }
+        }
+
+        let findings: Vec<&Finding> = findings
+            .iter()
+            .filter(|f| f.policy_violation.is_none() && f.scope != Scope::Config)
+            .collect();
+        for f in &findings {
+            group(&mut density.by_scout, &f.scout_name).findings += 1;
+            if let Some(language) = languages.get(f.scout_name.as_str()) {
+                group(&mut density.by_language, language).findings += 1;
+            }
+            if let Some(file) = counts.files.get(&f.path) {
+                group(&mut density.by_directory, &file.directory).findings += 1;
+            }
+        }
+
+        density.per_kloc = per_kloc(findings.len() as u64, density.lines);
+        for g in density
+            .by_language
+            .values_mut()
+            .chain(density.by_scout.values_mut())
+            .chain(density.by_directory.values_mut())
+        {
+            g.per_kloc = per_kloc(g.findings, g.lines);
+        }
+        density
+    }
+}
+
+fn group<'a>(groups: &'a mut BTreeMap<String, DensityGroup>, key: &str) -> &'a mut DensityGroup {
+    groups.entry(key.to_string()).or_default()
+}
+
+/// The top-level directory of a relative path, `.` for files at the root.
+pub fn top_level_directory(path: &Path) -> String {
+    let mut components = path
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)));
+    match (components.next(), components.next()) {
+        (Some(dir), Some(_)) => dir.as_os_str().to_string_lossy().into_owned(),
+        _ => ".".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(path: &str, scout_name: &str) -> Finding {
+        Finding {
+            path: path.into(),
+            line_number: 1,
+            scout_name: scout_name.into(),
+            linter: scout_name.into(),
+            ..Default::default()
+        }
+    }
+
+    fn file(lines: u64, directory: &str, scouts: &[(&str, &str)]) -> FileLines {
+        FileLines {
+            lines,
+            directory: directory.into(),
+            scouts: scouts
+                .iter()
+                .map(|(s, l)| (s.to_string(), l.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn groups_lines_and_findings() {
+        let ts = [("eslint", "javascript"), ("typescript", "javascript")];
+        let counts = LineCounts {
+            files: HashMap::from([
+                ("web/app.ts".to_string(), file(1500, "web", &ts)),
+                (
+                    "app.py".to_string(),
+                    file(500, ".", &[("flake8", "python")]),
+                ),
+            ]),
+        };
+        let findings = [
+            finding("web/app.ts", "eslint"),
+            finding("web/app.ts", "typescript"),
+            finding("app.py", "flake8"),
+        ];
+        let density = Density::compute(&counts, &findings);
+        assert_eq!(density.lines, 2000);
+        assert_eq!(density.per_kloc, 1.5);
+        let js = &density.by_language["javascript"];
+        assert_eq!((js.findings, js.lines, js.per_kloc), (2, 1500, 1.33));
+        assert_eq!(density.by_scout["eslint"].per_kloc, 0.67);
+        assert_eq!(density.by_directory["."].per_kloc, 2.0);
+        assert_eq!(per_kloc(1, 0), 0.0);
+    }
+
+    #[test]
+    fn directories_are_top_level() {
+        assert_eq!(top_level_directory(Path::new("src/a/b.rs")), "src");
+        assert_eq!(top_level_directory(Path::new("./src/b.rs")), "src");
+        assert_eq!(top_level_directory(Path::new("b.rs")), ".");
+    }
+}
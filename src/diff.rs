@@ -0,0 +1,406 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::finding::Finding;
+use crate::scanner::ScanResult;
+
+/// Lines changed in one file of a unified diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path before the change; `None` for added files.
+    pub old_path: Option<String>,
+    /// Path after the change; `None` for deleted files.
+    pub new_path: Option<String>,
+    /// Line numbers of added lines in the new file.
+    pub added: Vec<usize>,
+    /// Removed lines with their line numbers in the old file.
+    pub removed: Vec<(usize, String)>,
+}
+
+/// A parsed unified diff, such as the output of `git diff`.
+#[derive(Debug, Clone, Default)]
+pub struct Diff {
+    pub files: Vec<FileDiff>,
+}
+
+/// Suppressions added and removed by a diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffSummary {
+    /// The ref or diff file compared against.
+    pub base: String,
+    /// Number of findings on added lines.
+    pub added: u64,
+    /// Suppressions on removed lines, located in the old file.
+    pub removed: Vec<Finding>,
+}
+
+impl Diff {
+    pub fn parse(text: &str) -> Self {
+        let mut files = Vec::new();
+        let mut current: Option<FileDiff> = None;
+        // Position in, and lines left of, the current hunk on each side
+        let (mut old_line, mut new_line) = (0, 0);
+        let (mut old_left, mut new_left) = (0usize, 0usize);
+        // Whether the current file has a `diff --git` header
+        let mut git = false;
+
+        for line in text.lines() {
+            if old_left > 0 || new_left > 0 {
+                let Some(file) = current.as_mut() else {
+                    continue;
+                };
+                match line.as_bytes().first() {
+                    Some(b'+') => {
+                        file.added.push(new_line);
+                        new_line += 1;
+                        new_left = new_left.saturating_sub(1);
+                    }
+                    Some(b'-') => {
+                        file.removed.push((old_line, line[1..].to_string()));
+                        old_line += 1;
+                        old_left = old_left.saturating_sub(1);
+                    }
+                    // "\ No newline at end of file"
+                    Some(b'\\') => {}
+                    _ => {
+                        old_line += 1;
+                        new_line += 1;
+                        old_left = old_left.saturating_sub(1);
+                        new_left = new_left.saturating_sub(1);
+                    }
+                }
+                continue;
+            }
+
+            if line.starts_with("diff ") {
+                files.extend(current.take());
+                current = Some(FileDiff::default());
+                git = line.starts_with("diff --git ");
+            } else if let Some(path) = line.strip_prefix("--- ") {
+                // Plain diffs have no "diff" line between files
+                if current
+                    .as_ref()
+                    .is_some_and(|f| f.new_path.is_some() || !f.added.is_empty())
+                {
+                    files.extend(current.take());
+                    git = false;
+                }
+                current.get_or_insert_with(FileDiff::default).old_path = parse_path(path);
+            } else if let Some(path) = line.strip_prefix("+++ ") {
+                let file = current.get_or_insert_with(FileDiff::default);
+                file.new_path = parse_path(path);
+                strip_prefixes(file, git);
+            } else if line.starts_with("@@ ") {
+                let mut ranges = line.split_whitespace().skip(1);
+                let old = ranges.next().and_then(|r| parse_range(r, '-'));
+                let new = ranges.next().and_then(|r| parse_range(r, '+'));
+                if let (Some(old), Some(new)) = (old, new) {
+                    (old_line, old_left) = old;
+                    (new_line, new_left) = new;
+                }
+            }
+        }
+        files.extend(current);
+
+        Diff { files }
+    }
+
+    /// The lines each file under `scan_path` lost, as its old contents with
+    /// the lines the diff does not show filled in, by path relative to `scan_dir` and with the
+    /// path in the diff. Diff paths are resolved against `root`; every path
+    /// must be canonical.
+    pub fn removed_files(
+        &self,
+        root: &Path,
+        scan_dir: &Path,
+        scan_path: &Path,
+    ) -> Vec<(PathBuf, String, Vec<u8>)> {
+        self.files
+            .iter()
+            .filter(|file| !file.removed.is_empty())
+            .filter_map(|file| {
+                let old_path = file.old_path.as_ref()?;
+                let path = root.join(old_path);
+                if !path.starts_with(scan_path) {
+                    return None;
+                }
+                let relative = path.strip_prefix(scan_dir).ok()?.to_path_buf();
+                Some((relative, old_path.clone(), removed_content(file)))
+            })
+            .collect()
+    }
+
+    /// Keep only findings on added lines and record `removed`, the
+    /// suppressions on removed lines. Diff paths are resolved against `root`.
+    pub fn apply(
+        &self,
+        result: &mut ScanResult,
+        root: &Path,
+        mut removed: Vec<Finding>,
+        base: &str,
+    ) {
+        let mut added: HashMap<PathBuf, HashSet<usize>> = HashMap::new();
+        for file in &self.files {
+            if let Some(ref path) = file.new_path {
+                if let Ok(path) = root.join(path).canonicalize() {
+                    added.entry(path).or_default().extend(&file.added);
+                }
+            }
+        }
+
+        let mut canonical: HashMap<String, Option<PathBuf>> = HashMap::new();
+        result.retain(|f| {
+            canonical
+                .entry(f.path.clone())
+                .or_insert_with(|| Path::new(&f.path).canonicalize().ok())
+                .as_ref()
+                .and_then(|path| added.get(path))
+                .is_some_and(|lines| lines.contains(&f.line_number))
+        });
+
+        removed.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then(a.line_number.cmp(&b.line_number))
+                .then(a.scout_name.cmp(&b.scout_name))
+                .then(a.rule_id.cmp(&b.rule_id))
+        });
+
+        result.diff = Some(DiffSummary {
+            base: base.to_string(),
+            added: result.findings.len() as u64,
+            removed,
+        });
+    }
+}
+
+/// The old contents of a file, with the lines a diff removes at their line
+/// numbers. The lines it does not show are filled with code, so that a block
+/// directive after them is not taken for one covering the whole file.
+fn removed_content(file: &FileDiff) -> Vec<u8> {
+    let mut lines: Vec<&str> = Vec::new();
+    for (line_number, text) in &file.removed {
+        if lines.len() < *line_number {
+            lines.resize(*line_number, "_");
+        }
+        lines[line_number - 1] = text;
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content.into_bytes()
+}
+
+/// Parse the path of a `---`/`+++` line, dropping timestamps. `/dev/null`
+/// yields `None`.
+fn parse_path(raw: &str) -> Option<String> {
+    let path = raw.split('\t').next().unwrap_or(raw).trim();
+    let path = path.trim_matches('"');
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.to_string())
+}
+
+/// Drop the `a/` and `b/` prefixes git puts on the paths of each side, under
+/// a `diff --git` header or when both sides carry them. Plain diffs name real
+/// paths, which may start with a directory called `a` or `b`.
+fn strip_prefixes(file: &mut FileDiff, git: bool) {
+    let prefixed = |path: &Option<String>, prefix: &str| {
+        path.as_deref().is_some_and(|p| p.starts_with(prefix))
+    };
+    let both = prefixed(&file.old_path, "a/") && prefixed(&file.new_path, "b/");
+    if !git && !both {
+        return;
+    }
+    for (path, prefix) in [(&mut file.old_path, "a/"), (&mut file.new_path, "b/")] {
+        if let Some(stripped) = path.as_deref().and_then(|p| p.strip_prefix(prefix)) {
+            *path = Some(stripped.to_string());
+        }
+    }
+}
+
+/// Parse a hunk range such as `-12,3` or `+7` into start line and count.
+fn parse_range(range: &str, sign: char) -> Option<(usize, usize)> {
+    let range = range.strip_prefix(sign)?;
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIT_DIFF: &str = "\
+diff --git a/src/app.py b/src/app.py
+index 1111111..2222222 100644
+--- a/src/app.py
++++ b/src/app.py
+@@ -2 +2,2 @@ import os
+-import sys  # noqa: F401
++import re  # noqa
++--- not a header
+@@ -10,0 +12 @@ def f():
++    return g()  # type: ignore
+diff --git a/old.py b/old.py
+deleted file mode 100644
+--- a/old.py
++++ /dev/null
+@@ -1,2 +0,0 @@
+-x = 1
+-y = 2  # type: ignore
+";
+
+    #[test]
+    fn parses_git_diff() {
+        let diff = Diff::parse(GIT_DIFF);
+        assert_eq!(diff.files.len(), 2);
+        let app = &diff.files[0];
+        assert_eq!(app.old_path.as_deref(), Some("src/app.py"));
+        assert_eq!(app.new_path.as_deref(), Some("src/app.py"));
+        assert_eq!(app.added, vec![2, 3, 12]);
+        assert_eq!(app.removed, vec![(2, "import sys  # noqa: F401".into())]);
+        let old = &diff.files[1];
+        assert!(old.new_path.is_none());
+        assert_eq!(old.removed.len(), 2);
+    }
+
+    #[test]
+    fn parses_plain_diff_with_context() {
+        let text = "\
+--- a.js\t2024-01-01 00:00:00
++++ a.js\t2024-01-02 00:00:00
+@@ -1,3 +1,3 @@
+ one
+-two
++2
+ three
+--- b.js
++++ b.js
+@@ -5 +5 @@
+-x
++y
+";
+        let diff = Diff::parse(text);
+        assert_eq!(diff.files.len(), 2);
+        assert_eq!(diff.files[0].new_path.as_deref(), Some("a.js"));
+        assert_eq!(diff.files[0].added, vec![2]);
+        assert_eq!(diff.files[0].removed, vec![(2, "two".into())]);
+        assert_eq!(diff.files[1].added, vec![5]);
+    }
+
+    #[test]
+    fn keeps_real_a_and_b_directories_in_plain_diffs() {
+        let text = "\
+--- a/x.py
++++ a/x.py
+@@ -1 +1 @@
+-x
++y
+--- a/y.py
++++ b/y.py
+@@ -1 +1 @@
+-x
++y
+--- /dev/null
++++ b/z.py
+@@ -0,0 +1 @@
++z
+";
+        let diff = Diff::parse(text);
+        let paths: Vec<(Option<&str>, Option<&str>)> = diff
+            .files
+            .iter()
+            .map(|f| (f.old_path.as_deref(), f.new_path.as_deref()))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                (Some("a/x.py"), Some("a/x.py")),
+                (Some("y.py"), Some("y.py")),
+                (None, Some("b/z.py")),
+            ]
+        );
+
+        let git = "\
+diff --git a/z.py b/z.py
+new file mode 100644
+--- /dev/null
++++ b/z.py
+@@ -0,0 +1 @@
++z
+";
+        assert_eq!(Diff::parse(git).files[0].new_path.as_deref(), Some("z.py"));
+    }
+
+    #[test]
+    fn removed_lines_keep_their_old_line_numbers() {
+        let diff = Diff::parse(GIT_DIFF);
+        let root = Path::new("/repo");
+        let files = diff.removed_files(root, root, root);
+        let summary: Vec<(&Path, &str, &str)> = files
+            .iter()
+            .map(|(relative, path, content)| {
+                (
+                    relative.as_path(),
+                    path.as_str(),
+                    std::str::from_utf8(content).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    Path::new("src/app.py"),
+                    "src/app.py",
+                    "_\nimport sys  # noqa: F401\n"
+                ),
+                (
+                    Path::new("old.py"),
+                    "old.py",
+                    "x = 1\ny = 2  # type: ignore\n"
+                ),
+            ]
+        );
+
+        // Only files under the scanned path
+        let src = diff.removed_files(root, root, &root.join("src"));
+        assert_eq!(src.len(), 1);
+        let nested = diff.removed_files(root, &root.join("src"), &root.join("src"));
+        assert_eq!(nested[0].0, Path::new("app.py"));
+    }
+
+    #[test]
+    fn apply_filters_to_added_lines_and_lists_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        let file = dir.path().join("src/app.py");
+        std::fs::write(&file, "").unwrap();
+        let finding = |line_number| Finding {
+            path: file.display().to_string(),
+            line_number,
+            scout_name: "flake8".into(),
+            linter: "flake8".into(),
+            rule_id: "flake8-noqa".into(),
+            blanket: true,
+            ..Default::default()
+        };
+        let mut result = ScanResult {
+            findings: vec![finding(1), finding(2), finding(12)],
+            ..Default::default()
+        };
+        let mut old = finding(2);
+        old.path = "old.py".into();
+        Diff::parse(GIT_DIFF).apply(&mut result, dir.path(), vec![old], "main");
+
+        let lines: Vec<usize> = result.findings.iter().map(|f| f.line_number).collect();
+        assert_eq!(lines, vec![2, 12]);
+        let summary = result.diff.unwrap();
+        assert_eq!(summary.added, 2);
+        assert_eq!(summary.removed[0].path, "old.py");
+    }
+}
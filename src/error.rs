@@ -23,6 +23,24 @@ pub enum LintScoutError {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
 
+    #[error("failed to load baseline from {path}: {source}")]
+    BaselineLoad {
+        path: PathBuf,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("failed to load report from {path}: {source}")]
+    ReportLoad {
+        path: PathBuf,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("git error: {0}")]
+    Git(String),
+
+    #[error("{0} already exists (use --force to overwrite)")]
+    HookExists(PathBuf),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
@@ -0,0 +1,225 @@
+//! Files exempted from a linter, or from some of its rules, by ignore files
+//! and per-file mappings such as `.eslintignore` or Ruff's
+//! `per-file-ignores`, resolved against the files actually scanned.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use ignore::gitignore::GitignoreBuilder;
+use ignore::Match;
+use serde::{Deserialize, Serialize};
+
+use crate::linter_config::ConfigExemption;
+
+/// Files exempted from a linter, or from one of its rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exemption {
+    pub linter: String,
+    /// The rule the files are exempt from; absent when they are exempt from
+    /// the linter altogether.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// Paths of the exempted files, in order.
+    pub files: Vec<String>,
+    /// The patterns that exempt them.
+    pub sources: Vec<ExemptionSource>,
+}
+
+/// A pattern in a config file, with its line.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ExemptionSource {
+    pub path: String,
+    pub line: usize,
+    pub pattern: String,
+}
+
+/// An exemption read from a config file.
+#[derive(Debug, Clone)]
+pub struct ExemptionRule {
+    /// Finding path of the config file.
+    pub path: String,
+    /// Directory of the config file relative to the scan root, empty at the
+    /// root; its patterns are relative to it.
+    pub dir: String,
+    pub exemption: ConfigExemption,
+}
+
+/// A scanned file, with the linters of the scouts that apply to it.
+#[derive(Debug, Clone)]
+pub struct LintedFile {
+    /// Finding path of the file.
+    pub path: String,
+    /// Path relative to the scan root.
+    pub relative: String,
+    pub linters: Vec<String>,
+}
+
+/// Match each rule's patterns, with gitignore semantics, against the files
+/// of its linter under the config file's directory, grouping the exempted
+/// files by linter and rule.
+pub fn resolve(rules: &[ExemptionRule], files: &[LintedFile]) -> Vec<Exemption> {
+    type Group = (BTreeSet<String>, BTreeSet<ExemptionSource>);
+    let mut groups: BTreeMap<(String, Option<String>), Group> = BTreeMap::new();
+    for rule in rules {
+        let exemption = &rule.exemption;
+        let mut builder = GitignoreBuilder::new("");
+        for (pattern, _) in &exemption.patterns {
+            // Patterns that are not valid globs exempt nothing
+            let _ = builder.add_line(None, pattern);
+        }
+        let Ok(matcher) = builder.build() else {
+            continue;
+        };
+        let keys: Vec<Option<String>> = if exemption.rules.is_empty() {
+            vec![None]
+        } else {
+            exemption.rules.iter().cloned().map(Some).collect()
+        };
+        for file in files
+            .iter()
+            .filter(|f| f.linters.iter().any(|l| l == exemption.linter))
+        {
+            let Some(relative) = within(&file.relative, &rule.dir) else {
+                continue;
+            };
+            let Match::Ignore(glob) = matcher.matched_path_or_any_parents(relative, false) else {
+                continue;
+            };
+            let line = exemption
+                .patterns
+                .iter()
+                .find(|(p, _)| p == glob.original())
+                .map_or(0, |(_, line)| *line);
+            let source = ExemptionSource {
+                path: rule.path.clone(),
+                line,
+                pattern: glob.original().to_string(),
+            };
+            for key in &keys {
+                let group = groups
+                    .entry((exemption.linter.to_string(), key.clone()))
+                    .or_default();
+                group.0.insert(file.path.clone());
+                group.1.insert(source.clone());
+            }
+        }
+    }
+    groups
+        .into_iter()
+        .map(|((linter, rule), (files, sources))| Exemption {
+            linter,
+            rule,
+            files: files.into_iter().collect(),
+            sources: sources.into_iter().collect(),
+        })
+        .collect()
+}
+
+/// `relative` as a path under `dir`, if it is.
+fn within<'a>(relative: &'a str, dir: &str) -> Option<&'a Path> {
+    if dir.is_empty() {
+        return Some(Path::new(relative));
+    }
+    Path::new(relative).strip_prefix(dir).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        path: &str,
+        dir: &str,
+        linter: &'static str,
+        patterns: &[&str],
+        rules: &[&str],
+    ) -> ExemptionRule {
+        ExemptionRule {
+            path: path.into(),
+            dir: dir.into(),
+            exemption: ConfigExemption {
+                linter,
+                patterns: patterns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| (p.to_string(), i + 1))
+                    .collect(),
+                rules: rules.iter().map(|r| r.to_string()).collect(),
+            },
+        }
+    }
+
+    fn file(relative: &str, linters: &[&str]) -> LintedFile {
+        LintedFile {
+            path: format!("./{relative}"),
+            relative: relative.into(),
+            linters: linters.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn resolves_patterns_against_scanned_files() {
+        let rules = [
+            rule(
+                "./web/.eslintignore",
+                "web",
+                "eslint",
+                &["legacy/", "!legacy/keep.js", "*.min.js"],
+                &[],
+            ),
+            rule("./setup.cfg", "", "flake8", &["tests/*"], &["S101", "E501"]),
+            rule("./setup.cfg", "", "flake8", &["__init__.py"], &["E501"]),
+        ];
+        let files = [
+            file("web/legacy/a.js", &["eslint", "prettier"]),
+            file("web/legacy/keep.js", &["eslint"]),
+            file("web/vendor/lib.min.js", &["eslint"]),
+            file("legacy/b.js", &["eslint"]),
+            file("tests/test_app.py", &["flake8", "ruff"]),
+            file("app/__init__.py", &["flake8"]),
+            file("app/main.py", &["flake8"]),
+        ];
+        let exemptions = resolve(&rules, &files);
+        let summary: Vec<(&str, Option<&str>, Vec<&str>)> = exemptions
+            .iter()
+            .map(|e| {
+                (
+                    e.linter.as_str(),
+                    e.rule.as_deref(),
+                    e.files.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "eslint",
+                    None,
+                    vec!["./web/legacy/a.js", "./web/vendor/lib.min.js"]
+                ),
+                (
+                    "flake8",
+                    Some("E501"),
+                    vec!["./app/__init__.py", "./tests/test_app.py"]
+                ),
+                ("flake8", Some("S101"), vec!["./tests/test_app.py"]),
+            ]
+        );
+        assert_eq!(
+            exemptions[0].sources,
+            [
+                ExemptionSource {
+                    path: "./web/.eslintignore".into(),
+                    line: 1,
+                    pattern: "legacy/".into()
+                },
+                ExemptionSource {
+                    path: "./web/.eslintignore".into(),
+                    line: 3,
+                    pattern: "*.min.js".into()
+                },
+            ]
+        );
+    }
+}
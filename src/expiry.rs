@@ -0,0 +1,246 @@
+//! Expiry markers on suppressions, e.g. `-- until 2026-12-01` or
+//! `TODO(JIRA-123)`, and the policy failing expired or unticketed ones.
+//! Markers are read from the comment holding the directive, so a date in a
+//! string or in code on the same line is not taken for one.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ExpiryConfig;
+use crate::date::{self, SECONDS_PER_DAY};
+use crate::error::{LintScoutError, Result};
+use crate::finding::Finding;
+use crate::scanner::ScanResult;
+
+/// Recognized without configuration: `until YYYY-MM-DD` and `TODO(TICKET)`.
+const BUILTIN_PATTERNS: [&str; 2] = [
+    r"\buntil[ :]+(?P<expires>\d{4}-\d{2}-\d{2})\b",
+    r"\bTODO\((?P<ticket>[^)\s]+)\)",
+];
+
+/// The expiry marker of a suppression and whether it fails the policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expiry {
+    /// Last day (`YYYY-MM-DD`) the suppression may stay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    /// Ticket tracking its removal, e.g. `JIRA-123`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+    /// Why the suppression fails the expiry policy, if it does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+}
+
+/// Parses expiry markers and checks them against the configured policy.
+pub struct ExpiryPolicy {
+    patterns: Vec<Regex>,
+    require_ticket: bool,
+    ticket_pattern: Option<(String, Regex)>,
+}
+
+impl ExpiryPolicy {
+    pub fn new(config: &ExpiryConfig) -> Result<Self> {
+        let mut patterns = Vec::new();
+        if let Some(ref pattern) = config.pattern {
+            let re = compile(pattern, false)?;
+            if !re
+                .capture_names()
+                .flatten()
+                .any(|name| name == "expires" || name == "ticket")
+            {
+                return Err(LintScoutError::Config(format!(
+                    "expiry pattern '{pattern}' has no 'expires' or 'ticket' group"
+                )));
+            }
+            patterns.push(re);
+        }
+        for pattern in BUILTIN_PATTERNS {
+            patterns.push(compile(pattern, true)?);
+        }
+        let ticket_pattern = match config.ticket_pattern {
+            Some(ref pattern) => Some((
+                pattern.clone(),
+                compile(&format!("^(?:{pattern})$"), false)?,
+            )),
+            None => None,
+        };
+        Ok(ExpiryPolicy {
+            patterns,
+            require_ticket: config.require_ticket,
+            ticket_pattern,
+        })
+    }
+
+    /// The expiry date and ticket named in a comment; the configured pattern
+    /// takes precedence over the built-in ones.
+    pub fn parse(&self, text: &str) -> (Option<String>, Option<String>) {
+        let (mut expires, mut ticket) = (None, None);
+        for re in &self.patterns {
+            for caps in re.captures_iter(text) {
+                if expires.is_none() {
+                    expires = caps.name("expires").map(|m| m.as_str().to_string());
+                }
+                if ticket.is_none() {
+                    ticket = caps.name("ticket").map(|m| m.as_str().to_string());
+                }
+            }
+        }
+        (expires, ticket)
+    }
+
+    /// The expiry of a finding at time `now`, if it has a marker or fails
+    /// the policy. Only expired or invalid markers fail, unless tickets are
+    /// required, in which case a suppression needs a ticket or a date.
+    pub fn check(&self, finding: &Finding, now: i64) -> Option<Expiry> {
+        let (expires, ticket) = self.parse(&finding.comment);
+        let issue = match (&expires, &ticket) {
+            (Some(d), _) if date::parse_date(d).is_none() => {
+                Some(format!("invalid expiry date '{d}'"))
+            }
+            (Some(d), _) if date::parse_date(d).is_some_and(|t| now >= t + SECONDS_PER_DAY) => {
+                Some(format!("expired on {d}"))
+            }
+            (None, None) if self.require_ticket => Some("missing ticket reference".to_string()),
+            (_, Some(t)) => match self.ticket_pattern {
+                Some((ref pattern, ref re)) if !re.is_match(t) => {
+                    Some(format!("ticket '{t}' does not match '{pattern}'"))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        if expires.is_none() && ticket.is_none() && issue.is_none() {
+            return None;
+        }
+        Some(Expiry {
+            expires,
+            ticket,
+            issue,
+        })
+    }
+
+    /// Record the expiry of every finding other than rule policy violations
+    /// and update the count of failing ones.
+    pub fn apply(&self, result: &mut ScanResult, now: i64) {
+        for finding in &mut result.findings {
+            if finding.policy_violation.is_none() {
+                finding.expiry = self.check(finding, now);
+            }
+        }
+        result.recount();
+    }
+}
+
+fn compile(pattern: &str, case_insensitive: bool) -> Result<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| LintScoutError::InvalidPattern {
+            pattern: pattern.to_string(),
+            source: e,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scope::Scope;
+
+    fn finding(comment: &str) -> Finding {
+        Finding {
+            path: "a.js".into(),
+            line_number: 1,
+            line_text: format!("foo();  {comment}"),
+            comment: comment.into(),
+            scout_name: "eslint".into(),
+            linter: "eslint".into(),
+            rule_id: "eslint-disable-next-line".into(),
+            rule_description: "ESLint disable next line".into(),
+            scope: Scope::NextLine,
+            suppressed_rules: Some(vec!["no-foo".into()]),
+            ..Default::default()
+        }
+    }
+
+    fn policy(pattern: Option<&str>, require_ticket: bool, ticket: Option<&str>) -> ExpiryPolicy {
+        ExpiryPolicy::new(&ExpiryConfig {
+            pattern: pattern.map(String::from),
+            require_ticket,
+            ticket_pattern: ticket.map(String::from),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_builtin_markers() {
+        let p = policy(None, false, None);
+        assert_eq!(
+            p.parse("// eslint-disable-next-line no-foo -- until 2026-12-01"),
+            (Some("2026-12-01".into()), None)
+        );
+        assert_eq!(
+            p.parse("# noqa: E501 TODO(JIRA-123) Until: 2027-01-31"),
+            (Some("2027-01-31".into()), Some("JIRA-123".into()))
+        );
+        assert_eq!(p.parse("# noqa: E501"), (None, None));
+    }
+
+    #[test]
+    fn parses_configured_pattern() {
+        let p = policy(
+            Some(r"expires=(?P<expires>\S+) see (?P<ticket>#\d+)"),
+            false,
+            None,
+        );
+        assert_eq!(
+            p.parse("// nolint expires=2026-03-01 see #42 until 2027-01-01"),
+            (Some("2026-03-01".into()), Some("#42".into()))
+        );
+        assert!(ExpiryPolicy::new(&ExpiryConfig {
+            pattern: Some(r"(?P<date>\d+)".into()),
+            ..ExpiryConfig::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn flags_expired_and_unticketed() {
+        let now = date::parse_date("2026-12-01").unwrap() + 3600;
+        let p = policy(None, true, Some(r"(JIRA|OPS)-\d+"));
+        let issue = |text: &str| p.check(&finding(text), now).and_then(|e| e.issue);
+
+        assert_eq!(issue("-- until 2026-12-01 TODO(JIRA-1)"), None);
+        assert_eq!(
+            issue("-- until 2026-11-30 TODO(JIRA-1)").as_deref(),
+            Some("expired on 2026-11-30")
+        );
+        assert_eq!(
+            issue("-- until 2026-02-30").as_deref(),
+            Some("invalid expiry date '2026-02-30'")
+        );
+        assert_eq!(issue("-- until 2027-01-01"), None);
+        assert_eq!(
+            issue("// eslint-disable-line").as_deref(),
+            Some("missing ticket reference")
+        );
+        assert_eq!(
+            issue("TODO(FOO-9)").as_deref(),
+            Some(r"ticket 'FOO-9' does not match '(JIRA|OPS)-\d+'")
+        );
+        assert!(policy(None, false, None)
+            .check(&finding("// eslint-disable-line"), now)
+            .is_none());
+    }
+
+    #[test]
+    fn reads_markers_from_the_comment_only() {
+        let p = policy(None, false, None);
+        let f = Finding {
+            line_text: r#"alert("until 2020-01-01"); // eslint-disable-line"#.into(),
+            comment: "// eslint-disable-line".into(),
+            ..finding("")
+        };
+        assert!(p.check(&f, date::now()).is_none());
+    }
+}
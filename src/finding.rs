@@ -1,14 +1,84 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+use crate::acknowledge::Acknowledgement;
+use crate::blame::Blame;
+use crate::catalog::RuleDetail;
+use crate::expiry::Expiry;
+use crate::policy::{AgePolicyMatch, PolicyViolation};
+use crate::region::SuppressedRegion;
+use crate::scope::Scope;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Finding {
     pub path: String,
     pub line_number: usize,
     pub line_text: String,
+    /// The comment holding the directive, from which expiry markers are read.
+    #[serde(skip)]
+    pub comment: String,
     pub scout_name: String,
     pub linter: String,
     pub rule_id: String,
     pub rule_description: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub scope: Scope,
+    /// Suppresses every rule rather than named ones, e.g. a bare `# noqa`.
+    #[serde(default)]
+    pub blanket: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub suppressed_rules: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<SuppressedRegion>,
+    /// Explanation attached to the directive; empty when the separator is
+    /// present without a reason.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<String>,
+    /// Why the justification fails the configured policy, if it does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification_issue: Option<String>,
+    /// Identifies the finding across line shifts; see [`crate::fingerprint`].
+    #[serde(default)]
+    pub fingerprint: String,
+    /// Who last changed the line, with `--blame`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame: Option<Blame>,
+    /// Age policies the suppression falls under.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub age_policies: Vec<AgePolicyMatch>,
+    /// Set on findings reporting a rule policy violation by the directive on
+    /// this line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_violation: Option<PolicyViolation>,
+    /// Expiry date and ticket named by the suppression, and why they fail
+    /// the expiry policy, if they do.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<Expiry>,
+    /// The `lintscout-ignore` comment approving the suppression, on
+    /// acknowledged findings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledgement: Option<Acknowledgement>,
+    /// What the rule catalog knows about each suppressed rule it covers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rule_details: Vec<RuleDetail>,
+    /// Set when the linter is not configured anywhere in the scanned tree, so
+    /// the directive suppresses nothing.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub orphaned: bool,
+    /// Dotted path of the key, for suppressions in linter config files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_path: Option<String>,
+    /// Other linters that honor the directive, such as Ruff for a `# noqa`
+    /// attributed to Flake8.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub also_honored_by: Vec<String>,
+}
+
+impl Finding {
+    /// Suppressed rules the linter does not have, leaving out rule policy
+    /// violations so that each directive counts once.
+    pub fn unknown_rules(&self) -> impl Iterator<Item = &RuleDetail> {
+        self.rule_details
+            .iter()
+            .filter(move |d| !d.known && self.policy_violation.is_none())
+    }
 }
@@ -0,0 +1,107 @@
+//! Stable fingerprints that identify a finding independently of its line
+//! number, so it can be matched across edits that shift code around.
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Collapse runs of whitespace and trim, so re-indentation does not change a
+/// fingerprint.
+pub fn normalize(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The nearest non-blank lines before and after line `index` (0-based).
+pub fn context<'a>(lines: &[&'a str], index: usize) -> (&'a str, &'a str) {
+    let before = lines[..index]
+        .iter()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .copied()
+        .unwrap_or("");
+    let after = lines
+        .get(index + 1..)
+        .unwrap_or(&[])
+        .iter()
+        .find(|l| !l.trim().is_empty())
+        .copied()
+        .unwrap_or("");
+    (before, after)
+}
+
+/// Fingerprint a finding from its path relative to the git toplevel (or the
+/// scan root outside git), scout, rule, line text and surrounding context.
+pub fn fingerprint(
+    path: &str,
+    scout: &str,
+    rule_id: &str,
+    line: &str,
+    context: (&str, &str),
+) -> String {
+    hash(&[
+        path,
+        scout,
+        rule_id,
+        &normalize(line),
+        &normalize(context.0),
+        &normalize(context.1),
+    ])
+}
+
+/// Fingerprint a finding derived from another one, such as a policy
+/// violation reported for a directive.
+pub fn derive(fingerprint: &str, parts: &[&str]) -> String {
+    let mut all = vec![fingerprint];
+    all.extend_from_slice(parts);
+    hash(&all)
+}
+
+fn hash(parts: &[&str]) -> String {
+    let mut hash = FNV_OFFSET;
+    for part in parts {
+        // A separator byte that cannot occur in UTF-8 keeps fields distinct
+        for byte in part.bytes().chain([0xff]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_collapses_whitespace() {
+        assert_eq!(normalize("  a \t b  "), "a b");
+    }
+
+    #[test]
+    fn context_skips_blank_lines() {
+        let lines = ["first", "", "directive", "  ", "last"];
+        assert_eq!(context(&lines, 2), ("first", "last"));
+        assert_eq!(context(&lines, 0), ("", "directive"));
+        assert_eq!(context(&lines, 4), ("directive", ""));
+    }
+
+    #[test]
+    fn fingerprint_ignores_indentation_but_not_fields() {
+        let a = fingerprint("a.js", "eslint", "r", "  // x", ("f()", "g()"));
+        let b = fingerprint("a.js", "eslint", "r", "// x", ("f()", "  g()"));
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 16);
+        assert_ne!(
+            a,
+            fingerprint("b.js", "eslint", "r", "// x", ("f()", "g()"))
+        );
+        assert_ne!(
+            a,
+            fingerprint("a.js", "eslint", "r", "// x", ("f()", "h()"))
+        );
+        // Field boundaries matter
+        assert_ne!(
+            fingerprint("ab", "c", "r", "", ("", "")),
+            fingerprint("a", "bc", "r", "", ("", ""))
+        );
+    }
+}
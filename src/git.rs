@@ -0,0 +1,332 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::error::{LintScoutError, Result};
+
+/// Run the local `git` binary in `dir`, returning its standard output.
+pub fn run(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| LintScoutError::Git(format!("failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(LintScoutError::Git(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Run `git` in `dir` with `input` on its standard input, returning its
+/// standard output.
+pub fn run_with_input(dir: &Path, args: &[&str], input: Vec<u8>) -> Result<String> {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| LintScoutError::Git(format!("failed to run git: {e}")))?;
+
+    // Write from another thread so a full stdout pipe cannot block us
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = thread::spawn(move || stdin.write_all(&input));
+    let output = child.wait_with_output()?;
+    // git may exit before reading all of its input, e.g. on a bad argument
+    let _ = writer.join();
+    if !output.status.success() {
+        return Err(LintScoutError::Git(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The directory to run git in for a scan path, which may be a file.
+pub fn work_dir(path: &Path) -> &Path {
+    if path.is_dir() {
+        return path;
+    }
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// The pathspec that selects a scan path when git runs in its
+/// [`work_dir`].
+pub fn pathspec(path: &Path) -> String {
+    match path.file_name() {
+        Some(name) if !path.is_dir() => name.to_string_lossy().into_owned(),
+        _ => ".".to_string(),
+    }
+}
+
+/// The root of the working tree containing `dir`.
+pub fn toplevel(dir: &Path) -> Result<PathBuf> {
+    run(dir, &["rev-parse", "--show-toplevel"]).map(|out| PathBuf::from(out.trim()))
+}
+
+/// Unified diff of the working tree against `base`, without context lines.
+pub fn diff(dir: &Path, base: &str) -> Result<String> {
+    run(
+        dir,
+        &[
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            "--unified=0",
+            base,
+            "--",
+        ],
+    )
+}
+
+/// Regular files staged in the index that match `pathspec`, with their blob
+/// ids. Paths are relative to `dir`; unmerged entries are skipped.
+pub fn staged_files(dir: &Path, pathspec: &str) -> Result<Vec<(PathBuf, String)>> {
+    let out = run(dir, &["ls-files", "--stage", "-z", "--", pathspec])?;
+    let mut files = Vec::new();
+    for entry in out.split('\0').filter(|e| !e.is_empty()) {
+        // "<mode> <oid> <stage>\t<path>"
+        let Some((info, path)) = entry.split_once('\t') else {
+            continue;
+        };
+        let mut fields = info.split(' ');
+        let (Some(mode), Some(oid), Some(stage)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if matches!(mode, "100644" | "100755") && stage == "0" {
+            files.push((PathBuf::from(path), oid.to_string()));
+        }
+    }
+    Ok(files)
+}
+
+/// Read the contents of blobs with a single `git cat-file --batch` process.
+pub fn read_blobs(dir: &Path, oids: &[String]) -> Result<Vec<Vec<u8>>> {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["cat-file", "--batch"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| LintScoutError::Git(format!("failed to run git: {e}")))?;
+
+    // Write requests from another thread so a full stdout pipe cannot block us
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let requests: String = oids.iter().map(|oid| format!("{oid}\n")).collect();
+    let writer = thread::spawn(move || stdin.write_all(requests.as_bytes()));
+
+    let mut reader = BufReader::new(child.stdout.take().expect("stdout is piped"));
+    let mut blobs = Vec::with_capacity(oids.len());
+    for oid in oids {
+        // "<oid> <type> <size>", or "<oid> missing"
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let size = header
+            .split_whitespace()
+            .nth(2)
+            .and_then(|s| s.parse::<usize>().ok())
+            .ok_or_else(|| {
+                LintScoutError::Git(format!("cannot read object {oid}: {}", header.trim()))
+            })?;
+        // Contents are followed by a newline
+        let mut content = vec![0; size + 1];
+        reader.read_exact(&mut content)?;
+        content.pop();
+        blobs.push(content);
+    }
+
+    writer
+        .join()
+        .map_err(|_| LintScoutError::Git("failed to write to git cat-file".into()))??;
+    child.wait()?;
+    Ok(blobs)
+}
+
+/// Regular files in the tree of `rev` that match `pathspec`, with their blob
+/// ids. Paths are relative to `dir`.
+pub fn tree_files(dir: &Path, rev: &str, pathspec: &str) -> Result<Vec<(PathBuf, String)>> {
+    let out = run(dir, &["ls-tree", "-r", "-z", rev, pathspec])?;
+    let mut files = Vec::new();
+    for entry in out.split('\0').filter(|e| !e.is_empty()) {
+        // "<mode> <type> <oid>\t<path>"
+        let Some((info, path)) = entry.split_once('\t') else {
+            continue;
+        };
+        let mut fields = info.split(' ');
+        let (Some(mode), Some("blob"), Some(oid)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if matches!(mode, "100644" | "100755") {
+            files.push((PathBuf::from(path), oid.to_string()));
+        }
+    }
+    Ok(files)
+}
+
+/// Files in the tree of `rev` that match `pathspec`, with their blob ids.
+/// With `respect_gitignore`, files matched by the `.gitignore` files
+/// committed in that tree are left out, as a checkout would be walked.
+pub fn tree_entries(
+    dir: &Path,
+    rev: &str,
+    pathspec: &str,
+    respect_gitignore: bool,
+) -> Result<Vec<(PathBuf, String)>> {
+    let mut files = tree_files(dir, rev, pathspec)?;
+    if respect_gitignore {
+        let (paths, oids): (Vec<PathBuf>, Vec<String>) = files
+            .iter()
+            .filter(|(path, _)| path.file_name().is_some_and(|n| n == ".gitignore"))
+            .cloned()
+            .unzip();
+        let matchers = gitignore_matchers(paths.into_iter().zip(read_blobs(dir, &oids)?));
+        files.retain(|(path, _)| !is_ignored(&matchers, path));
+    }
+    Ok(files)
+}
+
+/// Like [`tree_entries`], with the contents of each file.
+pub fn tree_contents(
+    dir: &Path,
+    rev: &str,
+    pathspec: &str,
+    respect_gitignore: bool,
+) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let (paths, oids): (Vec<PathBuf>, Vec<String>) =
+        tree_entries(dir, rev, pathspec, respect_gitignore)?
+            .into_iter()
+            .unzip();
+    let blobs = read_blobs(dir, &oids)?;
+    Ok(paths.into_iter().zip(blobs).collect())
+}
+
+/// Commits on the first-parent history of `rev`, newest first, with their
+/// committer time in seconds since the Unix epoch.
+pub fn first_parent_log(dir: &Path, rev: &str) -> Result<Vec<(String, i64)>> {
+    let out = run(
+        dir,
+        &["log", "--first-parent", "--format=%H %ct", rev, "--"],
+    )?;
+    Ok(out
+        .lines()
+        .filter_map(|line| {
+            let (commit, time) = line.split_once(' ')?;
+            Some((commit.to_string(), time.parse().ok()?))
+        })
+        .collect())
+}
+
+/// The files changed by each commit touching `pathspec`, relative to `dir`,
+/// optionally limited to commits made since a timestamp.
+pub fn changed_files(dir: &Path, since: Option<i64>, pathspec: &str) -> Result<Vec<Vec<String>>> {
+    let since = since.map(|t| format!("--since={}T00:00:00Z", crate::date::format_date(t)));
+    let mut args = vec!["log", "--format=%x1e", "--name-only", "--relative"];
+    args.extend(since.as_deref());
+    args.extend(["--", pathspec]);
+    let out = run(dir, &args)?;
+    // Each commit starts with a record separator line
+    Ok(out
+        .split('\x1e')
+        .skip(1)
+        .map(|commit| {
+            commit
+                .lines()
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .collect())
+}
+
+/// Build a matcher per `.gitignore` file, deepest first.
+fn gitignore_matchers(files: impl Iterator<Item = (PathBuf, Vec<u8>)>) -> Vec<Gitignore> {
+    let mut matchers = Vec::new();
+    for (path, content) in files {
+        let mut builder = GitignoreBuilder::new(path.parent().unwrap_or(Path::new("")));
+        for line in String::from_utf8_lossy(&content).lines() {
+            // Like git, skip patterns that fail to parse
+            let _ = builder.add_line(Some(path.clone()), line);
+        }
+        if let Ok(matcher) = builder.build() {
+            matchers.push(matcher);
+        }
+    }
+    matchers.sort_by_key(|m| std::cmp::Reverse(m.path().components().count()));
+    matchers
+}
+
+/// Whether the deepest `.gitignore` with an opinion on `path` ignores it.
+fn is_ignored(matchers: &[Gitignore], path: &Path) -> bool {
+    for matcher in matchers.iter().filter(|m| path.starts_with(m.path())) {
+        let matched = matcher.matched_path_or_any_parents(path, false);
+        if matched.is_ignore() {
+            return true;
+        }
+        if matched.is_whitelist() {
+            return false;
+        }
+    }
+    false
+}
+
+/// Staged files matching `pathspec` with their contents in the index.
+pub fn staged_contents(dir: &Path, pathspec: &str) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let (paths, oids): (Vec<PathBuf>, Vec<String>) =
+        staged_files(dir, pathspec)?.into_iter().unzip();
+    let blobs = read_blobs(dir, &oids)?;
+    Ok(paths.into_iter().zip(blobs).collect())
+}
+
+/// The hooks directory of the repository containing `dir`, honoring
+/// `core.hooksPath`.
+pub fn hooks_dir(dir: &Path) -> Result<PathBuf> {
+    let out = run(dir, &["rev-parse", "--git-path", "hooks"])?;
+    Ok(dir.join(out.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matchers(files: &[(&str, &str)]) -> Vec<Gitignore> {
+        gitignore_matchers(
+            files
+                .iter()
+                .map(|(path, content)| (PathBuf::from(path), content.as_bytes().to_vec())),
+        )
+    }
+
+    #[test]
+    fn committed_gitignores_apply_to_their_subtree() {
+        let m = matchers(&[
+            (".gitignore", "generated/\n*.min.js\n"),
+            ("web/.gitignore", "!keep.min.js\n"),
+        ]);
+        assert!(is_ignored(&m, Path::new("generated/api.py")));
+        assert!(is_ignored(&m, Path::new("lib/app.min.js")));
+        assert!(!is_ignored(&m, Path::new("web/keep.min.js")));
+        assert!(is_ignored(&m, Path::new("web/other.min.js")));
+        assert!(!is_ignored(&m, Path::new("src/app.js")));
+    }
+}
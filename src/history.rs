@@ -0,0 +1,265 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::date::{self, SECONDS_PER_DAY};
+use crate::density::top_level_directory;
+use crate::error::Result;
+use crate::git;
+use crate::scanner::Scanner;
+use crate::scope::Scope;
+
+/// How often to sample history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Interval {
+    Day,
+    Week,
+    Month,
+}
+
+/// Suppression counts at one sampled revision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Sample {
+    /// The sampled date; the revision is the last commit on or before it.
+    pub date: String,
+    pub commit: String,
+    pub total: u64,
+    pub by_scout: BTreeMap<String, u64>,
+    pub by_rule: BTreeMap<String, u64>,
+    /// Counts per top-level directory, with `.` for files at the root.
+    pub by_directory: BTreeMap<String, u64>,
+}
+
+/// Sample dates from `since` to `until` (inclusive), as midnight UTC
+/// timestamps. Monthly samples keep the day of month where possible.
+pub fn sample_times(since: i64, until: i64, every: Interval) -> Vec<i64> {
+    let since = since.div_euclid(SECONDS_PER_DAY) * SECONDS_PER_DAY;
+    let (year, month, day) = date::civil_from_days(since / SECONDS_PER_DAY);
+    let mut times = Vec::new();
+    for n in 0.. {
+        let time = match every {
+            Interval::Day => since + n * SECONDS_PER_DAY,
+            Interval::Week => since + n * 7 * SECONDS_PER_DAY,
+            Interval::Month => {
+                let months = i64::from(month) - 1 + n;
+                let (y, m) = (
+                    year + months.div_euclid(12),
+                    months.rem_euclid(12) as u32 + 1,
+                );
+                date::days_from_civil(y, m, day.min(date::days_in_month(y, m))) * SECONDS_PER_DAY
+            }
+        };
+        if time > until {
+            break;
+        }
+        times.push(time);
+    }
+    times
+}
+
+/// Scans sampled revisions of a branch straight from git objects. Files
+/// whose blob did not change between samples are not scanned again.
+pub struct History {
+    dir: PathBuf,
+    pathspec: String,
+    respect_gitignore: bool,
+    scopes: Option<Vec<Scope>>,
+    scanner: Scanner,
+    /// (scout, rule) of each finding, by path and blob id
+    cache: HashMap<(PathBuf, String), Vec<(String, String)>>,
+}
+
+impl History {
+    /// Sample the files under `dir` matching `pathspec`; `scanner` must be
+    /// rooted at `dir`.
+    pub fn new(dir: &Path, pathspec: &str, scanner: Scanner) -> Self {
+        History {
+            dir: dir.to_path_buf(),
+            pathspec: pathspec.to_string(),
+            respect_gitignore: true,
+            scopes: None,
+            scanner,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn with_gitignore(mut self, respect: bool) -> Self {
+        self.respect_gitignore = respect;
+        self
+    }
+
+    /// Only count suppressions with these scopes.
+    pub fn with_scopes(mut self, scopes: Option<Vec<Scope>>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Sample the first-parent history of `rev` at each time in `times`,
+    /// skipping times before its first commit.
+    pub fn run(&mut self, rev: &str, times: &[i64]) -> Result<Vec<Sample>> {
+        let log = git::first_parent_log(&self.dir, rev)?;
+        let mut samples = Vec::new();
+        for &time in times {
+            // The state at the end of the sampled day
+            let end_of_day = time + SECONDS_PER_DAY - 1;
+            let Some((commit, _)) = log.iter().find(|(_, t)| *t <= end_of_day) else {
+                continue;
+            };
+            samples.push(self.sample(commit, time)?);
+        }
+        Ok(samples)
+    }
+
+    fn sample(&mut self, commit: &str, time: i64) -> Result<Sample> {
+        let entries = git::tree_entries(&self.dir, commit, &self.pathspec, self.respect_gitignore)?;
+
+        let mut missing = Vec::new();
+        for entry in &entries {
+            if self.cache.contains_key(entry) {
+                continue;
+            }
+            if self.scanner.applies_to_file(&self.dir.join(&entry.0)) {
+                missing.push(entry.clone());
+            } else {
+                self.cache.insert(entry.clone(), Vec::new());
+            }
+        }
+        if !missing.is_empty() {
+            let oids: Vec<String> = missing.iter().map(|(_, oid)| oid.clone()).collect();
+            let blobs = git::read_blobs(&self.dir, &oids)?;
+            let files = missing
+                .iter()
+                .map(|(path, _)| path.clone())
+                .zip(blobs)
+                .collect();
+            let result = self.scanner.run_files(files)?;
+
+            let mut found: HashMap<PathBuf, Vec<(String, String)>> = HashMap::new();
+            for f in result.findings {
+                if self.scopes.as_ref().is_some_and(|s| !s.contains(&f.scope)) {
+                    continue;
+                }
+                let path = Path::new(&f.path);
+                let relative = path.strip_prefix(&self.dir).unwrap_or(path).to_path_buf();
+                found
+                    .entry(relative)
+                    .or_default()
+                    .push((f.scout_name, f.rule_id));
+            }
+            for entry in missing {
+                let findings = found.remove(&entry.0).unwrap_or_default();
+                self.cache.insert(entry, findings);
+            }
+        }
+
+        let mut sample = Sample {
+            date: date::format_date(time),
+            commit: commit.to_string(),
+            total: 0,
+            by_scout: BTreeMap::new(),
+            by_rule: BTreeMap::new(),
+            by_directory: BTreeMap::new(),
+        };
+        for entry in &entries {
+            let directory = top_level_directory(&entry.0);
+            for (scout, rule) in &self.cache[entry] {
+                sample.total += 1;
+                *sample.by_scout.entry(scout.clone()).or_default() += 1;
+                *sample.by_rule.entry(rule.clone()).or_default() += 1;
+                *sample.by_directory.entry(directory.clone()).or_default() += 1;
+            }
+        }
+        Ok(sample)
+    }
+}
+
+/// Format samples as CSV in long form: one row per date and count.
+pub fn to_csv(samples: &[Sample]) -> String {
+    let mut out = String::from("date,commit,dimension,key,count\n");
+    for s in samples {
+        let mut row = |dimension: &str, key: &str, count: u64| {
+            out.push_str(&format!(
+                "{},{},{},{},{}\n",
+                s.date,
+                s.commit,
+                dimension,
+                csv_field(key),
+                count
+            ));
+        };
+        row("total", "all", s.total);
+        for (key, count) in &s.by_scout {
+            row("scout", key, *count);
+        }
+        for (key, count) in &s.by_rule {
+            row("rule", key, *count);
+        }
+        for (key, count) in &s.by_directory {
+            row("directory", key, *count);
+        }
+    }
+    out
+}
+
+/// Format samples as a JSON array.
+pub fn to_json(samples: &[Sample]) -> String {
+    serde_json::to_string_pretty(samples).unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates(times: &[i64]) -> Vec<String> {
+        times.iter().map(|t| date::format_date(*t)).collect()
+    }
+
+    #[test]
+    fn samples_each_interval() {
+        let since = date::parse_date("2024-01-31").unwrap();
+        let until = date::parse_date("2024-04-30").unwrap();
+        assert_eq!(
+            dates(&sample_times(since, until, Interval::Month)),
+            ["2024-01-31", "2024-02-29", "2024-03-31", "2024-04-30"]
+        );
+        assert_eq!(
+            dates(&sample_times(
+                since,
+                since + 15 * SECONDS_PER_DAY,
+                Interval::Week
+            )),
+            ["2024-01-31", "2024-02-07", "2024-02-14"]
+        );
+        assert_eq!(sample_times(since, since, Interval::Day).len(), 1);
+        assert!(sample_times(until, since, Interval::Day).is_empty());
+    }
+
+    #[test]
+    fn csv_is_long_form() {
+        let sample = Sample {
+            date: "2024-01-01".into(),
+            commit: "abc".into(),
+            total: 2,
+            by_scout: BTreeMap::from([("flake8".into(), 2)]),
+            by_rule: BTreeMap::from([("flake8-noqa".into(), 2)]),
+            by_directory: BTreeMap::from([("a,b".into(), 2)]),
+        };
+        assert_eq!(
+            to_csv(&[sample]),
+            "date,commit,dimension,key,count\n\
+             2024-01-01,abc,total,all,2\n\
+             2024-01-01,abc,scout,flake8,2\n\
+             2024-01-01,abc,rule,flake8-noqa,2\n\
+             2024-01-01,abc,directory,\"a,b\",2\n"
+        );
+    }
+}
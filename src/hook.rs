@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{LintScoutError, Result};
+use crate::git;
+
+/// Pre-commit hook that checks the staged content of the commit.
+pub const PRE_COMMIT: &str = "\
+#!/bin/sh
+# Installed by `lintscout install-hook`.
+# Checks staged files for lint ignore directives before each commit.
+exec lintscout --staged
+";
+
+/// Write the pre-commit hook into the repository containing `dir`,
+/// returning its path. An existing hook is only replaced when `force` is set.
+pub fn install(dir: &Path, force: bool) -> Result<PathBuf> {
+    let hooks = git::hooks_dir(dir)?;
+    let path = hooks.join("pre-commit");
+    if path.exists() && !force {
+        return Err(LintScoutError::HookExists(path));
+    }
+    fs::create_dir_all(&hooks)?;
+    fs::write(&path, PRE_COMMIT)?;
+    make_executable(&path)?;
+    Ok(path)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,213 @@
+//! Ranking of files and directories where suppressions meet frequent change.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::scanner::ScanResult;
+
+/// A file with suppressions, ranked by [`score`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileHotspot {
+    pub path: String,
+    pub suppressions: u64,
+    /// Commits that changed the file in the analyzed window.
+    pub commits: u64,
+    /// Non-blank lines.
+    pub loc: u64,
+    pub score: f64,
+}
+
+/// A directory ranked by the summed score of its files with suppressions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DirectoryHotspot {
+    pub path: String,
+    pub files: u64,
+    pub suppressions: u64,
+    /// Commits that changed any file directly in the directory.
+    pub commits: u64,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Hotspots {
+    /// Start of the churn window (`YYYY-MM-DD`), or all history.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    pub files: Vec<FileHotspot>,
+    pub directories: Vec<DirectoryHotspot>,
+}
+
+/// How much a file's suppressions hurt: the suppression count, multiplied by
+/// one plus the number of commits changing the file and by one plus the
+/// suppressions per 100 lines. Suppressions in small, busy files rank first.
+pub fn score(suppressions: u64, commits: u64, loc: u64) -> f64 {
+    let density = suppressions as f64 * 100.0 / loc.max(1) as f64;
+    let score = suppressions as f64 * (1 + commits) as f64 * (1.0 + density);
+    (score * 100.0).round() / 100.0
+}
+
+impl Hotspots {
+    /// Rank the files with findings in `result`, whose paths lie under
+    /// `dir`, using the files changed per commit from
+    /// [`crate::git::changed_files`].
+    pub fn analyze(result: &ScanResult, dir: &Path, commits: &[Vec<String>]) -> Self {
+        let mut files: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+        for f in &result.findings {
+            let path = Path::new(&f.path);
+            let relative = path.strip_prefix(dir).unwrap_or(path);
+            let key = relative.to_string_lossy().replace('\\', "/");
+            let loc = result.lines.files.get(&f.path).map_or(0, |l| l.lines);
+            files.entry(key).or_insert((0, loc)).0 += 1;
+        }
+        Self::rank(files, commits)
+    }
+
+    /// Rank files given their (suppressions, lines) by relative path.
+    pub fn rank(files: BTreeMap<String, (u64, u64)>, commits: &[Vec<String>]) -> Self {
+        let mut file_commits: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut directory_commits: BTreeMap<&str, u64> = BTreeMap::new();
+        for changed in commits {
+            let mut directories = BTreeSet::new();
+            for path in changed {
+                *file_commits.entry(path).or_default() += 1;
+                directories.insert(parent(path));
+            }
+            for directory in directories {
+                *directory_commits.entry(directory).or_default() += 1;
+            }
+        }
+
+        let mut ranked: Vec<FileHotspot> = files
+            .iter()
+            .map(|(path, &(suppressions, loc))| {
+                let commits = file_commits.get(path.as_str()).copied().unwrap_or(0);
+                FileHotspot {
+                    path: path.clone(),
+                    suppressions,
+                    commits,
+                    loc,
+                    score: score(suppressions, commits, loc),
+                }
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.path.cmp(&b.path)));
+
+        let mut directories: BTreeMap<&str, DirectoryHotspot> = BTreeMap::new();
+        for file in &ranked {
+            let path = parent(&file.path);
+            let directory = directories.entry(path).or_insert_with(|| DirectoryHotspot {
+                path: path.to_string(),
+                files: 0,
+                suppressions: 0,
+                commits: directory_commits.get(path).copied().unwrap_or(0),
+                score: 0.0,
+            });
+            directory.files += 1;
+            directory.suppressions += file.suppressions;
+            directory.score += file.score;
+        }
+        let mut directories: Vec<DirectoryHotspot> = directories.into_values().collect();
+        for directory in &mut directories {
+            directory.score = (directory.score * 100.0).round() / 100.0;
+        }
+        directories.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.path.cmp(&b.path)));
+
+        Hotspots {
+            since: None,
+            files: ranked,
+            directories,
+        }
+    }
+
+    /// Keep only the `limit` highest ranked files and directories.
+    pub fn truncate(&mut self, limit: usize) {
+        self.files.truncate(limit);
+        self.directories.truncate(limit);
+    }
+
+    /// Format the ranking as `text` tables or `json`.
+    pub fn format(&self, format: &str) -> String {
+        if format == "json" {
+            return serde_json::to_string_pretty(self)
+                .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"));
+        }
+        if self.files.is_empty() {
+            return "No lint ignore directives found.\n".to_string();
+        }
+        let mut out = format!(
+            "{:>10} {:>6} {:>8} {:>7}  File\n",
+            "Score", "Supp", "Commits", "LOC"
+        );
+        for f in &self.files {
+            out.push_str(&format!(
+                "{:>10.2} {:>6} {:>8} {:>7}  {}\n",
+                f.score, f.suppressions, f.commits, f.loc, f.path
+            ));
+        }
+        out.push_str(&format!(
+            "\n{:>10} {:>6} {:>8} {:>7}  Directory\n",
+            "Score", "Supp", "Commits", "Files"
+        ));
+        for d in &self.directories {
+            out.push_str(&format!(
+                "{:>10.2} {:>6} {:>8} {:>7}  {}\n",
+                d.score, d.suppressions, d.commits, d.files, d.path
+            ));
+        }
+        out
+    }
+}
+
+/// The directory containing a `/`-separated relative path, `.` at the root.
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map_or(".", |(dir, _)| dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commits(changed: &[&[&str]]) -> Vec<Vec<String>> {
+        changed
+            .iter()
+            .map(|c| c.iter().map(|p| p.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn scores_weigh_churn_and_density() {
+        assert_eq!(score(2, 0, 200), 4.0);
+        assert_eq!(score(2, 3, 200), 16.0);
+        assert_eq!(score(1, 0, 0), 101.0);
+    }
+
+    #[test]
+    fn ranks_files_and_directories() {
+        let files = BTreeMap::from([
+            ("src/busy.py".to_string(), (1, 100)),
+            ("src/quiet.py".to_string(), (1, 100)),
+            ("setup.py".to_string(), (4, 200)),
+        ]);
+        let history = commits(&[
+            &["src/busy.py", "src/other.py"],
+            &["src/busy.py"],
+            &["src/other.py", "README.md"],
+        ]);
+        let hotspots = Hotspots::rank(files, &history);
+        let order: Vec<_> = hotspots.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(order, ["setup.py", "src/busy.py", "src/quiet.py"]);
+        assert_eq!(hotspots.files[1].commits, 2);
+        assert_eq!(hotspots.files[1].score, 6.0);
+
+        let root = &hotspots.directories[0];
+        assert_eq!(
+            (root.path.as_str(), root.commits, root.score),
+            (".", 1, 12.0)
+        );
+        let src = &hotspots.directories[1];
+        assert_eq!((src.path.as_str(), src.files, src.commits), ("src", 2, 3));
+        assert_eq!(src.score, 8.0);
+    }
+}
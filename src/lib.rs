@@ -1,11 +1,36 @@
+pub mod acknowledge;
+pub mod attribution;
+pub mod baseline;
+pub mod blame;
+pub mod budget;
 pub mod builtin;
+pub mod catalog;
 pub mod cli;
+pub mod compare;
 pub mod config;
+pub mod date;
+pub mod density;
+pub mod diff;
 pub mod error;
+pub mod exemption;
+pub mod expiry;
 pub mod finding;
+pub mod fingerprint;
+pub mod git;
+pub mod history;
+pub mod hook;
+pub mod hotspot;
+pub mod linter_config;
+pub mod orphan;
 pub mod output;
+pub mod policy;
+pub mod ratchet;
+pub mod region;
 pub mod registry;
 pub mod rule;
 pub mod scanner;
+pub mod scope;
 pub mod scout;
 pub mod stats;
+pub mod subtree;
+pub mod syntax;
@@ -0,0 +1,252 @@
+//! Where each linter's config exempts files from it, or from some of its
+//! rules, by config file.
+
+use super::extract::{get_option, is_off};
+use super::{ConfigExemption, Node, Value};
+
+fn exemption(
+    linter: &'static str,
+    patterns: Vec<(String, usize)>,
+    rules: Vec<String>,
+) -> ConfigExemption {
+    ConfigExemption {
+        linter,
+        patterns,
+        rules,
+    }
+}
+
+/// The glob patterns of a sequence, or of a single string.
+fn patterns(node: &Node) -> Vec<(String, usize)> {
+    match node.value {
+        Value::Scalar(ref pattern) => vec![(pattern.clone(), node.line)],
+        _ => node.list(),
+    }
+}
+
+pub fn none(_: &Node) -> Vec<ConfigExemption> {
+    Vec::new()
+}
+
+/// Every pattern of an ignore file, parsed as a sequence of its lines.
+fn ignore_file(linter: &'static str, root: &Node) -> Vec<ConfigExemption> {
+    vec![exemption(linter, root.list(), Vec::new())]
+}
+
+pub fn eslintignore(root: &Node) -> Vec<ConfigExemption> {
+    ignore_file("eslint", root)
+}
+
+pub fn prettierignore(root: &Node) -> Vec<ConfigExemption> {
+    ignore_file("prettier", root)
+}
+
+pub fn stylelintignore(root: &Node) -> Vec<ConfigExemption> {
+    ignore_file("stylelint", root)
+}
+
+/// `ignorePatterns`, and the `files` of `overrides` turning rules off.
+fn eslint_config(config: &Node, out: &mut Vec<ConfigExemption>) {
+    if let Some(ignored) = config.get("ignorePatterns") {
+        out.push(exemption("eslint", patterns(ignored), Vec::new()));
+    }
+    for o in config.get("overrides").map_or(&[][..], Node::items) {
+        let (Some(files), Some(rules)) = (o.get("files"), o.get("rules")) else {
+            continue;
+        };
+        let off: Vec<String> = rules
+            .entries()
+            .iter()
+            .filter(|e| is_off(&e.value, &["off", "0"]))
+            .map(|e| e.key.clone())
+            .collect();
+        if !off.is_empty() {
+            out.push(exemption("eslint", patterns(files), off));
+        }
+    }
+}
+
+pub fn eslintrc(root: &Node) -> Vec<ConfigExemption> {
+    let mut out = Vec::new();
+    eslint_config(root, &mut out);
+    out
+}
+
+pub fn package_json(root: &Node) -> Vec<ConfigExemption> {
+    let mut out = Vec::new();
+    if let Some(config) = root.get("eslintConfig") {
+        eslint_config(config, &mut out);
+    }
+    out
+}
+
+pub fn pyproject(root: &Node) -> Vec<ConfigExemption> {
+    let mut out = Vec::new();
+    if let Some(ruff) = root.get_path(&["tool", "ruff"]) {
+        ruff_config(ruff, &mut out);
+    }
+    out
+}
+
+pub fn ruff_toml(root: &Node) -> Vec<ConfigExemption> {
+    let mut out = Vec::new();
+    ruff_config(root, &mut out);
+    out
+}
+
+fn ruff_config(ruff: &Node, out: &mut Vec<ConfigExemption>) {
+    for key in ["exclude", "extend-exclude"] {
+        if let Some(excluded) = ruff.get(key) {
+            out.push(exemption("ruff", excluded.list(), Vec::new()));
+        }
+    }
+    for table in [Some(ruff), ruff.get("lint")].into_iter().flatten() {
+        for key in ["per-file-ignores", "extend-per-file-ignores"] {
+            for e in table.get(key).map_or(&[][..], Node::entries) {
+                let rules = e.value.list().into_iter().map(|(rule, _)| rule).collect();
+                out.push(exemption("ruff", vec![(e.key.clone(), e.line)], rules));
+            }
+        }
+    }
+}
+
+/// `setup.cfg`, `tox.ini` and `.flake8`.
+pub fn setup_cfg(root: &Node) -> Vec<ConfigExemption> {
+    let mut out = Vec::new();
+    let Some(flake8) = root.get("flake8") else {
+        return out;
+    };
+    for key in ["exclude", "extend-exclude"] {
+        if let Some(excluded) = get_option(flake8, key) {
+            out.push(exemption("flake8", excluded.list(), Vec::new()));
+        }
+    }
+    for key in ["per-file-ignores", "extend-per-file-ignores"] {
+        if let Some(mapping) = get_option(flake8, key) {
+            out.extend(
+                flake8_mapping(mapping)
+                    .into_iter()
+                    .map(|(pattern, rules)| exemption("flake8", vec![pattern], rules)),
+            );
+        }
+    }
+    out
+}
+
+/// Flake8's `file: code, code` mappings, where codes are letters followed
+/// by digits and anything else starts the next file pattern.
+fn flake8_mapping(mapping: &Node) -> Vec<((String, usize), Vec<String>)> {
+    let is_code = |s: &str| {
+        let digits = s.trim_start_matches(|c: char| c.is_ascii_uppercase());
+        digits.len() < s.len() && digits.chars().all(|c| c.is_ascii_digit())
+    };
+    let mut found: Vec<((String, usize), Vec<String>)> = Vec::new();
+    for (token, line) in mapping.list() {
+        for piece in token.split(':').filter(|p| !p.is_empty()) {
+            match found.last_mut() {
+                Some((_, codes)) if is_code(piece) => codes.push(piece.to_string()),
+                _ => found.push(((piece.to_string(), line), Vec::new())),
+            }
+        }
+    }
+    found.retain(|(_, codes)| !codes.is_empty());
+    found
+}
+
+pub fn rubocop(root: &Node) -> Vec<ConfigExemption> {
+    let mut out = Vec::new();
+    for cop in root.entries() {
+        let Some(excluded) = cop.value.get("Exclude") else {
+            continue;
+        };
+        let rules = if cop.key == "AllCops" {
+            Vec::new()
+        } else {
+            vec![cop.key.clone()]
+        };
+        out.push(exemption("rubocop", excluded.list(), rules));
+    }
+    out
+}
+
+/// Rules in `ignored` are off for every Dockerfile.
+pub fn hadolint(root: &Node) -> Vec<ConfigExemption> {
+    let Some(ignored) = root.get("ignored") else {
+        return Vec::new();
+    };
+    let rules: Vec<String> = ignored.list().into_iter().map(|(rule, _)| rule).collect();
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    vec![exemption(
+        "hadolint",
+        vec![("*".to_string(), ignored.line)],
+        rules,
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ini, json, toml};
+    use super::*;
+
+    type Summary = (Vec<(String, usize)>, Vec<String>);
+
+    fn summary(found: Vec<ConfigExemption>) -> Vec<Summary> {
+        found.into_iter().map(|e| (e.patterns, e.rules)).collect()
+    }
+
+    #[test]
+    fn flake8_mappings() {
+        let root = ini::parse(
+            "[flake8]\nexclude = build,.venv\nper-file-ignores =\n    __init__.py:F401\n    tests/*: S101, E501\n",
+        );
+        assert_eq!(
+            summary(setup_cfg(&root)),
+            [
+                (vec![("build".into(), 2), (".venv".into(), 2)], Vec::new()),
+                (vec![("__init__.py".into(), 4)], vec!["F401".into()]),
+                (
+                    vec![("tests/*".into(), 5)],
+                    vec!["S101".into(), "E501".into()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn ruff_per_file_ignores() {
+        let root = toml::parse(
+            "[tool.ruff]\nextend-exclude = [\"migrations\"]\n\n[tool.ruff.lint.per-file-ignores]\n\"tests/**\" = [\"S101\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            summary(pyproject(&root)),
+            [
+                (vec![("migrations".into(), 2)], Vec::new()),
+                (vec![("tests/**".into(), 5)], vec!["S101".into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn eslint_overrides_turning_rules_off() {
+        let root = json::parse(
+            r#"{
+  "ignorePatterns": "dist/",
+  "overrides": [
+    { "files": ["*.test.js"], "rules": { "no-console": "off", "eqeqeq": "error" } },
+    { "files": "*.ts", "rules": { "eqeqeq": "error" } }
+  ]
+}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            summary(eslintrc(&root)),
+            [
+                (vec![("dist/".into(), 2)], Vec::new()),
+                (vec![("*.test.js".into(), 4)], vec!["no-console".into()]),
+            ]
+        );
+    }
+}
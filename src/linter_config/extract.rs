@@ -0,0 +1,791 @@
+//! Where each linter's config turns rules off, by config file.
+
+use super::{ConfigSuppression, Node, Value};
+
+const DISABLE: &str = "config-disable";
+const EXCLUDE: &str = "config-exclude";
+
+/// TypeScript options that `"strict": true` turns on, and the checks
+/// switched off by setting them to false.
+const STRICT_OPTIONS: &[&str] = &[
+    "strict",
+    "alwaysStrict",
+    "noImplicitAny",
+    "noImplicitThis",
+    "strictBindCallApply",
+    "strictBuiltinIteratorReturn",
+    "strictFunctionTypes",
+    "strictNullChecks",
+    "strictPropertyInitialization",
+    "useUnknownInCatchVariables",
+];
+
+struct Found<'a> {
+    linter: &'static str,
+    out: &'a mut Vec<ConfigSuppression>,
+}
+
+impl Found<'_> {
+    fn push(
+        &mut self,
+        rule_id: &'static str,
+        description: &'static str,
+        line: usize,
+        key_path: String,
+        rules: Vec<String>,
+    ) {
+        self.out.push(ConfigSuppression {
+            linter: self.linter,
+            rule_id,
+            description,
+            line,
+            key_path,
+            rules,
+        });
+    }
+
+    /// One suppression per rule listed in `node`.
+    fn list(&mut self, description: &'static str, node: &Node, key_path: String) {
+        for (rule, line) in node.list() {
+            self.push(DISABLE, description, line, key_path.clone(), vec![rule]);
+        }
+    }
+}
+
+fn found<'a>(linter: &'static str, out: &'a mut Vec<ConfigSuppression>) -> Found<'a> {
+    Found { linter, out }
+}
+
+/// Whether an ESLint-style rule setting, a level or an array starting with
+/// one, is one of the `off` levels.
+pub(super) fn is_off(setting: &Node, off: &[&str]) -> bool {
+    let level = match setting.value {
+        Value::Seq(ref items) => items.first(),
+        _ => Some(setting),
+    };
+    level
+        .and_then(Node::as_str)
+        .is_some_and(|level| off.iter().any(|o| level.eq_ignore_ascii_case(o)))
+}
+
+/// Rules turned off in the `rules` of `config` and of its `overrides`.
+fn rules_off(f: &mut Found, description: &'static str, config: &Node, prefix: &str, off: &[&str]) {
+    if let Some(rules) = config.get("rules") {
+        for e in rules.entries() {
+            if is_off(&e.value, off) {
+                f.push(
+                    DISABLE,
+                    description,
+                    e.line,
+                    format!("{prefix}rules.{}", e.key),
+                    vec![e.key.clone()],
+                );
+            }
+        }
+    }
+    if let Some(overrides) = config.get("overrides") {
+        for (i, o) in overrides.items().iter().enumerate() {
+            rules_off(f, description, o, &format!("{prefix}overrides[{i}]."), off);
+        }
+    }
+}
+
+pub fn none(_: &Node) -> Vec<ConfigSuppression> {
+    Vec::new()
+}
+
+const ESLINT: &str = "ESLint rule turned off in config";
+const STYLELINT: &str = "Stylelint rule turned off in config";
+
+pub fn eslintrc(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    rules_off(
+        &mut found("eslint", &mut out),
+        ESLINT,
+        root,
+        "",
+        &["off", "0"],
+    );
+    out
+}
+
+pub fn stylelintrc(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    rules_off(
+        &mut found("stylelint", &mut out),
+        STYLELINT,
+        root,
+        "",
+        &["null"],
+    );
+    out
+}
+
+pub fn package_json(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    if let Some(config) = root.get("eslintConfig") {
+        rules_off(
+            &mut found("eslint", &mut out),
+            ESLINT,
+            config,
+            "eslintConfig.",
+            &["off", "0"],
+        );
+    }
+    if let Some(config) = root.get("stylelint") {
+        rules_off(
+            &mut found("stylelint", &mut out),
+            STYLELINT,
+            config,
+            "stylelint.",
+            &["null"],
+        );
+    }
+    out
+}
+
+pub fn oxlintrc(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    rules_off(
+        &mut found("oxlint", &mut out),
+        "oxlint rule turned off in config",
+        root,
+        "",
+        &["off", "allow", "0"],
+    );
+    out
+}
+
+pub fn biome(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    let mut f = found("biome", &mut out);
+    biome_linter(&mut f, root, "");
+    for (i, o) in root
+        .get("overrides")
+        .map_or(&[][..], Node::items)
+        .iter()
+        .enumerate()
+    {
+        biome_linter(&mut f, o, &format!("overrides[{i}]."));
+    }
+    out
+}
+
+fn biome_linter(f: &mut Found, config: &Node, prefix: &str) {
+    let Some(linter) = config.get("linter") else {
+        return;
+    };
+    if let Some(enabled) = linter
+        .get("enabled")
+        .filter(|e| e.as_str() == Some("false"))
+    {
+        f.push(
+            DISABLE,
+            "Biome linter turned off in config",
+            enabled.line,
+            format!("{prefix}linter.enabled"),
+            Vec::new(),
+        );
+    }
+    let off = |node: &Node| node.as_str().or_else(|| node.get("level")?.as_str()) == Some("off");
+    for group in linter.get("rules").map_or(&[][..], Node::entries) {
+        let key_path = format!("{prefix}linter.rules.{}", group.key);
+        if off(&group.value) {
+            f.push(
+                DISABLE,
+                "Biome rule group turned off in config",
+                group.line,
+                key_path,
+                vec![format!("lint/{}", group.key)],
+            );
+            continue;
+        }
+        for rule in group.value.entries().iter().filter(|r| off(&r.value)) {
+            f.push(
+                DISABLE,
+                "Biome rule turned off in config",
+                rule.line,
+                format!("{key_path}.{}", rule.key),
+                vec![format!("lint/{}/{}", group.key, rule.key)],
+            );
+        }
+    }
+}
+
+pub fn tsconfig(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    let mut f = found("typescript", &mut out);
+    for e in root.get("compilerOptions").map_or(&[][..], Node::entries) {
+        if STRICT_OPTIONS.contains(&e.key.as_str()) && e.value.as_str() == Some("false") {
+            f.push(
+                DISABLE,
+                "TypeScript strict check turned off in config",
+                e.line,
+                format!("compilerOptions.{}", e.key),
+                vec![e.key.clone()],
+            );
+        }
+    }
+    out
+}
+
+pub fn pyproject(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    let Some(tool) = root.get("tool") else {
+        return out;
+    };
+    if let Some(ruff) = tool.get("ruff") {
+        ruff_config(&mut found("ruff", &mut out), ruff, "tool.ruff.");
+    }
+    if let Some(pylint) = tool.get("pylint") {
+        let mut f = found("pylint", &mut out);
+        pylint_disable(&mut f, pylint, "tool.pylint.");
+        for section in pylint.entries() {
+            pylint_disable(
+                &mut f,
+                &section.value,
+                &format!("tool.pylint.{}.", section.key),
+            );
+        }
+    }
+    if let Some(mypy) = tool.get("mypy") {
+        let mut f = found("mypy", &mut out);
+        mypy_config(&mut f, mypy, "tool.mypy.");
+        for (i, o) in mypy
+            .get("overrides")
+            .map_or(&[][..], Node::items)
+            .iter()
+            .enumerate()
+        {
+            mypy_config(&mut f, o, &format!("tool.mypy.overrides[{i}]."));
+        }
+    }
+    if let Some(pyright) = tool.get("pyright") {
+        pyright_config(&mut found("pyright", &mut out), pyright, "tool.pyright.");
+    }
+    if let Some(skips) = tool.get_path(&["bandit", "skips"]) {
+        found("bandit", &mut out).list(
+            "Bandit test skipped in config",
+            skips,
+            "tool.bandit.skips".into(),
+        );
+    }
+    out
+}
+
+pub fn ruff_toml(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    ruff_config(&mut found("ruff", &mut out), root, "");
+    out
+}
+
+fn ruff_config(f: &mut Found, ruff: &Node, prefix: &str) {
+    for (table, path) in [
+        (Some(ruff), prefix.to_string()),
+        (ruff.get("lint"), format!("{prefix}lint.")),
+    ] {
+        for key in ["ignore", "extend-ignore"] {
+            if let Some(codes) = table.and_then(|t| t.get(key)) {
+                f.list("Ruff rule ignored in config", codes, format!("{path}{key}"));
+            }
+        }
+    }
+}
+
+fn pylint_disable(f: &mut Found, section: &Node, prefix: &str) {
+    if let Some(disable) = section.get("disable") {
+        for (rule, line) in disable.list() {
+            let rules = if rule == "all" {
+                Vec::new()
+            } else {
+                vec![rule]
+            };
+            f.push(
+                DISABLE,
+                "Pylint message disabled in config",
+                line,
+                format!("{prefix}disable"),
+                rules,
+            );
+        }
+    }
+}
+
+fn mypy_config(f: &mut Found, section: &Node, prefix: &str) {
+    if let Some(codes) = get_option(section, "disable_error_code") {
+        f.list(
+            "mypy error code disabled in config",
+            codes,
+            format!("{prefix}disable_error_code"),
+        );
+    }
+    if let Some(ignore) = get_option(section, "ignore_errors").filter(|i| i.is_true()) {
+        f.push(
+            DISABLE,
+            "mypy errors ignored in config",
+            ignore.line,
+            format!("{prefix}ignore_errors"),
+            Vec::new(),
+        );
+    }
+}
+
+fn pyright_config(f: &mut Found, pyright: &Node, prefix: &str) {
+    for e in pyright.entries() {
+        let value = e.value.as_str();
+        if e.key == "typeCheckingMode" && value == Some("off") {
+            f.push(
+                DISABLE,
+                "Pyright type checking turned off in config",
+                e.line,
+                format!("{prefix}{}", e.key),
+                Vec::new(),
+            );
+        } else if e.key.starts_with("report") && matches!(value, Some("false" | "none")) {
+            f.push(
+                DISABLE,
+                "Pyright check turned off in config",
+                e.line,
+                format!("{prefix}{}", e.key),
+                vec![e.key.clone()],
+            );
+        }
+    }
+}
+
+pub fn pyrightconfig(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    pyright_config(&mut found("pyright", &mut out), root, "");
+    out
+}
+
+/// An INI option, whose names `configparser` matches case-insensitively and
+/// flake8 and mypy accept with dashes or underscores.
+pub(super) fn get_option<'a>(section: &'a Node, name: &str) -> Option<&'a Node> {
+    let normalize = |s: &str| s.to_ascii_lowercase().replace('-', "_");
+    section
+        .entries()
+        .iter()
+        .find(|e| normalize(&e.key) == normalize(name))
+        .map(|e| &e.value)
+}
+
+/// `setup.cfg`, `tox.ini`, `.flake8` and `mypy.ini`, whose sections are
+/// named after the tool they configure.
+pub fn setup_cfg(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    for section in root.entries() {
+        let name = section.key.as_str();
+        if name == "flake8" {
+            let mut f = found("flake8", &mut out);
+            for key in ["ignore", "extend-ignore"] {
+                if let Some(codes) = get_option(&section.value, key) {
+                    f.list(
+                        "Flake8 code ignored in config",
+                        codes,
+                        format!("flake8.{key}"),
+                    );
+                }
+            }
+        } else if name == "mypy" || name.starts_with("mypy-") {
+            mypy_config(
+                &mut found("mypy", &mut out),
+                &section.value,
+                &format!("{name}."),
+            );
+        } else if name.starts_with("pylint") {
+            pylint_disable(
+                &mut found("pylint", &mut out),
+                &section.value,
+                &format!("{name}."),
+            );
+        }
+    }
+    out
+}
+
+pub fn pylintrc(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    let mut f = found("pylint", &mut out);
+    for section in root.entries() {
+        pylint_disable(&mut f, &section.value, &format!("{}.", section.key));
+    }
+    out
+}
+
+pub fn cargo(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    let mut f = found("clippy", &mut out);
+    for prefix in [&["lints"][..], &["workspace", "lints"]] {
+        let Some(clippy) = root.get_path(prefix).and_then(|l| l.get("clippy")) else {
+            continue;
+        };
+        for e in clippy.entries() {
+            let level = e.value.as_str().or_else(|| e.value.get("level")?.as_str());
+            if level == Some("allow") {
+                f.push(
+                    DISABLE,
+                    "Clippy lint allowed in Cargo.toml",
+                    e.line,
+                    format!("{}.clippy.{}", prefix.join("."), e.key),
+                    vec![e.key.clone()],
+                );
+            }
+        }
+    }
+    out
+}
+
+pub fn golangci(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    let mut f = found("golangci-lint", &mut out);
+    if let Some(disable) = root.get_path(&["linters", "disable"]) {
+        f.list(
+            "golangci-lint linter disabled in config",
+            disable,
+            "linters.disable".into(),
+        );
+    }
+    // `issues.exclude-rules` in version 1 configs, `linters.exclusions.rules`
+    // in version 2
+    for (path, rules) in [
+        (
+            "issues.exclude-rules",
+            root.get_path(&["issues", "exclude-rules"]),
+        ),
+        (
+            "linters.exclusions.rules",
+            root.get_path(&["linters", "exclusions", "rules"]),
+        ),
+    ] {
+        for (i, rule) in rules.map_or(&[][..], Node::items).iter().enumerate() {
+            let linters = rule.get("linters").map(Node::list).unwrap_or_default();
+            f.push(
+                EXCLUDE,
+                "golangci-lint issues excluded in config",
+                rule.line,
+                format!("{path}[{i}]"),
+                linters.into_iter().map(|(linter, _)| linter).collect(),
+            );
+        }
+    }
+    if let Some(exclude) = root.get_path(&["issues", "exclude"]) {
+        for (_, line) in exclude.list() {
+            f.push(
+                EXCLUDE,
+                "golangci-lint issues excluded in config",
+                line,
+                "issues.exclude".into(),
+                Vec::new(),
+            );
+        }
+    }
+    out
+}
+
+pub fn rubocop(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    let mut f = found("rubocop", &mut out);
+    for cop in root.entries() {
+        // Cops are `Department/Name`; whole departments are capitalized
+        if cop.key == "AllCops" || !cop.key.starts_with(|c: char| c.is_ascii_uppercase()) {
+            continue;
+        }
+        if let Some(enabled) = cop
+            .value
+            .get("Enabled")
+            .filter(|e| e.as_str() == Some("false"))
+        {
+            f.push(
+                DISABLE,
+                "RuboCop cop disabled in config",
+                enabled.line,
+                format!("{}.Enabled", cop.key),
+                vec![cop.key.clone()],
+            );
+        }
+        if let Some(exclude) = cop.value.entries().iter().find(|e| e.key == "Exclude") {
+            f.push(
+                EXCLUDE,
+                "RuboCop cop excluded for some files in config",
+                exclude.line,
+                format!("{}.Exclude", cop.key),
+                vec![cop.key.clone()],
+            );
+        }
+    }
+    out
+}
+
+pub fn hadolint(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    if let Some(ignored) = root.get("ignored") {
+        found("hadolint", &mut out).list(
+            "Hadolint rule ignored in config",
+            ignored,
+            "ignored".into(),
+        );
+    }
+    out
+}
+
+pub fn shellcheckrc(root: &Node) -> Vec<ConfigSuppression> {
+    let mut out = Vec::new();
+    let mut f = found("shellcheck", &mut out);
+    for e in root.entries().iter().filter(|e| e.key == "disable") {
+        for (rule, line) in e.value.list() {
+            let rules = if rule == "all" {
+                Vec::new()
+            } else {
+                vec![rule]
+            };
+            f.push(
+                DISABLE,
+                "ShellCheck check disabled in config",
+                line,
+                "disable".into(),
+                rules,
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{json, toml, yaml};
+    use super::*;
+
+    fn summary(found: Vec<ConfigSuppression>) -> Vec<(&'static str, usize, String, Vec<String>)> {
+        found
+            .into_iter()
+            .map(|s| (s.linter, s.line, s.key_path, s.rules))
+            .collect()
+    }
+
+    fn rules(rules: &[&str]) -> Vec<String> {
+        rules.iter().map(|r| r.to_string()).collect()
+    }
+
+    #[test]
+    fn eslint_overrides_and_package_json() {
+        let root = json::parse(
+            r#"{
+  "eslintConfig": {
+    "rules": { "no-console": ["off", { "allow": ["warn"] }], "eqeqeq": "error" },
+    "overrides": [
+      { "files": ["*.test.js"], "rules": { "no-unused-expressions": 0 } }
+    ]
+  },
+  "stylelint": { "rules": { "color-no-invalid-hex": null } }
+}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            summary(package_json(&root)),
+            [
+                (
+                    "eslint",
+                    3,
+                    "eslintConfig.rules.no-console".into(),
+                    rules(&["no-console"])
+                ),
+                (
+                    "eslint",
+                    5,
+                    "eslintConfig.overrides[0].rules.no-unused-expressions".into(),
+                    rules(&["no-unused-expressions"])
+                ),
+                (
+                    "stylelint",
+                    8,
+                    "stylelint.rules.color-no-invalid-hex".into(),
+                    rules(&["color-no-invalid-hex"])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_and_biome() {
+        let root = json::parse(
+            "{\n  \"compilerOptions\": {\n    \"strict\": true,\n    \"noImplicitAny\": false,\n    \"allowJs\": false\n  }\n}",
+        )
+        .unwrap();
+        assert_eq!(
+            summary(tsconfig(&root)),
+            [(
+                "typescript",
+                4,
+                "compilerOptions.noImplicitAny".into(),
+                rules(&["noImplicitAny"])
+            )]
+        );
+        let root = json::parse(
+            r#"{"linter": {"rules": {"style": "off", "suspicious": {"noExplicitAny": {"level": "off"}, "noDebugger": "error"}}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            summary(biome(&root))
+                .into_iter()
+                .map(|(_, _, path, rules)| (path, rules))
+                .collect::<Vec<_>>(),
+            [
+                ("linter.rules.style".into(), rules(&["lint/style"])),
+                (
+                    "linter.rules.suspicious.noExplicitAny".into(),
+                    rules(&["lint/suspicious/noExplicitAny"])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_tools_in_pyproject() {
+        let root = toml::parse(
+            r#"[tool.ruff]
+extend-ignore = ["E501"]
+
+[tool.ruff.lint]
+ignore = ["B008", "S101"]
+
+[tool.pylint."messages control"]
+disable = ["missing-docstring"]
+
+[tool.mypy]
+disable_error_code = ["import-untyped"]
+
+[[tool.mypy.overrides]]
+module = "legacy.*"
+ignore_errors = true
+
+[tool.pyright]
+reportMissingImports = false
+reportUnusedVariable = "warning"
+
+[tool.bandit]
+skips = ["B101"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            summary(pyproject(&root)),
+            [
+                (
+                    "ruff",
+                    2,
+                    "tool.ruff.extend-ignore".into(),
+                    rules(&["E501"])
+                ),
+                ("ruff", 5, "tool.ruff.lint.ignore".into(), rules(&["B008"])),
+                ("ruff", 5, "tool.ruff.lint.ignore".into(), rules(&["S101"])),
+                (
+                    "pylint",
+                    8,
+                    "tool.pylint.messages control.disable".into(),
+                    rules(&["missing-docstring"])
+                ),
+                (
+                    "mypy",
+                    11,
+                    "tool.mypy.disable_error_code".into(),
+                    rules(&["import-untyped"])
+                ),
+                (
+                    "mypy",
+                    15,
+                    "tool.mypy.overrides[0].ignore_errors".into(),
+                    Vec::new()
+                ),
+                (
+                    "pyright",
+                    18,
+                    "tool.pyright.reportMissingImports".into(),
+                    rules(&["reportMissingImports"])
+                ),
+                ("bandit", 22, "tool.bandit.skips".into(), rules(&["B101"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn clippy_lints_in_cargo_toml() {
+        let root = toml::parse(
+            "[lints.clippy]\npedantic = { level = \"warn\", priority = -1 }\nmodule_name_repetitions = \"allow\"\n\n[workspace.lints.clippy]\ntodo = { level = \"allow\" }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            summary(cargo(&root)),
+            [
+                (
+                    "clippy",
+                    3,
+                    "lints.clippy.module_name_repetitions".into(),
+                    rules(&["module_name_repetitions"])
+                ),
+                (
+                    "clippy",
+                    6,
+                    "workspace.lints.clippy.todo".into(),
+                    rules(&["todo"])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn yaml_configs() {
+        let root = yaml::parse(
+            "linters:\n  disable:\n    - errcheck\nissues:\n  exclude-rules:\n    - path: _test\\.go\n      linters: [gosec]\n",
+        );
+        assert_eq!(
+            summary(golangci(&root)),
+            [
+                (
+                    "golangci-lint",
+                    3,
+                    "linters.disable".into(),
+                    rules(&["errcheck"])
+                ),
+                (
+                    "golangci-lint",
+                    6,
+                    "issues.exclude-rules[0]".into(),
+                    rules(&["gosec"])
+                ),
+            ]
+        );
+        let root = yaml::parse(
+            "AllCops:\n  Exclude: ['vendor/**/*']\nStyle/Documentation:\n  Enabled: false\nMetrics/MethodLength:\n  Max: 20\n  Exclude:\n    - 'db/**/*'\n",
+        );
+        assert_eq!(
+            summary(rubocop(&root)),
+            [
+                (
+                    "rubocop",
+                    4,
+                    "Style/Documentation.Enabled".into(),
+                    rules(&["Style/Documentation"])
+                ),
+                (
+                    "rubocop",
+                    7,
+                    "Metrics/MethodLength.Exclude".into(),
+                    rules(&["Metrics/MethodLength"])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn all_disables_everything() {
+        let root = super::super::ini::parse("disable=SC2086,all\n");
+        assert_eq!(
+            summary(shellcheckrc(&root)),
+            [
+                ("shellcheck", 1, "disable".into(), rules(&["SC2086"])),
+                ("shellcheck", 1, "disable".into(), Vec::new()),
+            ]
+        );
+    }
+}
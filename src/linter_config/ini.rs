@@ -0,0 +1,101 @@
+//! INI files as Python's `configparser` reads them for flake8, pylint and
+//! mypy: `[section]` headers, `key = value` or `key: value` options and
+//! values continued on indented lines. Options before the first section,
+//! as in `.shellcheckrc`, are kept at the root.
+
+use super::{Entry, Node, Value};
+
+pub fn parse(text: &str) -> Node {
+    let mut root = Node::map(1);
+    let mut section: Option<usize> = None;
+    let mut continues = false;
+    for (i, line) in text.lines().enumerate() {
+        let number = i + 1;
+        let trimmed = line.trim();
+        let entries = match section {
+            Some(index) => match root.value {
+                Value::Map(ref mut sections) => match sections[index].value.value {
+                    Value::Map(ref mut entries) => entries,
+                    _ => unreachable!("sections are maps"),
+                },
+                _ => unreachable!("the root is a map"),
+            },
+            None => match root.value {
+                Value::Map(ref mut entries) => entries,
+                _ => unreachable!("the root is a map"),
+            },
+        };
+        if trimmed.is_empty() {
+            continues = false;
+        } else if continues && line.starts_with([' ', '\t']) {
+            if let Some(Entry {
+                value:
+                    Node {
+                        value: Value::Scalar(ref mut value),
+                        ..
+                    },
+                ..
+            }) = entries.last_mut()
+            {
+                // Comment lines stay as empty lines so that items keep their
+                // line numbers
+                value.push('\n');
+                if !trimmed.starts_with(['#', ';']) {
+                    value.push_str(strip_comment(trimmed));
+                }
+            }
+        } else if let Some(name) = trimmed.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            let Value::Map(ref mut sections) = root.value else {
+                unreachable!("the root is a map");
+            };
+            sections.push(Entry {
+                key: name.trim().to_string(),
+                line: number,
+                value: Node::map(number),
+            });
+            section = Some(sections.len() - 1);
+            continues = false;
+        } else if let Some(split) = trimmed
+            .find(['=', ':'])
+            .filter(|_| !trimmed.starts_with(['#', ';']))
+        {
+            entries.push(Entry {
+                key: trimmed[..split].trim().to_string(),
+                line: number,
+                value: Node::scalar(number, trimmed[split + 1..].trim()),
+            });
+            continues = true;
+        }
+    }
+    root
+}
+
+/// A continuation line without its ` # comment`.
+fn strip_comment(line: &str) -> &str {
+    match line.find(" #") {
+        Some(i) => line[..i].trim_end(),
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sections_and_continuations() {
+        let root = parse(
+            "disable=SC2086\n\n[flake8]\nmax-line-length = 100\nextend-ignore =\n    # black\n    E203,  # whitespace\n    W503\n\n[mypy-legacy.*]\nignore_errors: True\n",
+        );
+        assert_eq!(root.get("disable").unwrap().as_str(), Some("SC2086"));
+        let flake8 = root.get("flake8").unwrap();
+        assert_eq!(flake8.line, 3);
+        assert_eq!(
+            flake8.get("extend-ignore").unwrap().list(),
+            [("E203".to_string(), 7), ("W503".to_string(), 8)]
+        );
+        let mypy = root.get("mypy-legacy.*").unwrap();
+        assert_eq!(mypy.entries()[0].line, 11);
+        assert!(mypy.get("ignore_errors").unwrap().is_true());
+    }
+}
@@ -0,0 +1,210 @@
+//! JSON with the comments and trailing commas that `tsconfig.json`,
+//! `.eslintrc.json` and friends allow.
+
+use super::{Entry, Node, Value};
+
+pub fn parse(text: &str) -> Result<Node, String> {
+    let mut parser = Parser {
+        text: text.as_bytes(),
+        pos: 0,
+        line: 1,
+    };
+    let root = parser.value()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(root),
+        Some(_) => Err(parser.error("trailing characters")),
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        if b == b'\n' {
+            self.line += 1;
+        }
+        Some(b)
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("line {}: {message}", self.line)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.bump() {
+            Some(b) if b == expected => Ok(()),
+            _ => Err(self.error(&format!("expected `{}`", expected as char))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            match (self.peek(), self.text.get(self.pos + 1)) {
+                (Some(b' ' | b'\t' | b'\r' | b'\n'), _) => {
+                    self.bump();
+                }
+                (Some(b'/'), Some(b'/')) => {
+                    while self.peek().is_some_and(|b| b != b'\n') {
+                        self.bump();
+                    }
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    self.bump();
+                    self.bump();
+                    while self.peek().is_some() && !self.text[self.pos..].starts_with(b"*/") {
+                        self.bump();
+                    }
+                    self.bump();
+                    self.bump();
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Node, String> {
+        self.skip_whitespace();
+        let line = self.line;
+        let value = match self.peek() {
+            Some(b'{') => {
+                self.bump();
+                let mut entries = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some(b'}') {
+                        self.bump();
+                        break;
+                    }
+                    let line = self.line;
+                    let key = self.string()?;
+                    self.expect(b':')?;
+                    let value = self.value()?;
+                    entries.push(Entry { key, line, value });
+                    if !self.separator(b'}')? {
+                        break;
+                    }
+                }
+                Value::Map(entries)
+            }
+            Some(b'[') => {
+                self.bump();
+                let mut items = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some(b']') {
+                        self.bump();
+                        break;
+                    }
+                    items.push(self.value()?);
+                    if !self.separator(b']')? {
+                        break;
+                    }
+                }
+                Value::Seq(items)
+            }
+            Some(b'"') => Value::Scalar(self.string()?),
+            Some(_) => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'+' | b'.'))
+                {
+                    self.bump();
+                }
+                if start == self.pos {
+                    return Err(self.error("expected a value"));
+                }
+                Value::Scalar(String::from_utf8_lossy(&self.text[start..self.pos]).into_owned())
+            }
+            None => return Err(self.error("unexpected end of file")),
+        };
+        Ok(Node { line, value })
+    }
+
+    /// Consume the comma after a member or element, or the closing
+    /// bracket; returns whether more members may follow.
+    fn separator(&mut self, close: u8) -> Result<bool, String> {
+        self.skip_whitespace();
+        match self.bump() {
+            Some(b',') => Ok(true),
+            Some(b) if b == close => Ok(false),
+            _ => Err(self.error(&format!("expected `,` or `{}`", close as char))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            match self.bump() {
+                Some(b'"') => break,
+                Some(b'\\') => match self.bump() {
+                    Some(b'n') => bytes.push(b'\n'),
+                    Some(b't') => bytes.push(b'\t'),
+                    Some(b'r') => bytes.push(b'\r'),
+                    Some(b'b') => bytes.push(0x08),
+                    Some(b'f') => bytes.push(0x0c),
+                    Some(b'u') => {
+                        let hex = self.text.get(self.pos..self.pos + 4).unwrap_or_default();
+                        let c = std::str::from_utf8(hex)
+                            .ok()
+                            .and_then(|h| u32::from_str_radix(h, 16).ok())
+                            .and_then(char::from_u32)
+                            .unwrap_or(char::REPLACEMENT_CHARACTER);
+                        self.pos += hex.len();
+                        bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                    }
+                    Some(b) => bytes.push(b),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(b'\n') | None => return Err(self.error("unterminated string")),
+                Some(b) => bytes.push(b),
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commented_json_with_lines() {
+        let root = parse(
+            "{\n  /* options */\n  \"compilerOptions\": {\n    \"strict\": false, // legacy\n    \"lib\": [\"dom\", \"es\\u0032020\",],\n  },\n}\n",
+        )
+        .unwrap();
+        let options = root.get("compilerOptions").unwrap();
+        assert_eq!(options.line, 3);
+        assert_eq!(root.entries()[0].line, 3);
+        assert_eq!(options.entries()[0].line, 4);
+        assert_eq!(options.get("strict").unwrap().as_str(), Some("false"));
+        assert_eq!(
+            options.get("lib").unwrap().list(),
+            [("dom".to_string(), 5), ("es2020".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn reports_errors_with_lines() {
+        assert_eq!(
+            parse("{\n  \"a\" 1\n}").unwrap_err(),
+            "line 2: expected `:`"
+        );
+        assert_eq!(parse("[1, 2").unwrap_err(), "line 1: expected `,` or `]`");
+        assert!(parse("rules:\n  a: off").is_err());
+    }
+}
@@ -0,0 +1,577 @@
+//! Rules suppressed by linter configuration files rather than by inline
+//! directives, e.g. `"no-explicit-any": "off"` in `.eslintrc.json`,
+//! `ignore = ["E501"]` under `[tool.ruff]` or `[lints.clippy]` in
+//! `Cargo.toml`, and files exempted by ignore files and per-file mappings
+//! such as `.eslintignore` or Ruff's `per-file-ignores`.
+//!
+//! Config files are parsed into a [`Node`] tree that keeps the line of every
+//! key and value, so that findings point at the line doing the suppressing.
+//! The parsers cover what linter configs use rather than the full JSON, YAML,
+//! TOML and INI grammars.
+
+mod exempt;
+mod extract;
+mod ini;
+mod json;
+mod toml;
+mod yaml;
+
+use crate::orphan::name_matches;
+
+/// A parsed value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// Strings, numbers, booleans and nulls, as written without quotes.
+    Scalar(String),
+    Seq(Vec<Node>),
+    Map(Vec<Entry>),
+}
+
+/// A value and the line it starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub line: usize,
+    pub value: Value,
+}
+
+/// A key of a mapping, with the line it is on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub key: String,
+    pub line: usize,
+    pub value: Node,
+}
+
+impl Node {
+    fn scalar(line: usize, text: impl Into<String>) -> Self {
+        Node {
+            line,
+            value: Value::Scalar(text.into()),
+        }
+    }
+
+    fn map(line: usize) -> Self {
+        Node {
+            line,
+            value: Value::Map(Vec::new()),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        self.entries()
+            .iter()
+            .find(|e| e.key == key)
+            .map(|e| &e.value)
+    }
+
+    /// The entry at a dotted path of keys.
+    pub fn get_path(&self, path: &[&str]) -> Option<&Node> {
+        path.iter().try_fold(self, |node, key| node.get(key))
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        match self.value {
+            Value::Map(ref entries) => entries,
+            _ => &[],
+        }
+    }
+
+    pub fn items(&self) -> &[Node] {
+        match self.value {
+            Value::Seq(ref items) => items,
+            _ => &[],
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self.value {
+            Value::Scalar(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Whether the value is a boolean true, as TOML, YAML or INI write it.
+    pub fn is_true(&self) -> bool {
+        self.as_str()
+            .is_some_and(|s| matches!(s.to_ascii_lowercase().as_str(), "true" | "yes" | "on" | "1"))
+    }
+
+    /// The strings of a sequence, or of a scalar separated by commas,
+    /// whitespace and newlines, each with its line.
+    pub fn list(&self) -> Vec<(String, usize)> {
+        match self.value {
+            Value::Seq(ref items) => items
+                .iter()
+                .filter_map(|n| n.as_str().map(|s| (s.to_string(), n.line)))
+                .collect(),
+            Value::Scalar(ref s) => s
+                .split('\n')
+                .enumerate()
+                .flat_map(|(i, line)| {
+                    line.split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|t| !t.is_empty())
+                        .map(move |t| (t.to_string(), self.line + i))
+                })
+                .collect(),
+            Value::Map(_) => Vec::new(),
+        }
+    }
+}
+
+/// Syntax of a config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Yaml,
+    /// JSON when it parses as such, YAML otherwise, e.g. `.eslintrc`.
+    JsonOrYaml,
+    Toml,
+    Ini,
+    /// One pattern per line, with `#` comments, as in `.eslintignore`.
+    Lines,
+}
+
+/// A config file name, its syntax, the linters it configures and where they
+/// are told to suppress something or to exempt files.
+struct Kind {
+    /// File name, with at most one `*` wildcard.
+    file: &'static str,
+    format: Format,
+    linters: &'static [&'static str],
+    extract: fn(&Node) -> Vec<ConfigSuppression>,
+    exempt: fn(&Node) -> Vec<ConfigExemption>,
+}
+
+const KINDS: &[Kind] = &[
+    kind(
+        "package.json",
+        Format::Json,
+        &["eslint", "stylelint"],
+        extract::package_json,
+        exempt::package_json,
+    ),
+    kind(
+        ".eslintrc",
+        Format::JsonOrYaml,
+        &["eslint"],
+        extract::eslintrc,
+        exempt::eslintrc,
+    ),
+    kind(
+        ".eslintrc.json",
+        Format::Json,
+        &["eslint"],
+        extract::eslintrc,
+        exempt::eslintrc,
+    ),
+    kind(
+        ".eslintrc.y*ml",
+        Format::Yaml,
+        &["eslint"],
+        extract::eslintrc,
+        exempt::eslintrc,
+    ),
+    kind(
+        ".eslintignore",
+        Format::Lines,
+        &["eslint"],
+        extract::none,
+        exempt::eslintignore,
+    ),
+    kind(
+        ".oxlintrc.json",
+        Format::Json,
+        &["oxlint"],
+        extract::oxlintrc,
+        exempt::none,
+    ),
+    kind(
+        "biome.json*",
+        Format::Json,
+        &["biome"],
+        extract::biome,
+        exempt::none,
+    ),
+    kind(
+        ".prettierignore",
+        Format::Lines,
+        &["prettier"],
+        extract::none,
+        exempt::prettierignore,
+    ),
+    kind(
+        ".stylelintrc",
+        Format::JsonOrYaml,
+        &["stylelint"],
+        extract::stylelintrc,
+        exempt::none,
+    ),
+    kind(
+        ".stylelintrc.json",
+        Format::Json,
+        &["stylelint"],
+        extract::stylelintrc,
+        exempt::none,
+    ),
+    kind(
+        ".stylelintrc.y*ml",
+        Format::Yaml,
+        &["stylelint"],
+        extract::stylelintrc,
+        exempt::none,
+    ),
+    kind(
+        ".stylelintignore",
+        Format::Lines,
+        &["stylelint"],
+        extract::none,
+        exempt::stylelintignore,
+    ),
+    kind(
+        "tsconfig*.json",
+        Format::Json,
+        &["typescript"],
+        extract::tsconfig,
+        exempt::none,
+    ),
+    kind(
+        "jsconfig.json",
+        Format::Json,
+        &["typescript"],
+        extract::tsconfig,
+        exempt::none,
+    ),
+    kind(
+        "pyproject.toml",
+        Format::Toml,
+        &["ruff", "pylint", "mypy", "pyright", "bandit"],
+        extract::pyproject,
+        exempt::pyproject,
+    ),
+    kind(
+        "ruff.toml",
+        Format::Toml,
+        &["ruff"],
+        extract::ruff_toml,
+        exempt::ruff_toml,
+    ),
+    kind(
+        ".ruff.toml",
+        Format::Toml,
+        &["ruff"],
+        extract::ruff_toml,
+        exempt::ruff_toml,
+    ),
+    kind(
+        "Cargo.toml",
+        Format::Toml,
+        &["clippy"],
+        extract::cargo,
+        exempt::none,
+    ),
+    kind(
+        "setup.cfg",
+        Format::Ini,
+        &["flake8", "pylint", "mypy"],
+        extract::setup_cfg,
+        exempt::setup_cfg,
+    ),
+    kind(
+        "tox.ini",
+        Format::Ini,
+        &["flake8", "pylint", "mypy"],
+        extract::setup_cfg,
+        exempt::setup_cfg,
+    ),
+    kind(
+        ".flake8",
+        Format::Ini,
+        &["flake8"],
+        extract::setup_cfg,
+        exempt::setup_cfg,
+    ),
+    kind(
+        ".pylintrc",
+        Format::Ini,
+        &["pylint"],
+        extract::pylintrc,
+        exempt::none,
+    ),
+    kind(
+        "pylintrc",
+        Format::Ini,
+        &["pylint"],
+        extract::pylintrc,
+        exempt::none,
+    ),
+    kind(
+        "mypy.ini",
+        Format::Ini,
+        &["mypy"],
+        extract::setup_cfg,
+        exempt::none,
+    ),
+    kind(
+        ".mypy.ini",
+        Format::Ini,
+        &["mypy"],
+        extract::setup_cfg,
+        exempt::none,
+    ),
+    kind(
+        "pyrightconfig.json",
+        Format::Json,
+        &["pyright"],
+        extract::pyrightconfig,
+        exempt::none,
+    ),
+    kind(
+        ".golangci.y*ml",
+        Format::Yaml,
+        &["golangci-lint"],
+        extract::golangci,
+        exempt::none,
+    ),
+    kind(
+        ".golangci.toml",
+        Format::Toml,
+        &["golangci-lint"],
+        extract::golangci,
+        exempt::none,
+    ),
+    kind(
+        ".golangci.json",
+        Format::Json,
+        &["golangci-lint"],
+        extract::golangci,
+        exempt::none,
+    ),
+    kind(
+        ".rubocop.yml",
+        Format::Yaml,
+        &["rubocop"],
+        extract::rubocop,
+        exempt::rubocop,
+    ),
+    kind(
+        ".hadolint.y*ml",
+        Format::Yaml,
+        &["hadolint"],
+        extract::hadolint,
+        exempt::hadolint,
+    ),
+    kind(
+        ".shellcheckrc",
+        Format::Ini,
+        &["shellcheck"],
+        extract::shellcheckrc,
+        exempt::none,
+    ),
+    kind(
+        "shellcheckrc",
+        Format::Ini,
+        &["shellcheck"],
+        extract::shellcheckrc,
+        exempt::none,
+    ),
+];
+
+const fn kind(
+    file: &'static str,
+    format: Format,
+    linters: &'static [&'static str],
+    extract: fn(&Node) -> Vec<ConfigSuppression>,
+    exempt: fn(&Node) -> Vec<ConfigExemption>,
+) -> Kind {
+    Kind {
+        file,
+        format,
+        linters,
+        extract,
+        exempt,
+    }
+}
+
+fn kind_of(name: &str) -> Option<&'static Kind> {
+    KINDS.iter().find(|k| name_matches(k.file, name))
+}
+
+/// The linters a config file with this name may suppress rules of; empty
+/// for other files.
+pub fn linters(name: &str) -> &'static [&'static str] {
+    kind_of(name).map_or(&[], |k| k.linters)
+}
+
+/// Something a config file tells a linter to ignore.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSuppression {
+    pub linter: &'static str,
+    /// `config-disable` for rules turned off everywhere, `config-exclude`
+    /// for rules or issues excluded under conditions such as paths.
+    pub rule_id: &'static str,
+    pub description: &'static str,
+    pub line: usize,
+    /// Dotted path of the key, e.g. `rules.no-explicit-any`.
+    pub key_path: String,
+    /// Rules it suppresses; empty when it suppresses every rule.
+    pub rules: Vec<String>,
+}
+
+/// Files a config file exempts from a linter, or from some of its rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigExemption {
+    pub linter: &'static str,
+    /// Patterns of the exempted files, relative to the config file's
+    /// directory, each with its line.
+    pub patterns: Vec<(String, usize)>,
+    /// Rules the files are exempt from; empty when exempt from every rule.
+    pub rules: Vec<String>,
+}
+
+/// What a config file suppresses and exempts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinterConfig {
+    pub suppressions: Vec<ConfigSuppression>,
+    pub exemptions: Vec<ConfigExemption>,
+}
+
+/// Parse the config file `name`.
+pub fn read(name: &str, content: &str) -> Result<LinterConfig, String> {
+    let Some(kind) = kind_of(name) else {
+        return Ok(LinterConfig::default());
+    };
+    let root = match kind.format {
+        Format::Json => json::parse(content)?,
+        Format::Yaml => yaml::parse(content),
+        Format::JsonOrYaml => json::parse(content).unwrap_or_else(|_| yaml::parse(content)),
+        Format::Toml => toml::parse(content)?,
+        Format::Ini => ini::parse(content),
+        Format::Lines => pattern_lines(content),
+    };
+    Ok(LinterConfig {
+        suppressions: (kind.extract)(&root),
+        exemptions: (kind.exempt)(&root),
+    })
+}
+
+/// The non-blank, non-comment lines of an ignore file, as a sequence.
+fn pattern_lines(content: &str) -> Node {
+    let items = content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| Node::scalar(number, line))
+        .collect();
+    Node {
+        line: 1,
+        value: Value::Seq(items),
+    }
+}
+
+/// The comment at the end of a config line, used as the justification of
+/// what the line suppresses.
+pub fn trailing_comment(name: &str, line: &str) -> Option<String> {
+    let marker = match kind_of(name)?.format {
+        Format::Json => "//",
+        Format::JsonOrYaml if line.contains("//") => "//",
+        Format::Yaml | Format::JsonOrYaml | Format::Toml => "#",
+        Format::Ini | Format::Lines => return None,
+    };
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, _) if line[i..].starts_with(marker) && i > 0 => {
+                return Some(line[i + marker.len()..].trim().to_string()).filter(|c| !c.is_empty());
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_items_with_lines() {
+        let node = Node::scalar(3, "\nE501,\nW503, E203");
+        assert_eq!(
+            node.list(),
+            [
+                ("E501".to_string(), 4),
+                ("W503".to_string(), 5),
+                ("E203".to_string(), 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_config_kinds() {
+        assert_eq!(linters(".eslintrc.yaml"), ["eslint"]);
+        assert_eq!(linters("tsconfig.build.json"), ["typescript"]);
+        assert_eq!(linters(".golangci.yml"), ["golangci-lint"]);
+        assert!(linters("config.json").is_empty());
+    }
+
+    #[test]
+    fn reads_trailing_comments() {
+        assert_eq!(
+            trailing_comment("pyproject.toml", r#"ignore = ["E501"]  # long URLs"#).as_deref(),
+            Some("long URLs")
+        );
+        assert_eq!(
+            trailing_comment("tsconfig.json", r#""strict": false, // legacy code"#).as_deref(),
+            Some("legacy code")
+        );
+        assert_eq!(trailing_comment(".rubocop.yml", "  Exclude: ['a#b']"), None);
+        assert_eq!(trailing_comment("setup.cfg", "ignore = E501 # x"), None);
+    }
+
+    #[test]
+    fn parses_each_format() {
+        let found = |name: &str, content: &str| -> Vec<(usize, String, Vec<String>)> {
+            read(name, content)
+                .unwrap()
+                .suppressions
+                .into_iter()
+                .map(|s| (s.line, s.key_path, s.rules))
+                .collect()
+        };
+        assert_eq!(
+            found(
+                ".eslintrc.json",
+                "{\n  // legacy\n  \"rules\": {\n    \"no-console\": \"off\",\n    \"eqeqeq\": [\"error\"],\n    \"no-var\": [0]\n  }\n}\n"
+            ),
+            [
+                (4, "rules.no-console".into(), vec!["no-console".into()]),
+                (6, "rules.no-var".into(), vec!["no-var".into()])
+            ]
+        );
+        assert_eq!(
+            found(".eslintrc", "rules:\n  no-console: off\n"),
+            [(2, "rules.no-console".into(), vec!["no-console".into()])]
+        );
+        assert_eq!(
+            found(
+                "setup.cfg",
+                "[flake8]\nextend-ignore =\n    E203,\n    W503\n"
+            ),
+            [
+                (3, "flake8.extend-ignore".into(), vec!["E203".into()]),
+                (4, "flake8.extend-ignore".into(), vec!["W503".into()])
+            ]
+        );
+        assert!(read("tsconfig.json", "{ \"compilerOptions\": ").is_err());
+        assert_eq!(
+            read(".eslintignore", "# built\ndist/\n\n!dist/keep.js\n")
+                .unwrap()
+                .exemptions[0]
+                .patterns,
+            [("dist/".to_string(), 2), ("!dist/keep.js".to_string(), 4)]
+        );
+    }
+}
@@ -0,0 +1,396 @@
+//! TOML tables, arrays of tables, dotted keys, inline tables, arrays and
+//! strings. Other values such as numbers and dates are kept as written.
+
+use super::{Entry, Node, Value};
+
+pub fn parse(text: &str) -> Result<Node, String> {
+    let mut parser = Parser {
+        text: text.as_bytes(),
+        pos: 0,
+        line: 1,
+    };
+    let mut root = Node::map(1);
+    let mut table: Vec<String> = Vec::new();
+    loop {
+        parser.skip_blank();
+        let line = parser.line;
+        match parser.peek() {
+            None => break,
+            Some(b'[') if parser.text.get(parser.pos + 1) == Some(&b'[') => {
+                parser.pos += 2;
+                table = parser.keys()?;
+                parser.expect(b']')?;
+                parser.expect(b']')?;
+                let (last, parents) = table
+                    .split_last()
+                    .ok_or_else(|| parser.error("empty table name"))?;
+                let entries = descend(&mut root, parents, line).map_err(|e| parser.error(&e))?;
+                let index = entry(entries, last, line, || Value::Seq(Vec::new()));
+                match entries[index].value.value {
+                    Value::Seq(ref mut items) => items.push(Node::map(line)),
+                    _ => return Err(parser.error(&format!("`{last}` is not an array of tables"))),
+                }
+            }
+            Some(b'[') => {
+                parser.pos += 1;
+                table = parser.keys()?;
+                parser.expect(b']')?;
+                descend(&mut root, &table, line).map_err(|e| parser.error(&e))?;
+            }
+            Some(_) => {
+                let keys = parser.keys()?;
+                parser.expect(b'=')?;
+                let value = parser.value()?;
+                let path: Vec<String> = table.iter().chain(&keys).cloned().collect();
+                let (last, parents) = path.split_last().ok_or_else(|| parser.error("empty key"))?;
+                let entries = descend(&mut root, parents, line).map_err(|e| parser.error(&e))?;
+                entries.push(Entry {
+                    key: last.clone(),
+                    line,
+                    value,
+                });
+            }
+        }
+        parser.skip_spaces();
+        match parser.peek() {
+            None | Some(b'\n' | b'\r' | b'#') => {}
+            Some(_) => return Err(parser.error("expected the end of the line")),
+        }
+    }
+    Ok(root)
+}
+
+/// The index of the entry `key` in `entries`, added with `value` if missing.
+fn entry(entries: &mut Vec<Entry>, key: &str, line: usize, value: impl FnOnce() -> Value) -> usize {
+    match entries.iter().position(|e| e.key == key) {
+        Some(index) => index,
+        None => {
+            entries.push(Entry {
+                key: key.to_string(),
+                line,
+                value: Node {
+                    line,
+                    value: value(),
+                },
+            });
+            entries.len() - 1
+        }
+    }
+}
+
+/// The entries of the table at `path`, creating missing tables; arrays of
+/// tables resolve to their last table.
+fn descend<'a>(
+    root: &'a mut Node,
+    path: &[String],
+    line: usize,
+) -> Result<&'a mut Vec<Entry>, String> {
+    let mut node = root;
+    for key in path {
+        let entries = match node.value {
+            Value::Map(ref mut entries) => entries,
+            _ => return Err(format!("`{key}` is not in a table")),
+        };
+        let index = entry(entries, key, line, || Value::Map(Vec::new()));
+        node = &mut entries[index].value;
+        if let Value::Seq(ref mut items) = node.value {
+            node = items
+                .last_mut()
+                .ok_or_else(|| format!("`{key}` is empty"))?;
+        }
+    }
+    match node.value {
+        Value::Map(ref mut entries) => Ok(entries),
+        _ => Err("not a table".to_string()),
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        if b == b'\n' {
+            self.line += 1;
+        }
+        Some(b)
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("line {}: {message}", self.line)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        self.skip_spaces();
+        match self.bump() {
+            Some(b) if b == expected => Ok(()),
+            _ => Err(self.error(&format!("expected `{}`", expected as char))),
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.bump();
+        }
+    }
+
+    /// Skip whitespace, newlines and comments.
+    fn skip_blank(&mut self) {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\r' | b'\n') => {
+                    self.bump();
+                }
+                Some(b'#') => {
+                    while self.peek().is_some_and(|b| b != b'\n') {
+                        self.bump();
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// A dotted key such as `tool."messages control".disable`.
+    fn keys(&mut self) -> Result<Vec<String>, String> {
+        let mut keys = Vec::new();
+        loop {
+            self.skip_spaces();
+            let key = match self.peek() {
+                Some(b'"') => self.basic_string()?,
+                Some(b'\'') => self.literal_string()?,
+                _ => {
+                    let start = self.pos;
+                    while self
+                        .peek()
+                        .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'))
+                    {
+                        self.bump();
+                    }
+                    if start == self.pos {
+                        return Err(self.error("expected a key"));
+                    }
+                    String::from_utf8_lossy(&self.text[start..self.pos]).into_owned()
+                }
+            };
+            keys.push(key);
+            self.skip_spaces();
+            if self.peek() != Some(b'.') {
+                return Ok(keys);
+            }
+            self.bump();
+        }
+    }
+
+    fn value(&mut self) -> Result<Node, String> {
+        self.skip_spaces();
+        let line = self.line;
+        let value = match self.peek() {
+            Some(b'"') => Value::Scalar(self.basic_string()?),
+            Some(b'\'') => Value::Scalar(self.literal_string()?),
+            Some(b'[') => {
+                self.bump();
+                let mut items = Vec::new();
+                loop {
+                    self.skip_blank();
+                    if self.peek() == Some(b']') {
+                        self.bump();
+                        break;
+                    }
+                    items.push(self.value()?);
+                    self.skip_blank();
+                    match self.bump() {
+                        Some(b',') => {}
+                        Some(b']') => break,
+                        _ => return Err(self.error("expected `,` or `]`")),
+                    }
+                }
+                Value::Seq(items)
+            }
+            Some(b'{') => {
+                self.bump();
+                let mut table = Node::map(line);
+                loop {
+                    self.skip_spaces();
+                    if self.peek() == Some(b'}') {
+                        self.bump();
+                        break;
+                    }
+                    let line = self.line;
+                    let keys = self.keys()?;
+                    self.expect(b'=')?;
+                    let value = self.value()?;
+                    let (last, parents) =
+                        keys.split_last().ok_or_else(|| self.error("empty key"))?;
+                    let entries = descend(&mut table, parents, line).map_err(|e| self.error(&e))?;
+                    entries.push(Entry {
+                        key: last.clone(),
+                        line,
+                        value,
+                    });
+                    self.skip_spaces();
+                    match self.bump() {
+                        Some(b',') => {}
+                        Some(b'}') => break,
+                        _ => return Err(self.error("expected `,` or `}`")),
+                    }
+                }
+                table.value
+            }
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|b| !matches!(b, b',' | b']' | b'}' | b'#' | b'\n' | b'\r'))
+                {
+                    self.bump();
+                }
+                let text = String::from_utf8_lossy(&self.text[start..self.pos]);
+                if text.trim().is_empty() {
+                    return Err(self.error("expected a value"));
+                }
+                Value::Scalar(text.trim().to_string())
+            }
+        };
+        Ok(Node { line, value })
+    }
+
+    fn basic_string(&mut self) -> Result<String, String> {
+        let multiline = self.text[self.pos..].starts_with(b"\"\"\"");
+        self.pos += if multiline { 3 } else { 1 };
+        if multiline && self.peek() == Some(b'\n') {
+            self.bump();
+        }
+        let mut bytes = Vec::new();
+        loop {
+            if multiline && self.text[self.pos..].starts_with(b"\"\"\"") {
+                self.pos += 3;
+                break;
+            }
+            match self.bump() {
+                Some(b'"') if !multiline => break,
+                Some(b'\\') => match self.bump() {
+                    Some(b'n') => bytes.push(b'\n'),
+                    Some(b't') => bytes.push(b'\t'),
+                    Some(b'\n') if multiline => self.skip_blank(),
+                    Some(b) => bytes.push(b),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(b'\n') if !multiline => return Err(self.error("unterminated string")),
+                Some(b) => bytes.push(b),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn literal_string(&mut self) -> Result<String, String> {
+        let multiline = self.text[self.pos..].starts_with(b"'''");
+        let quote: &[u8] = if multiline { b"'''" } else { b"'" };
+        self.pos += quote.len();
+        if multiline && self.peek() == Some(b'\n') {
+            self.bump();
+        }
+        let start = self.pos;
+        while !self.text[self.pos..].starts_with(quote) {
+            match self.bump() {
+                Some(b'\n') if !multiline => return Err(self.error("unterminated string")),
+                None => return Err(self.error("unterminated string")),
+                _ => {}
+            }
+        }
+        let text = String::from_utf8_lossy(&self.text[start..self.pos]).into_owned();
+        self.pos += quote.len();
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tables_with_lines() {
+        let root = parse(
+            r#"[project]
+name = "app"
+
+[tool.ruff.lint]
+ignore = [
+    "E501",  # long lines
+    'W503',
+]
+per-file-ignores = { "tests/*" = ["S101"] }
+
+[tool.pylint."messages control"]
+disable = """
+    missing-docstring,
+"""
+
+[[tool.mypy.overrides]]
+module = "legacy.*"
+ignore_errors = true
+"#,
+        )
+        .unwrap();
+        let lint = root.get_path(&["tool", "ruff", "lint"]).unwrap();
+        assert_eq!(lint.line, 4);
+        assert_eq!(
+            lint.get("ignore").unwrap().list(),
+            [("E501".to_string(), 6), ("W503".to_string(), 7)]
+        );
+        assert_eq!(
+            lint.get_path(&["per-file-ignores", "tests/*"])
+                .unwrap()
+                .list(),
+            [("S101".to_string(), 9)]
+        );
+        let disable = root
+            .get_path(&["tool", "pylint", "messages control", "disable"])
+            .unwrap();
+        assert_eq!(disable.line, 12);
+        let overrides = root.get_path(&["tool", "mypy", "overrides"]).unwrap();
+        assert_eq!(overrides.items()[0].line, 16);
+        assert!(overrides.items()[0].get("ignore_errors").unwrap().is_true());
+    }
+
+    #[test]
+    fn dotted_keys_extend_tables() {
+        let root = parse("[lints]\nclippy.unwrap_used = \"allow\"\nclippy.todo = { level = \"allow\", priority = 1 }\n").unwrap();
+        let clippy = root.get_path(&["lints", "clippy"]).unwrap();
+        assert_eq!(
+            clippy
+                .entries()
+                .iter()
+                .map(|e| (e.key.as_str(), e.line))
+                .collect::<Vec<_>>(),
+            [("unwrap_used", 2), ("todo", 3)]
+        );
+        assert_eq!(
+            clippy.get_path(&["todo", "level"]).unwrap().as_str(),
+            Some("allow")
+        );
+    }
+
+    #[test]
+    fn reports_errors_with_lines() {
+        assert_eq!(
+            parse("a = 1\nb = [1,\n").unwrap_err(),
+            "line 3: expected a value"
+        );
+        assert_eq!(
+            parse("a = \"x\" y\n").unwrap_err(),
+            "line 1: expected the end of the line"
+        );
+        assert!(parse("a = 1\n[a.b]\n").is_err());
+    }
+}
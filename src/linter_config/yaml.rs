@@ -0,0 +1,356 @@
+//! The block mappings and sequences, flow collections and scalars of YAML
+//! linter configs. Anchors, tags and multiple documents are not resolved;
+//! lines the parser does not understand are skipped rather than failing.
+
+use super::{Entry, Node, Value};
+
+pub fn parse(text: &str) -> Node {
+    let lines: Vec<Line> = text
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let content = strip_comment(line).trim_end();
+            let text = content.trim_start();
+            if text.is_empty() || text == "---" || text == "..." || text.starts_with('%') {
+                return None;
+            }
+            Some(Line {
+                number: i + 1,
+                indent: content.len() - text.len(),
+                text: text.to_string(),
+            })
+        })
+        .collect();
+    let mut parser = Parser { lines, pos: 0 };
+    match parser.lines.first() {
+        Some(first) => {
+            let indent = first.indent;
+            parser.block(indent)
+        }
+        None => Node::map(1),
+    }
+}
+
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+struct Parser {
+    lines: Vec<Line>,
+    pos: usize,
+}
+
+impl Parser {
+    fn current(&self) -> Option<&Line> {
+        self.lines.get(self.pos)
+    }
+
+    /// The collection or scalar starting at the current line.
+    fn block(&mut self, indent: usize) -> Node {
+        let line = &self.lines[self.pos];
+        if is_item(&line.text) {
+            self.seq(indent)
+        } else if split_key(&line.text).is_some() {
+            self.map(indent)
+        } else {
+            let (number, text) = (line.number, line.text.clone());
+            self.pos += 1;
+            let text = self.flow_text(text);
+            flow(&text, number)
+        }
+    }
+
+    fn seq(&mut self, indent: usize) -> Node {
+        let start = self.lines[self.pos].number;
+        let mut items = Vec::new();
+        while let Some(line) = self.lines.get_mut(self.pos) {
+            if line.indent != indent || !is_item(&line.text) {
+                break;
+            }
+            let rest = line.text[1..].trim_start().to_string();
+            if rest.is_empty() {
+                let number = line.number;
+                self.pos += 1;
+                match self.current() {
+                    Some(next) if next.indent > indent => {
+                        let indent = next.indent;
+                        items.push(self.block(indent));
+                    }
+                    _ => items.push(Node::scalar(number, "")),
+                }
+            } else {
+                // The item's content continues as if it started on its own
+                // line, at the column after the dash
+                line.indent += line.text.len() - rest.len();
+                line.text = rest;
+                let indent = line.indent;
+                items.push(self.block(indent));
+            }
+        }
+        Node {
+            line: start,
+            value: Value::Seq(items),
+        }
+    }
+
+    fn map(&mut self, indent: usize) -> Node {
+        let start = self.lines[self.pos].number;
+        let mut entries = Vec::new();
+        while let Some(line) = self.current() {
+            if line.indent < indent || (is_item(&line.text) && line.indent == indent) {
+                break;
+            }
+            let Some((key, rest)) = split_key(&line.text).filter(|_| line.indent == indent) else {
+                self.pos += 1;
+                continue;
+            };
+            let number = line.number;
+            self.pos += 1;
+            let value = if rest.is_empty() {
+                match self.current() {
+                    Some(next) if next.indent > indent => {
+                        let indent = next.indent;
+                        self.block(indent)
+                    }
+                    Some(next) if next.indent == indent && is_item(&next.text) => self.seq(indent),
+                    _ => Node::scalar(number, ""),
+                }
+            } else if rest.starts_with('|') || rest.starts_with('>') {
+                let mut text = String::new();
+                while let Some(next) = self.current().filter(|next| next.indent > indent) {
+                    text.push_str(&next.text);
+                    text.push('\n');
+                    self.pos += 1;
+                }
+                Node::scalar(number, text)
+            } else {
+                let text = self.flow_text(rest);
+                flow(&text, number)
+            };
+            entries.push(Entry {
+                key,
+                line: number,
+                value,
+            });
+        }
+        Node {
+            line: start,
+            value: Value::Map(entries),
+        }
+    }
+
+    /// `text`, joined with the lines from the current one while its flow
+    /// collection is unclosed; consumes those lines.
+    fn flow_text(&mut self, mut text: String) -> String {
+        while depth(&text) > 0 {
+            let Some(next) = self.current() else {
+                break;
+            };
+            text.push('\n');
+            text.push_str(&next.text);
+            self.pos += 1;
+        }
+        text
+    }
+}
+
+fn is_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// The text of a line before its comment.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '#') if previous.is_whitespace() => return &line[..i],
+            _ => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+/// Split `key: value` at the first colon followed by a space or the end of
+/// the text, outside of quotes and brackets.
+fn split_key(text: &str) -> Option<(String, String)> {
+    if text.starts_with(['[', '{']) {
+        return None;
+    }
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, ':') if text[i + 1..].is_empty() || text[i + 1..].starts_with(' ') => {
+                return Some((unquote(text[..i].trim()), text[i + 1..].trim().to_string()));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// How many more brackets `text` opens than it closes.
+fn depth(text: &str) -> i32 {
+    let mut quote = None;
+    let mut depth = 0;
+    for c in text.chars() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// Parse a flow collection or scalar that starts on `line`.
+fn flow(text: &str, line: usize) -> Node {
+    let text = text.trim();
+    let value = match (text.chars().next(), text.chars().last()) {
+        (Some('['), Some(']')) => Value::Seq(
+            split_flow(&text[1..text.len() - 1])
+                .map(|(offset, item)| flow(item, line + newlines(text, offset)))
+                .collect(),
+        ),
+        (Some('{'), Some('}')) => Value::Map(
+            split_flow(&text[1..text.len() - 1])
+                .map(|(offset, member)| {
+                    let line = line + newlines(text, offset);
+                    let (key, value) = split_key(member.trim())
+                        .unwrap_or_else(|| (unquote(member.trim()), String::new()));
+                    Entry {
+                        key,
+                        line,
+                        value: flow(&value, line),
+                    }
+                })
+                .collect(),
+        ),
+        _ => Value::Scalar(unquote(text)),
+    };
+    Node { line, value }
+}
+
+fn newlines(text: &str, offset: usize) -> usize {
+    text[..offset + 1].matches('\n').count()
+}
+
+/// The non-empty members of a flow collection's contents, each with its
+/// offset within them.
+fn split_flow(inner: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut members = Vec::new();
+    let (mut quote, mut depth, mut start) = (None, 0, 0);
+    for (i, c) in inner.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                members.push((start, &inner[start..i]));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    members.push((start, &inner[start..]));
+    members.into_iter().filter_map(|(start, member)| {
+        let trimmed = member.trim_start();
+        (!trimmed.trim_end().is_empty()).then(|| (start + member.len() - trimmed.len(), trimmed))
+    })
+}
+
+fn unquote(text: &str) -> String {
+    if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+        text[1..text.len() - 1].replace("''", "'")
+    } else if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        text[1..text.len() - 1]
+            .replace("\\\"", "\"")
+            .replace("\\\\", "\\")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUBOCOP: &str = "\
+inherit_from: .rubocop_todo.yml
+
+AllCops:
+  Exclude:
+    - 'db/schema.rb'  # generated
+    - vendor/**/*
+
+Style/Documentation:
+  Enabled: false # too noisy
+Metrics/BlockLength: { Enabled: false, Max: 30 }
+";
+
+    #[test]
+    fn parses_nested_blocks_with_lines() {
+        let root = parse(RUBOCOP);
+        assert_eq!(
+            root.entries()
+                .iter()
+                .map(|e| (e.key.as_str(), e.line))
+                .collect::<Vec<_>>(),
+            [
+                ("inherit_from", 1),
+                ("AllCops", 3),
+                ("Style/Documentation", 8),
+                ("Metrics/BlockLength", 10)
+            ]
+        );
+        assert_eq!(
+            root.get_path(&["AllCops", "Exclude"]).unwrap().list(),
+            [
+                ("db/schema.rb".to_string(), 5),
+                ("vendor/**/*".to_string(), 6)
+            ]
+        );
+        let enabled = root.get_path(&["Style/Documentation", "Enabled"]).unwrap();
+        assert_eq!((enabled.line, enabled.as_str()), (9, Some("false")));
+        let block = root.get("Metrics/BlockLength").unwrap();
+        assert_eq!(block.get("Max").unwrap().as_str(), Some("30"));
+    }
+
+    #[test]
+    fn parses_sequences_of_mappings() {
+        let root = parse(
+            "issues:\n  exclude-rules:\n  - path: _test\\.go\n    linters:\n      - errcheck\n  - linters: [gosec,\n      lll]\n    text: G104\n",
+        );
+        let rules = root.get_path(&["issues", "exclude-rules"]).unwrap().items();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].line, 3);
+        assert_eq!(rules[0].get("path").unwrap().as_str(), Some("_test\\.go"));
+        assert_eq!(
+            rules[0].get("linters").unwrap().list(),
+            [("errcheck".to_string(), 5)]
+        );
+        assert_eq!(
+            rules[1].get("linters").unwrap().list(),
+            [("gosec".to_string(), 6), ("lll".to_string(), 7)]
+        );
+        assert_eq!(rules[1].get("text").unwrap().line, 8);
+    }
+
+    #[test]
+    fn tolerates_what_it_does_not_understand() {
+        let root = parse("a: |\n  text\n    more\nb: &anchor 1\n  stray\nc: 'it''s'\n");
+        assert_eq!(root.get("a").unwrap().as_str(), Some("text\nmore\n"));
+        assert_eq!(root.get("c").unwrap().as_str(), Some("it's"));
+        assert_eq!(parse("").entries().len(), 0);
+    }
+}
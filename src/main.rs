@@ -1,17 +1,76 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::process;
 
 use clap::Parser;
 
-use lintscout::cli::Cli;
+use lintscout::attribution;
+use lintscout::baseline::Baseline;
+use lintscout::blame;
+use lintscout::budget::Budgets;
+use lintscout::cli::{Cli, Command};
+use lintscout::compare::{self, Comparison};
 use lintscout::config::Config;
+use lintscout::date;
+use lintscout::diff::Diff;
+use lintscout::error::LintScoutError;
+use lintscout::expiry::ExpiryPolicy;
+use lintscout::finding::Finding;
+use lintscout::git;
+use lintscout::history::{self, History};
+use lintscout::hook;
+use lintscout::hotspot::Hotspots;
+use lintscout::orphan::ConfiguredLinters;
 use lintscout::output;
+use lintscout::policy::{AgePolicy, JustificationPolicy, RulePolicy};
+use lintscout::ratchet::Ratchet;
 use lintscout::registry::ScoutRegistry;
-use lintscout::scanner::Scanner;
+use lintscout::scanner::{ScanResult, Scanner};
+use lintscout::scout::Scout;
+use lintscout::subtree::{self, Thresholds};
 
 fn main() {
     let cli = Cli::parse();
 
-    let config = if let Some(ref path) = cli.config {
+    match cli.command {
+        Some(Command::Baseline {
+            ref path,
+            ref write,
+        }) => write_baseline(&cli, &load_config(&cli), path, write),
+        Some(Command::InstallHook { force }) => install_hook(&cli, force),
+        Some(Command::Compare {
+            ref old,
+            ref new,
+            ref format,
+        }) => compare_reports(old, new, format),
+        Some(Command::Hotspots {
+            ref path,
+            since,
+            limit,
+            ref format,
+        }) => hotspots(&cli, &load_config(&cli), path, since, limit, format),
+        Some(Command::Ratchet { ref path, check }) => {
+            ratchet(&cli, &load_config(&cli), path, check || in_ci())
+        }
+        Some(Command::History {
+            ref path,
+            since,
+            until,
+            every,
+            ref branch,
+            ref format,
+        }) => {
+            let times = history::sample_times(since, until.unwrap_or_else(date::now), every);
+            sample_history(&cli, &load_config(&cli), path, branch, &times, format)
+        }
+        None => check(&cli, &load_config(&cli)),
+    }
+}
+
+fn load_config(cli: &Cli) -> Config {
+    if let Some(ref path) = cli.config {
         match Config::load(path) {
             Ok(c) => c,
             Err(e) => {
@@ -28,91 +87,486 @@ fn main() {
             }
             None => Config::default(),
         }
+    }
+}
+
+/// The scouts selected by the CLI and config.
+fn select_scouts(cli: &Cli, config: &Config) -> Vec<Scout> {
+    let registry = match ScoutRegistry::new().with_builtins() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error initializing scouts: {e}");
+            process::exit(2);
+        }
     };
 
-    let format = if cli.format != "text" {
-        cli.format.clone()
+    let registry = match registry.with_config(config) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error loading custom scouts: {e}");
+            process::exit(2);
+        }
+    };
+
+    let registry = if let Some(ref names) = cli.scouts {
+        registry.filter(names)
     } else {
-        config.settings.output.clone()
+        registry
     };
 
-    let pass_threshold = cli.pass_threshold.or(config.settings.pass_threshold);
+    let scouts = registry
+        .exclude(&excluded_scouts(cli, config))
+        .into_scouts();
 
+    if scouts.is_empty() {
+        if !cli.quiet {
+            eprintln!("No scouts selected. Check your --scouts or --exclude-scouts flags.");
+        }
+        process::exit(0);
+    }
+
+    scouts
+}
+
+/// The scouts disabled by the config and `--exclude-scouts`.
+fn excluded_scouts(cli: &Cli, config: &Config) -> Vec<String> {
+    let mut exclude_scouts = config.settings.disable.scouts.clone();
+    if let Some(ref cli_excludes) = cli.exclude_scouts {
+        exclude_scouts.extend(cli_excludes.iter().cloned());
+    }
+    exclude_scouts
+}
+
+/// A scanner of `dir` with the filters selected by the CLI and config.
+fn scanner(cli: &Cli, config: &Config, dir: &Path, scouts: Vec<Scout>) -> Scanner {
     let excludes = cli
         .exclude
         .clone()
         .unwrap_or_else(|| config.settings.exclude.clone());
+    Scanner::new(dir, scouts)
+        .with_fingerprint_root(git::toplevel(git::work_dir(dir)).ok())
+        .with_excludes(excludes)
+        .with_config_file(cli.config.clone().or_else(Config::find))
+        .with_scout_filter(cli.scouts.clone(), excluded_scouts(cli, config))
+}
 
+/// Scan `path` with the given scouts and the filters selected by the CLI and
+/// config, attributing directives to the linters the tree configures.
+fn scan(cli: &Cli, config: &Config, path: &Path, scouts: Vec<Scout>) -> ScanResult {
     let respect_gitignore = !cli.no_gitignore && config.settings.respect_gitignore;
 
-    let registry = match ScoutRegistry::new().with_builtins() {
+    let scanned = if cli.staged {
+        // Paths from git are listed relative to the directory it runs in
+        let dir = git::work_dir(path);
+        git::staged_contents(dir, &git::pathspec(path))
+            .and_then(|files| scanner(cli, config, dir, scouts).run_files(files))
+    } else if let Some(ref rev) = cli.rev {
+        let dir = git::work_dir(path);
+        git::tree_contents(dir, rev, &git::pathspec(path), respect_gitignore)
+            .and_then(|files| scanner(cli, config, dir, scouts).run_files(files))
+    } else {
+        scanner(cli, config, path, scouts)
+            .with_gitignore(respect_gitignore)
+            .run()
+    };
+
+    let mut result = match scanned {
         Ok(r) => r,
         Err(e) => {
-            eprintln!("Error initializing scouts: {e}");
+            eprintln!("Scan error: {e}");
             process::exit(2);
         }
     };
 
-    let registry = match registry.with_config(&config) {
+    let above = configured_above(cli, path);
+    result.configured.extend(above);
+    let configured = result.configured.clone();
+    attribute(cli, &configured, &mut result);
+    RulePolicy::new(&config.policy).apply(&mut result);
+
+    result
+}
+
+/// Attribute directives to the linters the tree configures, filter them by
+/// `--scope` and mark those of unconfigured linters as orphaned.
+fn attribute(cli: &Cli, configured: &ConfiguredLinters, result: &mut ScanResult) {
+    attribution::attribute(result, configured);
+    if let Some(ref scopes) = cli.scope {
+        result.acknowledged.retain(|f| scopes.contains(&f.scope));
+        result.retain(|f| scopes.contains(&f.scope));
+    }
+    configured.apply(result);
+}
+
+/// The suppressions on the lines `diff` removes under the scan path, found
+/// and filtered like those of a scan and located by their path in the diff.
+fn removed_findings(
+    cli: &Cli,
+    config: &Config,
+    diff: &Diff,
+    root: &Path,
+    scouts: Vec<Scout>,
+    configured: &ConfiguredLinters,
+) -> Vec<Finding> {
+    let dir = git::work_dir(&cli.path);
+    let (Ok(root), Ok(scan_dir), Ok(scan_path)) = (
+        root.canonicalize(),
+        dir.canonicalize(),
+        cli.path.canonicalize(),
+    ) else {
+        return Vec::new();
+    };
+    let mut diff_paths = HashMap::new();
+    let files = diff
+        .removed_files(&root, &scan_dir, &scan_path)
+        .into_iter()
+        .map(|(relative, diff_path, content)| {
+            diff_paths.insert(dir.join(&relative).display().to_string(), diff_path);
+            (relative, content)
+        })
+        .collect();
+    let mut removed = match scanner(cli, config, dir, scouts).run_contents(files) {
         Ok(r) => r,
         Err(e) => {
-            eprintln!("Error loading custom scouts: {e}");
+            eprintln!("Scan error: {e}");
             process::exit(2);
         }
     };
+    attribute(cli, configured, &mut removed);
+    for f in &mut removed.findings {
+        if let Some(path) = diff_paths.get(&f.path) {
+            f.path = path.clone();
+        }
+    }
+    removed.findings
+}
 
-    let mut exclude_scouts = config.settings.disable.scouts.clone();
-    if let Some(ref cli_excludes) = cli.exclude_scouts {
-        exclude_scouts.extend(cli_excludes.iter().cloned());
+/// The linters configured above `path`, in the working tree or in the tree
+/// of `--rev`. The scan records those configured under it.
+fn configured_above(cli: &Cli, path: &Path) -> ConfiguredLinters {
+    match cli.rev {
+        Some(ref rev) => match ConfiguredLinters::detect_above_rev(git::work_dir(path), rev) {
+            Ok(linters) => linters,
+            Err(e) => {
+                eprintln!("Error detecting configured linters: {e}");
+                process::exit(2);
+            }
+        },
+        None => ConfiguredLinters::detect_above(path),
     }
+}
 
-    let registry = if let Some(ref names) = cli.scouts {
-        registry.filter(names)
-    } else {
-        registry
+/// Record the usage of the configured budgets by the findings under `path`.
+fn apply_budgets(config: &Config, result: &mut ScanResult, path: &Path) {
+    if config.settings.budgets.is_empty() {
+        return;
+    }
+    match Budgets::new(&config.settings.budgets) {
+        Ok(budgets) => budgets.apply(result, git::work_dir(path)),
+        Err(e) => {
+            eprintln!("Error loading budgets: {e}");
+            process::exit(2);
+        }
+    }
+}
+
+fn write_baseline(cli: &Cli, config: &Config, path: &Path, file: &Path) {
+    let result = scan(cli, config, path, select_scouts(cli, config));
+    let baseline = Baseline::from_result(&result);
+    if let Err(e) = baseline.write(file) {
+        eprintln!("Error writing baseline: {e}");
+        process::exit(2);
+    }
+    if !cli.quiet {
+        eprintln!(
+            "Wrote {} baseline entries to {}",
+            baseline.entries.len(),
+            file.display()
+        );
+    }
+    process::exit(0);
+}
+
+fn install_hook(cli: &Cli, force: bool) {
+    match hook::install(Path::new("."), force) {
+        Ok(path) => {
+            if !cli.quiet {
+                eprintln!("Installed pre-commit hook at {}", path.display());
+            }
+            process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error installing hook: {e}");
+            process::exit(2);
+        }
+    }
+}
+
+fn compare_reports(old: &Path, new: &Path, format: &str) {
+    let load = |path: &Path| match compare::load(path) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(2);
+        }
+    };
+    let comparison = Comparison::new(&load(old), &load(new));
+    print!("{}", comparison.format(format));
+    if format == "json" {
+        println!();
+    }
+    process::exit(if comparison.net > 0 { 1 } else { 0 });
+}
+
+fn hotspots(
+    cli: &Cli,
+    config: &Config,
+    path: &Path,
+    since: Option<i64>,
+    limit: usize,
+    format: &str,
+) {
+    let dir = git::work_dir(path);
+    let commits = match git::changed_files(dir, since, &git::pathspec(path)) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading git history: {e}");
+            process::exit(2);
+        }
     };
+    let result = scan(cli, config, path, select_scouts(cli, config));
+    let mut hotspots = Hotspots::analyze(&result, dir, &commits);
+    hotspots.since = since.map(date::format_date);
+    hotspots.truncate(limit);
+    print!("{}", hotspots.format(format));
+    if format == "json" {
+        println!();
+    }
+    process::exit(0);
+}
 
-    let scouts = registry.exclude(&exclude_scouts).into_scouts();
+/// Whether we run in CI, where the ratchet only checks.
+fn in_ci() -> bool {
+    std::env::var("CI").is_ok_and(|v| !v.is_empty() && v != "false")
+}
 
-    if scouts.is_empty() {
+fn ratchet(cli: &Cli, config: &Config, path: &Path, check_only: bool) {
+    let Some(file) = cli.config.clone().or_else(Config::find) else {
+        eprintln!("Error: no config file with thresholds to ratchet");
+        process::exit(2);
+    };
+    let mut result = scan(cli, config, path, select_scouts(cli, config));
+    apply_budgets(config, &mut result, path);
+    let usage = subtree::apply(
+        &mut result,
+        Thresholds::default(),
+        Thresholds::from(&config.settings),
+        !config.settings.budgets.is_empty(),
+    );
+
+    let ratchet = Ratchet::new(&config.settings, &result, &usage);
+    if ratchet.checks.is_empty() {
         if !cli.quiet {
-            eprintln!("No scouts selected. Check your --scouts or --exclude-scouts flags.");
+            eprintln!("No thresholds set in {}", file.display());
         }
         process::exit(0);
     }
 
-    let scanner = Scanner::new(&cli.path, scouts)
-        .with_excludes(excludes)
-        .with_gitignore(respect_gitignore);
+    let lowered = ratchet.improved().count();
+    let write = !check_only && lowered > 0;
+    if write {
+        let written = fs::read_to_string(&file)
+            .map_err(|e| LintScoutError::FileRead {
+                path: file.clone(),
+                source: e,
+            })
+            .and_then(|yaml| ratchet.rewrite(&yaml))
+            .and_then(|yaml| fs::write(&file, yaml).map_err(LintScoutError::from));
+        if let Err(e) = written {
+            eprintln!("Error updating {}: {e}", file.display());
+            process::exit(2);
+        }
+    }
 
-    let result = match scanner.run() {
-        Ok(r) => r,
+    print!("{}", ratchet.format(write));
+    if write && !cli.quiet {
+        eprintln!("Lowered {lowered} threshold(s) in {}", file.display());
+    }
+    process::exit(if ratchet.regressed() { 1 } else { 0 });
+}
+
+fn sample_history(
+    cli: &Cli,
+    config: &Config,
+    path: &Path,
+    branch: &str,
+    times: &[i64],
+    format: &str,
+) {
+    let respect_gitignore = !cli.no_gitignore && config.settings.respect_gitignore;
+
+    let dir = git::work_dir(path);
+    let scanner = scanner(cli, config, dir, select_scouts(cli, config));
+    let mut history = History::new(dir, &git::pathspec(path), scanner)
+        .with_gitignore(respect_gitignore)
+        .with_scopes(cli.scope.clone());
+
+    let samples = match history.run(branch, times) {
+        Ok(s) => s,
         Err(e) => {
-            eprintln!("Scan error: {e}");
+            eprintln!("History error: {e}");
             process::exit(2);
         }
     };
+    if format == "json" {
+        println!("{}", history::to_json(&samples));
+    } else {
+        print!("{}", history::to_csv(&samples));
+    }
+    process::exit(0);
+}
 
-    if !cli.quiet || !result.findings.is_empty() {
-        print!("{}", output::format_output(&result, &format));
+/// The diff selected with `--diff-base` or `--diff-file`, with a label for
+/// what it compares against.
+fn read_diff(cli: &Cli) -> Option<(String, String)> {
+    let (base, text) = if let Some(ref base) = cli.diff_base {
+        (base.clone(), git::diff(git::work_dir(&cli.path), base))
+    } else if let Some(ref file) = cli.diff_file {
+        let text = if file.as_os_str() == "-" {
+            io::read_to_string(io::stdin()).map_err(LintScoutError::from)
+        } else {
+            fs::read_to_string(file).map_err(|e| LintScoutError::FileRead {
+                path: file.clone(),
+                source: e,
+            })
+        };
+        (file.display().to_string(), text)
+    } else {
+        return None;
+    };
+    match text {
+        Ok(text) => Some((base, text)),
+        Err(e) => {
+            eprintln!("Error reading diff: {e}");
+            process::exit(2);
+        }
+    }
+}
+
+fn check(cli: &Cli, config: &Config) {
+    let format = if cli.format != "text" {
+        cli.format.clone()
+    } else {
+        config.settings.output.clone()
+    };
+
+    let cli_thresholds = Thresholds {
+        pass: cli.pass_threshold,
+        blanket: if cli.fail_on_blanket {
+            Some(0)
+        } else {
+            cli.blanket_threshold
+        },
+        density: cli.density_threshold,
+    };
+    let thresholds = cli_thresholds.or(Thresholds::from(&config.settings));
+    let budgets = !config.settings.budgets.is_empty();
+
+    let scouts = select_scouts(cli, config);
+    let mut result = scan(cli, config, &cli.path, scouts.clone());
+
+    if let Some((base, text)) = read_diff(cli) {
+        let dir = git::work_dir(&cli.path);
+        let root = git::toplevel(dir).unwrap_or_else(|_| PathBuf::from("."));
+        let diff = Diff::parse(&text);
+        let removed = removed_findings(
+            cli,
+            config,
+            &diff,
+            &root,
+            scouts.clone(),
+            &result.configured,
+        );
+        diff.apply(&mut result, &root, removed, &base);
     }
 
-    let exit_code = match pass_threshold {
-        Some(threshold) => {
-            if result.stats.findings_count > threshold {
-                1
-            } else {
-                0
+    if let Some(ref file) = cli.baseline {
+        match Baseline::load(file) {
+            Ok(baseline) => baseline.apply(&mut result, &cli.path, cli.scope.as_deref()),
+            Err(e) => {
+                eprintln!("Error loading baseline: {e}");
+                process::exit(2);
             }
         }
-        None => {
-            if result.stats.findings_count > 0 {
-                1
-            } else {
-                0
-            }
+    }
+
+    let justification = match JustificationPolicy::new(&config.settings.justification) {
+        Ok(policy) => policy,
+        Err(e) => {
+            eprintln!("Error loading justification policy: {e}");
+            process::exit(2);
         }
     };
+    if cli.require_justification || config.settings.justification.required {
+        justification.apply(&mut result);
+    }
+
+    match ExpiryPolicy::new(&config.settings.expiry) {
+        Ok(policy) => policy.apply(&mut result, date::now()),
+        Err(e) => {
+            eprintln!("Error loading expiry policy: {e}");
+            process::exit(2);
+        }
+    }
+
+    if cli.blame || config.settings.blame {
+        let dir = git::work_dir(&cli.path);
+        if let Err(e) = git::toplevel(dir) {
+            eprintln!("Error: blame needs a git repository: {e}");
+            process::exit(2);
+        }
+        let source = match (&cli.rev, cli.staged) {
+            (Some(rev), _) => blame::Source::Rev(rev),
+            (None, true) => blame::Source::Index,
+            (None, false) => blame::Source::WorkTree,
+        };
+        blame::annotate(&mut result, dir, source);
+        AgePolicy::new(&config.settings.age_policies).apply(
+            &mut result,
+            &justification,
+            date::now(),
+        );
+    }
+
+    apply_budgets(config, &mut result, &cli.path);
+    let usage = subtree::apply(&mut result, cli_thresholds, thresholds, budgets);
+
+    if !cli.quiet || !result.findings.is_empty() {
+        print!("{}", output::format_output(&result, &format));
+    }
+
+    // Findings under nested configs with thresholds are checked against those
+    let exit_code = if thresholds.exceeded(&usage, budgets) {
+        1
+    } else {
+        0
+    };
+
+    let fail_on_unknown_rules = cli.fail_on_unknown_rules || config.settings.fail_on_unknown_rules;
+    let fail_on_orphaned = cli.fail_on_orphaned || config.settings.fail_on_orphaned;
+    let exit_code = if result.stats.unjustified_count > 0
+        || result.stats.age_failures > 0
+        || result.stats.policy_violations > 0
+        || result.stats.budgets_exceeded > 0
+        || result.stats.subtrees_exceeded > 0
+        || result.stats.expiry_failures > 0
+        || (fail_on_unknown_rules && result.stats.unknown_rules > 0)
+        || (fail_on_orphaned && result.stats.orphaned_count > 0)
+    {
+        1
+    } else {
+        exit_code
+    };
 
     process::exit(exit_code);
 }
@@ -0,0 +1,290 @@
+//! Linters configured in the scanned tree, detected from their config files,
+//! and the orphaned directives of linters that are not: left behind after the
+//! linter was dropped, they suppress nothing and are safe to delete.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::git;
+use crate::scanner::ScanResult;
+
+/// A file whose presence shows that a linter is configured.
+struct Marker {
+    linter: &'static str,
+    /// File name, with at most one `*` wildcard.
+    file: &'static str,
+    /// Text the file must contain, for files shared by several tools.
+    section: Option<&'static str>,
+}
+
+const fn file(linter: &'static str, file: &'static str) -> Marker {
+    Marker {
+        linter,
+        file,
+        section: None,
+    }
+}
+
+const fn section(linter: &'static str, file: &'static str, section: &'static str) -> Marker {
+    Marker {
+        linter,
+        file,
+        section: Some(section),
+    }
+}
+
+/// Linters without markers, such as Java's `@SuppressWarnings` or
+/// staticcheck, whose directives golangci-lint honors too, are never
+/// reported as orphaned.
+const MARKERS: &[Marker] = &[
+    file("eslint", ".eslintrc*"),
+    file("eslint", "eslint.config.*"),
+    section("eslint", "package.json", "\"eslintConfig\""),
+    file("oxlint", ".oxlintrc.json"),
+    file("oxlint", "oxlint.config.*"),
+    file("biome", "biome.json*"),
+    file("jshint", ".jshintrc"),
+    section("jshint", "package.json", "\"jshintConfig\""),
+    file("prettier", ".prettierrc*"),
+    file("prettier", "prettier.config.*"),
+    section("prettier", "package.json", "\"prettier\""),
+    file("stylelint", ".stylelintrc*"),
+    file("stylelint", "stylelint.config.*"),
+    section("stylelint", "package.json", "\"stylelint\""),
+    file("typescript", "tsconfig*.json"),
+    file("typescript", "jsconfig.json"),
+    file("pylint", ".pylintrc"),
+    file("pylint", "pylintrc"),
+    section("pylint", "pyproject.toml", "[tool.pylint"),
+    section("pylint", "setup.cfg", "[pylint"),
+    section("pylint", "tox.ini", "[pylint"),
+    file("flake8", ".flake8"),
+    section("flake8", "setup.cfg", "[flake8]"),
+    section("flake8", "tox.ini", "[flake8]"),
+    file("ruff", "ruff.toml"),
+    file("ruff", ".ruff.toml"),
+    section("ruff", "pyproject.toml", "[tool.ruff"),
+    file("mypy", "mypy.ini"),
+    file("mypy", ".mypy.ini"),
+    section("mypy", "pyproject.toml", "[tool.mypy"),
+    section("mypy", "setup.cfg", "[mypy"),
+    file("pyright", "pyrightconfig.json"),
+    section("pyright", "pyproject.toml", "[tool.pyright"),
+    file("bandit", ".bandit"),
+    section("bandit", "pyproject.toml", "[tool.bandit"),
+    file("golangci-lint", ".golangci.*"),
+    // Clippy runs without configuration in any Cargo project
+    file("clippy", "clippy.toml"),
+    file("clippy", ".clippy.toml"),
+    file("clippy", "Cargo.toml"),
+    file("rubocop", ".rubocop.yml"),
+    file("rubocop", ".rubocop_todo.yml"),
+    file("phpstan", "phpstan.neon*"),
+    file("phpstan", "phpstan.dist.neon"),
+    file("swiftlint", ".swiftlint.yml"),
+    file("hadolint", ".hadolint.y*ml"),
+    file("hadolint", "hadolint.y*ml"),
+    file("shellcheck", ".shellcheckrc"),
+    file("shellcheck", "shellcheckrc"),
+    file("detekt", "detekt*.yml"),
+    section("ktlint", ".editorconfig", "ktlint"),
+    file("clang-tidy", ".clang-tidy"),
+];
+
+impl Marker {
+    fn matches_name(&self, name: &str) -> bool {
+        name_matches(self.file, name)
+    }
+}
+
+/// Whether a file name matches a pattern with at most one `*` wildcard.
+pub(crate) fn name_matches(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        }
+        None => name == pattern,
+    }
+}
+
+/// Whether a file with this name may show that a linter is configured.
+pub(crate) fn is_candidate(name: &str) -> bool {
+    MARKERS.iter().any(|m| m.matches_name(name))
+}
+
+/// Whether a candidate's markers need its content.
+fn needs_content(name: &str) -> bool {
+    MARKERS
+        .iter()
+        .any(|m| m.section.is_some() && m.matches_name(name))
+}
+
+/// Whether configured linters can be detected for `linter` at all.
+pub fn detectable(linter: &str) -> bool {
+    MARKERS.iter().any(|m| m.linter == linter)
+}
+
+/// The linters configured in a tree. A scan records those configured under
+/// the scanned path as it walks it, in [`ScanResult::configured`]; the ones
+/// configured above it are detected separately.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfiguredLinters {
+    linters: BTreeSet<String>,
+}
+
+impl ConfiguredLinters {
+    /// Detect the linters configured in the directories above `path`, up to
+    /// the root of its git working tree.
+    pub fn detect_above(path: &Path) -> Self {
+        let mut configured = ConfiguredLinters::default();
+        let dir = fs::canonicalize(git::work_dir(path)).unwrap_or_else(|_| path.to_path_buf());
+        let Some(top) = dir.ancestors().find(|d| d.join(".git").exists()) else {
+            return configured;
+        };
+        for ancestor in dir.ancestors().skip(1).take_while(|d| d.starts_with(top)) {
+            let Ok(entries) = fs::read_dir(ancestor) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if entry.file_type().is_ok_and(|t| t.is_file()) {
+                    configured.record_file(&entry.path());
+                }
+            }
+        }
+        configured
+    }
+
+    /// Like [`ConfiguredLinters::detect_above`], in the tree of the git
+    /// revision `rev` rather than the working tree.
+    pub fn detect_above_rev(dir: &Path, rev: &str) -> Result<Self> {
+        let (top, prefix) = match git::toplevel(dir) {
+            Ok(top) => {
+                let prefix = git::run(dir, &["rev-parse", "--show-prefix"])?;
+                (top, PathBuf::from(prefix.trim()))
+            }
+            // Bare repositories have no working tree to be inside of
+            Err(_) => (dir.to_path_buf(), PathBuf::new()),
+        };
+        let (paths, oids): (Vec<PathBuf>, Vec<String>) = git::tree_files(&top, rev, ".")?
+            .into_iter()
+            .filter(|(path, _)| {
+                let parent = path.parent().unwrap_or(Path::new(""));
+                // Files under the scanned path are recorded by the scan
+                prefix.starts_with(parent)
+                    && parent != prefix
+                    && path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(is_candidate)
+            })
+            .unzip();
+        let blobs = git::read_blobs(&top, &oids)?;
+
+        let mut configured = ConfiguredLinters::default();
+        for (path, blob) in paths.iter().zip(blobs) {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                configured.record(name, &String::from_utf8_lossy(&blob));
+            }
+        }
+        Ok(configured)
+    }
+
+    /// Record the linters configured by the file at `path`, reading it only
+    /// when its name alone does not tell.
+    pub fn record_file(&mut self, path: &Path) {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return;
+        };
+        if !is_candidate(name) {
+            return;
+        }
+        let content = if needs_content(name) {
+            fs::read_to_string(path).unwrap_or_default()
+        } else {
+            String::new()
+        };
+        self.record(name, &content);
+    }
+
+    /// Add the linters configured in another part of the tree.
+    pub fn extend(&mut self, other: ConfiguredLinters) {
+        self.linters.extend(other.linters);
+    }
+
+    /// Record the linters configured by a file named `name`.
+    pub fn record(&mut self, name: &str, content: &str) {
+        for marker in MARKERS.iter().filter(|m| m.matches_name(name)) {
+            if marker.section.map_or(true, |s| content.contains(s)) {
+                self.linters.insert(marker.linter.to_string());
+            }
+        }
+    }
+
+    pub fn is_configured(&self, linter: &str) -> bool {
+        self.linters.contains(linter)
+    }
+
+    pub fn linters(&self) -> impl Iterator<Item = &str> {
+        self.linters.iter().map(String::as_str)
+    }
+
+    /// Mark the findings of detectable linters that are not configured as
+    /// orphaned. Rule policy violations are left unmarked so that each
+    /// directive counts once.
+    pub fn apply(&self, result: &mut ScanResult) {
+        for finding in &mut result.findings {
+            finding.orphaned = finding.policy_violation.is_none()
+                && detectable(&finding.linter)
+                && !self.is_configured(&finding.linter);
+        }
+        result.recount();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_marker_names() {
+        assert!(is_candidate(".eslintrc.json"));
+        assert!(is_candidate("eslint.config.mjs"));
+        assert!(is_candidate("tsconfig.build.json"));
+        assert!(is_candidate(".hadolint.yaml"));
+        assert!(is_candidate("phpstan.neon.dist"));
+        assert!(!is_candidate("eslint.json"));
+        assert!(!is_candidate("README.md"));
+        assert!(needs_content("pyproject.toml"));
+        assert!(!needs_content(".golangci.yml"));
+    }
+
+    #[test]
+    fn records_configured_linters() {
+        let mut configured = ConfiguredLinters::default();
+        configured.record(
+            "pyproject.toml",
+            "[project]\nname = \"app\"\n\n[tool.ruff]\nline-length = 100\n\n[tool.mypy.overrides]\n",
+        );
+        configured.record("setup.cfg", "[metadata]\nname = app\n");
+        configured.record("package.json", "{\"name\": \"app\", \"eslintConfig\": {}}");
+        configured.record(".golangci.yml", "");
+        assert_eq!(
+            configured.linters().collect::<Vec<_>>(),
+            ["eslint", "golangci-lint", "mypy", "ruff"]
+        );
+        assert!(!configured.is_configured("flake8"));
+        assert!(!configured.is_configured("jshint"));
+    }
+
+    #[test]
+    fn undetectable_linters_are_never_orphaned() {
+        assert!(detectable("jshint"));
+        assert!(detectable("clippy"));
+        assert!(!detectable("java"));
+        assert!(!detectable("gosec"));
+    }
+}
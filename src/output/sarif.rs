@@ -45,10 +45,20 @@ struct SarifResult {
     rule_index: usize,
     message: SarifMessage,
     locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<SarifSuppression>,
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     properties: HashMap<String, serde_json::Value>,
 }
 
+/// Marks a finding approved by a `lintscout-ignore` comment.
+#[derive(Serialize)]
+struct SarifSuppression {
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    justification: Option<String>,
+}
+
 #[derive(Serialize)]
 struct SarifMessage {
     text: String,
@@ -76,13 +86,15 @@ struct SarifArtifactLocation {
 struct SarifRegion {
     #[serde(rename = "startLine")]
     start_line: usize,
+    #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
 }
 
 pub fn format(result: &ScanResult) -> String {
     let mut rule_map: HashMap<String, usize> = HashMap::new();
     let mut rules: Vec<SarifRuleDescriptor> = Vec::new();
 
-    for finding in &result.findings {
+    for finding in result.findings.iter().chain(&result.acknowledged) {
         let composite_key = format!("{}/{}", finding.linter, finding.rule_id);
         if !rule_map.contains_key(&composite_key) {
             let index = rules.len();
@@ -99,6 +111,7 @@ pub fn format(result: &ScanResult) -> String {
     let results: Vec<SarifResult> = result
         .findings
         .iter()
+        .chain(&result.acknowledged)
         .map(|f| {
             let composite_key = format!("{}/{}", f.linter, f.rule_id);
             let rule_index = rule_map[&composite_key];
@@ -112,12 +125,73 @@ pub fn format(result: &ScanResult) -> String {
                 "scout_name".to_string(),
                 serde_json::Value::String(f.scout_name.clone()),
             );
+            properties.insert(
+                "scope".to_string(),
+                serde_json::Value::String(f.scope.to_string()),
+            );
+            properties.insert("blanket".to_string(), serde_json::Value::Bool(f.blanket));
             if let Some(ref suppressed) = f.suppressed_rules {
                 properties.insert(
                     "suppressed_rules".to_string(),
                     serde_json::json!(suppressed),
                 );
             }
+            if let Some(ref region) = f.region {
+                properties.insert("region".to_string(), serde_json::json!(region));
+            }
+            if let Some(ref justification) = f.justification {
+                properties.insert(
+                    "justification".to_string(),
+                    serde_json::Value::String(justification.clone()),
+                );
+            }
+            if let Some(ref blame) = f.blame {
+                properties.insert("blame".to_string(), serde_json::json!(blame));
+            }
+            if !f.age_policies.is_empty() {
+                properties.insert(
+                    "age_policies".to_string(),
+                    serde_json::json!(f.age_policies),
+                );
+            }
+            if let Some(ref violation) = f.policy_violation {
+                properties.insert("policy_violation".to_string(), serde_json::json!(violation));
+            }
+            if let Some(ref expiry) = f.expiry {
+                properties.insert("expiry".to_string(), serde_json::json!(expiry));
+            }
+            if !f.rule_details.is_empty() {
+                properties.insert(
+                    "rule_details".to_string(),
+                    serde_json::json!(f.rule_details),
+                );
+            }
+            if !f.also_honored_by.is_empty() {
+                properties.insert(
+                    "also_honored_by".to_string(),
+                    serde_json::json!(f.also_honored_by),
+                );
+            }
+            if f.orphaned {
+                properties.insert("orphaned".to_string(), serde_json::Value::Bool(true));
+            }
+            if let Some(ref key_path) = f.key_path {
+                properties.insert(
+                    "key_path".to_string(),
+                    serde_json::Value::String(key_path.clone()),
+                );
+            }
+            if let Some(ref issue) = f.justification_issue {
+                properties.insert(
+                    "justification_issue".to_string(),
+                    serde_json::Value::String(issue.clone()),
+                );
+            }
+
+            let description = match f.policy_violation {
+                Some(ref v) => format!("{} '{}'", f.rule_description, v.rule),
+                None => f.rule_description.clone(),
+            };
 
             SarifResult {
                 rule_id: composite_key,
@@ -125,7 +199,7 @@ pub fn format(result: &ScanResult) -> String {
                 message: SarifMessage {
                     text: format!(
                         "{}: {} (line: {})",
-                        f.rule_description,
+                        description,
                         f.line_text.trim(),
                         f.line_number
                     ),
@@ -137,9 +211,18 @@ pub fn format(result: &ScanResult) -> String {
                         },
                         region: SarifRegion {
                             start_line: f.line_number,
+                            end_line: f.region.as_ref().map(|r| r.end_line),
                         },
                     },
                 }],
+                suppressions: f
+                    .acknowledgement
+                    .iter()
+                    .map(|a| SarifSuppression {
+                        kind: "inSource",
+                        justification: a.reason.clone(),
+                    })
+                    .collect(),
                 properties,
             }
         })
@@ -167,7 +250,8 @@ pub fn format(result: &ScanResult) -> String {
 mod tests {
     use super::*;
     use crate::finding::Finding;
-    use crate::stats::ScanStats;
+    use crate::region::SuppressedRegion;
+    use crate::scope::Scope;
 
     #[test]
     fn sarif_output_structure() {
@@ -180,10 +264,11 @@ mod tests {
                 linter: "eslint".into(),
                 rule_id: "eslint-disable-next-line".into(),
                 rule_description: "ESLint disable next line".into(),
+                scope: Scope::NextLine,
                 suppressed_rules: Some(vec!["no-alert".into()]),
+                ..Default::default()
             }],
-            stats: ScanStats::default(),
-            errors: Vec::new(),
+            ..Default::default()
         };
 
         let output = format(&result);
@@ -206,6 +291,37 @@ mod tests {
             10
         );
         assert_eq!(results[0]["properties"]["suppressed_rules"][0], "no-alert");
+        assert_eq!(results[0]["properties"]["scope"], "next-line");
+    }
+
+    #[test]
+    fn sarif_region_end_line() {
+        let result = ScanResult {
+            findings: vec![Finding {
+                path: "a.js".into(),
+                line_number: 4,
+                line_text: "/* eslint-disable */".into(),
+                scout_name: "eslint".into(),
+                linter: "eslint".into(),
+                rule_id: "eslint-disable".into(),
+                rule_description: "ESLint disable block directive".into(),
+                scope: Scope::Region,
+                blanket: true,
+                region: Some(SuppressedRegion {
+                    start_line: 4,
+                    end_line: 9,
+                    lines: 4,
+                    terminated: true,
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&format(&result)).unwrap();
+        let region = &json["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 4);
+        assert_eq!(region["endLine"], 9);
     }
 
     #[test]
@@ -220,7 +336,9 @@ mod tests {
                     linter: "eslint".into(),
                     rule_id: "eslint-disable-next-line".into(),
                     rule_description: "ESLint disable next line".into(),
-                    suppressed_rules: None,
+                    scope: Scope::NextLine,
+                    blanket: true,
+                    ..Default::default()
                 },
                 Finding {
                     path: "b.js".into(),
@@ -230,11 +348,12 @@ mod tests {
                     linter: "eslint".into(),
                     rule_id: "eslint-disable-next-line".into(),
                     rule_description: "ESLint disable next line".into(),
-                    suppressed_rules: None,
+                    scope: Scope::NextLine,
+                    blanket: true,
+                    ..Default::default()
                 },
             ],
-            stats: ScanStats::default(),
-            errors: Vec::new(),
+            ..Default::default()
         };
 
         let output = format(&result);
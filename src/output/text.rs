@@ -1,3 +1,8 @@
+use std::collections::BTreeMap;
+
+use crate::exemption::Exemption;
+use crate::expiry::Expiry;
+use crate::finding::Finding;
 use crate::scanner::ScanResult;
 
 pub fn format(result: &ScanResult) -> String {
@@ -5,24 +10,16 @@ pub fn format(result: &ScanResult) -> String {
 
     if result.findings.is_empty() {
         out.push_str("No lint ignore directives found.\n");
+    } else if result.findings.iter().any(|f| f.blame.is_some()) {
+        for (author, findings) in group_by_author(&result.findings) {
+            out.push_str(&format!("== {author}: {} ==\n", findings.len()));
+            for f in findings {
+                out.push_str(&format_finding(f));
+            }
+        }
     } else {
         for f in &result.findings {
-            let suppressed = match &f.suppressed_rules {
-                Some(rules) if !rules.is_empty() => {
-                    format!(" (suppresses: {})", rules.join(", "))
-                }
-                _ => String::new(),
-            };
-            out.push_str(&format!(
-                "{}:{} [{}:{}] {}{}\n    {}\n",
-                f.path,
-                f.line_number,
-                f.linter,
-                f.rule_id,
-                f.rule_description,
-                suppressed,
-                f.line_text.trim()
-            ));
+            out.push_str(&format_finding(f));
         }
     }
 
@@ -32,6 +29,176 @@ pub fn format(result: &ScanResult) -> String {
         result.stats.files_walked, result.stats.files_scanned, result.stats.files_skipped
     ));
     out.push_str(&format!("Findings: {}\n", result.stats.findings_count));
+    if let Some(ref density) = result.density {
+        out.push_str(&format!(
+            "Density: {:.2} per KLOC ({} lines)\n",
+            density.per_kloc, density.lines
+        ));
+        for (dimension, groups) in [
+            ("language", &density.by_language),
+            ("scout", &density.by_scout),
+            ("directory", &density.by_directory),
+        ] {
+            for (key, group) in groups.iter().filter(|(_, g)| g.findings > 0) {
+                out.push_str(&format!(
+                    "  {dimension} {key}: {:.2} per KLOC ({} in {} lines)\n",
+                    group.per_kloc, group.findings, group.lines
+                ));
+            }
+        }
+    }
+    if let Some(ref diff) = result.diff {
+        out.push_str(&format!(
+            "Diff against {}: {} added, {} removed\n",
+            diff.base,
+            diff.added,
+            diff.removed.len()
+        ));
+        for f in &diff.removed {
+            out.push_str(&format!(
+                "  removed: {}:{} [{}:{}] {}\n",
+                f.path,
+                f.line_number,
+                f.linter,
+                f.rule_id,
+                f.line_text.trim()
+            ));
+        }
+    }
+    if let Some(ref baseline) = result.baseline {
+        out.push_str(&format!(
+            "Baseline: {} known, {} disappeared\n",
+            baseline.matched,
+            baseline.disappeared.len()
+        ));
+        for entry in &baseline.disappeared {
+            out.push_str(&format!(
+                "  gone: {}:{} [{}:{}] {}\n",
+                entry.path, entry.line_number, entry.scout_name, entry.rule_id, entry.line_text
+            ));
+        }
+    }
+    if result.stats.acknowledged_count > 0 {
+        out.push_str(&format!(
+            "Acknowledged: {}\n",
+            result.stats.acknowledged_count
+        ));
+    }
+    if result.stats.blanket_count > 0 {
+        out.push_str(&format!("Blanket: {}\n", result.stats.blanket_count));
+    }
+    if !result.budgets.is_empty() {
+        out.push_str(&format!(
+            "Budgets: {} of {} exceeded\n",
+            result.stats.budgets_exceeded,
+            result.budgets.len()
+        ));
+        for b in &result.budgets {
+            let scouts = match &b.scouts {
+                Some(scouts) => format!(" [{}]", scouts.join(", ")),
+                None => String::new(),
+            };
+            let exceeded = if b.exceeded { " (exceeded)" } else { "" };
+            out.push_str(&format!(
+                "  {}{}: {} of {}{}\n",
+                b.path, scouts, b.used, b.max, exceeded
+            ));
+        }
+    }
+    if !result.subtrees.is_empty() {
+        out.push_str(&format!(
+            "Subtrees: {} of {} exceeded\n",
+            result.stats.subtrees_exceeded,
+            result.subtrees.len()
+        ));
+        for s in &result.subtrees {
+            let mut usage = match s.pass_threshold {
+                Some(max) => vec![format!("{} of {max} findings", s.usage.findings)],
+                None => vec![format!("{} findings", s.usage.findings)],
+            };
+            if let Some(max) = s.blanket_threshold {
+                usage.push(format!("{} of {max} blanket", s.usage.blanket));
+            }
+            if let (Some(max), Some(per_kloc)) = (s.density_threshold, s.usage.per_kloc) {
+                usage.push(format!("{per_kloc:.2} of {max:.2} per KLOC"));
+            }
+            let exceeded = if s.exceeded { " (exceeded)" } else { "" };
+            out.push_str(&format!(
+                "  {} ({}): {}{}\n",
+                s.dir,
+                s.config,
+                usage.join(", "),
+                exceeded
+            ));
+        }
+    }
+    if result.stats.policy_violations > 0 {
+        out.push_str(&format!(
+            "Policy violations: {}\n",
+            result.stats.policy_violations
+        ));
+    }
+    if result.stats.expiry_failures > 0 {
+        out.push_str(&format!(
+            "Expiry failures: {}\n",
+            result.stats.expiry_failures
+        ));
+    }
+    if result.stats.orphaned_count > 0 {
+        let mut linters: Vec<&str> = result
+            .findings
+            .iter()
+            .filter(|f| f.orphaned)
+            .map(|f| f.linter.as_str())
+            .collect();
+        linters.sort_unstable();
+        linters.dedup();
+        out.push_str(&format!(
+            "Orphaned: {} (not configured: {})\n",
+            result.stats.orphaned_count,
+            linters.join(", ")
+        ));
+    }
+    if result.stats.config_count > 0 {
+        out.push_str(&format!(
+            "Config suppressions: {}\n",
+            result.stats.config_count
+        ));
+    }
+    if !result.exemptions.is_empty() {
+        let labels: Vec<String> = result.exemptions.iter().map(exemption_label).collect();
+        out.push_str(&format!("Exemptions: {}\n", labels.join(", ")));
+        for (e, label) in result.exemptions.iter().zip(&labels) {
+            let linter = match e.rule {
+                Some(_) => format!(" ({})", e.linter),
+                None => String::new(),
+            };
+            let sources: Vec<String> = e
+                .sources
+                .iter()
+                .map(|s| format!("{}:{} `{}`", s.path, s.line, s.pattern))
+                .collect();
+            out.push_str(&format!("  {label}{linter} by {}\n", sources.join(", ")));
+            for file in &e.files {
+                out.push_str(&format!("    {file}\n"));
+            }
+        }
+    }
+    if result.stats.unknown_rules > 0 {
+        out.push_str(&format!("Unknown rules: {}\n", result.stats.unknown_rules));
+    }
+    if result.stats.age_failures > 0 {
+        out.push_str(&format!(
+            "Age policy failures: {}\n",
+            result.stats.age_failures
+        ));
+    }
+    if result.stats.unjustified_count > 0 {
+        out.push_str(&format!(
+            "Unjustified: {}\n",
+            result.stats.unjustified_count
+        ));
+    }
     if result.stats.errors_count > 0 {
         out.push_str(&format!("Errors: {}\n", result.stats.errors_count));
     }
@@ -39,3 +206,123 @@ pub fn format(result: &ScanResult) -> String {
 
     out
 }
+
+/// `3 files exempted from eslint` or `1 file with E501 disabled`.
+fn exemption_label(e: &Exemption) -> String {
+    let files = match e.files.len() {
+        1 => "1 file".to_string(),
+        n => format!("{n} files"),
+    };
+    match e.rule {
+        Some(ref rule) => format!("{files} with {rule} disabled"),
+        None => format!("{files} exempted from {}", e.linter),
+    }
+}
+
+fn format_finding(f: &Finding) -> String {
+    let suppressed = match &f.suppressed_rules {
+        Some(rules) if !rules.is_empty() => {
+            format!(" (suppresses: {})", rules.join(", "))
+        }
+        _ => String::new(),
+    };
+    let scope = match &f.region {
+        Some(r) if r.terminated => format!(
+            " [{} {}-{}, {} lines]",
+            f.scope, r.start_line, r.end_line, r.lines
+        ),
+        Some(r) => format!(
+            " [{} {}-EOF, {} lines, unterminated]",
+            f.scope, r.start_line, r.lines
+        ),
+        None => match &f.key_path {
+            Some(key_path) => format!(" [{} {key_path}]", f.scope),
+            None => format!(" [{}]", f.scope),
+        },
+    };
+    let blanket = if f.blanket { " [blanket]" } else { "" };
+    let unjustified = match &f.justification_issue {
+        Some(issue) => format!(" [unjustified: {issue}]"),
+        None => String::new(),
+    };
+    let blame = match &f.blame {
+        Some(b) => format!(" [{} {}]", &b.commit[..b.commit.len().min(8)], b.date),
+        None => String::new(),
+    };
+    let violation = match &f.policy_violation {
+        Some(v) => match v.quota {
+            Some(quota) => format!(" [policy: {} over quota of {quota}]", v.rule),
+            None => format!(" [policy: {} denied]", v.rule),
+        },
+        None => String::new(),
+    };
+    let policies = if f.age_policies.is_empty() {
+        String::new()
+    } else {
+        let names: Vec<&str> = f.age_policies.iter().map(|m| m.name.as_str()).collect();
+        format!(" [age: {}]", names.join(", "))
+    };
+    let expiry = match &f.expiry {
+        Some(Expiry {
+            issue: Some(issue), ..
+        }) => format!(" [expiry: {issue}]"),
+        Some(e) => {
+            let marker: Vec<String> = e
+                .expires
+                .iter()
+                .map(|d| format!("until {d}"))
+                .chain(e.ticket.clone())
+                .collect();
+            format!(" [{}]", marker.join(", "))
+        }
+        None => String::new(),
+    };
+    let orphaned = if f.orphaned { " [orphaned]" } else { "" };
+    let honored = if f.also_honored_by.is_empty() {
+        String::new()
+    } else {
+        format!(" [also honored by {}]", f.also_honored_by.join(", "))
+    };
+    let unknown: String = f
+        .unknown_rules()
+        .map(|d| match d.suggestion {
+            Some(ref s) => format!(" [unknown rule {}, did you mean {s}?]", d.rule),
+            None => format!(" [unknown rule {}]", d.rule),
+        })
+        .collect();
+    format!(
+        "{}:{} [{}:{}] {}{}{}{}{}{}{}{}{}{}{}{}\n    {}\n",
+        f.path,
+        f.line_number,
+        f.linter,
+        f.rule_id,
+        f.rule_description,
+        suppressed,
+        scope,
+        blanket,
+        unjustified,
+        blame,
+        policies,
+        violation,
+        expiry,
+        unknown,
+        honored,
+        orphaned,
+        f.line_text.trim()
+    )
+}
+
+/// Group findings by blamed author, largest group first.
+fn group_by_author(findings: &[Finding]) -> Vec<(String, Vec<&Finding>)> {
+    let mut groups: BTreeMap<String, Vec<&Finding>> = BTreeMap::new();
+    for f in findings {
+        let author = match &f.blame {
+            Some(b) => format!("{} <{}>", b.author, b.author_mail),
+            None => "(not blamed)".to_string(),
+        };
+        groups.entry(author).or_default().push(f);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(&b.0)));
+    groups
+}
@@ -0,0 +1,513 @@
+use std::collections::{BTreeMap, HashMap};
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AgeAction, AgePolicyConfig, JustificationConfig, RulePolicyConfig};
+use crate::date::SECONDS_PER_DAY;
+use crate::error::{LintScoutError, Result};
+use crate::finding::Finding;
+use crate::fingerprint;
+use crate::scanner::ScanResult;
+
+/// Checks suppression justifications against the configured policy.
+pub struct JustificationPolicy {
+    min_length: usize,
+    forbidden: Vec<(String, Regex)>,
+}
+
+impl JustificationPolicy {
+    pub fn new(config: &JustificationConfig) -> Result<Self> {
+        let mut forbidden = Vec::new();
+        for phrase in &config.forbidden_phrases {
+            let pattern = format!(r"\b{}\b", regex::escape(phrase));
+            let re = RegexBuilder::new(&pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| LintScoutError::InvalidPattern { pattern, source: e })?;
+            forbidden.push((phrase.clone(), re));
+        }
+        Ok(JustificationPolicy {
+            min_length: config.min_length,
+            forbidden,
+        })
+    }
+
+    /// Describe why a finding's justification is unacceptable, if it is.
+    pub fn check(&self, finding: &Finding) -> Option<String> {
+        let reason = match finding.justification.as_deref() {
+            None => return Some("missing justification".into()),
+            Some("") => return Some("empty justification".into()),
+            Some(r) => r,
+        };
+        if reason.chars().count() < self.min_length {
+            return Some(format!(
+                "justification shorter than {} characters",
+                self.min_length
+            ));
+        }
+        self.forbidden
+            .iter()
+            .find(|(_, re)| re.is_match(reason))
+            .map(|(phrase, _)| format!("justification uses forbidden phrase '{phrase}'"))
+    }
+
+    /// Record the justification issue of every finding and update the
+    /// unjustified count. Policy violations repeat their directive and are
+    /// not checked again.
+    pub fn apply(&self, result: &mut ScanResult) {
+        for finding in &mut result.findings {
+            if finding.policy_violation.is_none() {
+                finding.justification_issue = self.check(finding);
+            }
+        }
+        result.recount();
+    }
+}
+
+/// An age policy that a finding falls under.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgePolicyMatch {
+    pub name: String,
+    pub action: AgeAction,
+}
+
+/// Flags blamed findings by how long ago their line was last changed.
+pub struct AgePolicy {
+    policies: Vec<AgePolicyConfig>,
+}
+
+impl AgePolicy {
+    pub fn new(policies: &[AgePolicyConfig]) -> Self {
+        AgePolicy {
+            policies: policies.to_vec(),
+        }
+    }
+
+    /// The policies matching a finding at time `now`. Findings without blame
+    /// information match nothing.
+    pub fn check(
+        &self,
+        finding: &Finding,
+        justification: &JustificationPolicy,
+        now: i64,
+    ) -> Vec<AgePolicyMatch> {
+        let Some(ref blame) = finding.blame else {
+            return Vec::new();
+        };
+        let age_days = (now - blame.author_time).div_euclid(SECONDS_PER_DAY);
+        self.policies
+            .iter()
+            .filter(|p| p.younger_than_days.map_or(true, |d| age_days < d as i64))
+            .filter(|p| p.older_than_days.map_or(true, |d| age_days > d as i64))
+            .filter(|p| !p.unjustified || justification.check(finding).is_some())
+            .map(|p| AgePolicyMatch {
+                name: p.name.clone(),
+                action: p.action,
+            })
+            .collect()
+    }
+
+    /// Record the matching policies of every finding other than rule policy
+    /// violations and update the count of failing ones.
+    pub fn apply(&self, result: &mut ScanResult, justification: &JustificationPolicy, now: i64) {
+        for finding in &mut result.findings {
+            if finding.policy_violation.is_none() {
+                finding.age_policies = self.check(finding, justification, now);
+            }
+        }
+        result.recount();
+    }
+}
+
+/// How a directive breaks a rule policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViolationKind {
+    /// It suppresses a denied rule.
+    Deny,
+    /// It suppresses a rule beyond its quota.
+    Quota,
+}
+
+/// A rule policy violation, reported as a finding of its own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    pub kind: ViolationKind,
+    /// The offending suppressed rule.
+    pub rule: String,
+    /// The exceeded quota, for quota violations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<u64>,
+}
+
+/// Evaluates the rules named by directives against per-linter allow lists,
+/// deny lists and quotas.
+pub struct RulePolicy {
+    policies: BTreeMap<String, RulePolicyConfig>,
+}
+
+impl RulePolicy {
+    pub fn new(policies: &BTreeMap<String, RulePolicyConfig>) -> Self {
+        RulePolicy {
+            policies: policies.clone(),
+        }
+    }
+
+    /// The policy for a finding, configured by scout name or linter, with
+    /// the name it is configured under.
+    fn policy_for(&self, finding: &Finding) -> Option<(&str, &RulePolicyConfig)> {
+        self.policies
+            .get_key_value(&finding.scout_name)
+            .or_else(|| self.policies.get_key_value(&finding.linter))
+            .map(|(key, policy)| (key.as_str(), policy))
+    }
+
+    /// Whether a finding only suppresses allowed rules.
+    pub fn is_allowed(&self, finding: &Finding) -> bool {
+        let (Some((_, policy)), Some(rules)) =
+            (self.policy_for(finding), &finding.suppressed_rules)
+        else {
+            return false;
+        };
+        !rules.is_empty()
+            && rules
+                .iter()
+                .all(|rule| !matches_any(&policy.deny, rule) && matches_any(&policy.allow, rule))
+    }
+
+    /// Drop findings that only suppress allowed rules, and add a finding
+    /// after each directive for every denied rule it suppresses and every
+    /// suppression beyond a quota. Blanket directives suppress every denied
+    /// rule, and are reported once for each deny pattern. Quotas count
+    /// directives in finding order, per policy.
+    pub fn apply(&self, result: &mut ScanResult) {
+        if self.policies.is_empty() {
+            return;
+        }
+        let mut used: HashMap<(&str, &str), u64> = HashMap::new();
+        let mut findings = Vec::with_capacity(result.findings.len());
+        for finding in &result.findings {
+            if finding.policy_violation.is_some() || self.is_allowed(finding) {
+                continue;
+            }
+            let mut violations = Vec::new();
+            let policy = self.policy_for(finding);
+            if let (Some((_, policy)), true) = (policy, finding.blanket) {
+                for pattern in &policy.deny {
+                    violations.push(violation(finding, ViolationKind::Deny, pattern, None));
+                }
+            } else if let (Some((key, policy)), Some(rules)) = (policy, &finding.suppressed_rules) {
+                for rule in rules {
+                    if matches_any(&policy.deny, rule) {
+                        violations.push(violation(finding, ViolationKind::Deny, rule, None));
+                        continue;
+                    }
+                    if matches_any(&policy.allow, rule) {
+                        continue;
+                    }
+                    for (pattern, &quota) in &policy.quota {
+                        if !matches(pattern, rule) {
+                            continue;
+                        }
+                        let count = used.entry((key, pattern)).or_default();
+                        *count += 1;
+                        if *count > quota {
+                            violations.push(violation(
+                                finding,
+                                ViolationKind::Quota,
+                                rule,
+                                Some(quota),
+                            ));
+                        }
+                    }
+                }
+            }
+            findings.push(finding.clone());
+            findings.extend(violations);
+        }
+        result.findings = findings;
+        result.recount();
+    }
+}
+
+fn violation(finding: &Finding, kind: ViolationKind, rule: &str, quota: Option<u64>) -> Finding {
+    let (rule_id, rule_description) = match kind {
+        ViolationKind::Deny => ("policy-deny", "Suppresses a denied rule"),
+        ViolationKind::Quota => ("policy-quota", "Suppresses a rule beyond its quota"),
+    };
+    Finding {
+        rule_id: rule_id.into(),
+        rule_description: rule_description.into(),
+        blanket: false,
+        suppressed_rules: Some(vec![rule.to_string()]),
+        region: None,
+        justification_issue: None,
+        fingerprint: fingerprint::derive(&finding.fingerprint, &[rule_id, rule]),
+        age_policies: Vec::new(),
+        expiry: None,
+        acknowledgement: None,
+        rule_details: finding
+            .rule_details
+            .iter()
+            .filter(|d| d.rule == rule)
+            .cloned()
+            .collect(),
+        orphaned: false,
+        key_path: None,
+        also_honored_by: Vec::new(),
+        policy_violation: Some(PolicyViolation {
+            kind,
+            rule: rule.to_string(),
+            quota,
+        }),
+        ..finding.clone()
+    }
+}
+
+fn matches_any(patterns: &[String], rule: &str) -> bool {
+    patterns.iter().any(|p| matches(p, rule))
+}
+
+/// Match a rule name against a pattern where `*` matches any characters.
+fn matches(pattern: &str, rule: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == rule;
+    };
+    let Some(mut remaining) = rule.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or("");
+    for part in parts {
+        match remaining.find(part) {
+            Some(i) => remaining = &remaining[i + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blame::Blame;
+    use crate::scope::Scope;
+
+    fn finding(justification: Option<&str>) -> Finding {
+        Finding {
+            path: "a.js".into(),
+            line_number: 1,
+            line_text: "// eslint-disable-next-line no-foo".into(),
+            scout_name: "eslint".into(),
+            linter: "eslint".into(),
+            rule_id: "eslint-disable-next-line".into(),
+            rule_description: "ESLint disable next line".into(),
+            scope: Scope::NextLine,
+            suppressed_rules: Some(vec!["no-foo".into()]),
+            justification: justification.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn policy() -> JustificationPolicy {
+        JustificationPolicy::new(&JustificationConfig {
+            required: true,
+            min_length: 8,
+            forbidden_phrases: vec!["temp".into(), "fix later".into()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn missing_and_empty() {
+        let p = policy();
+        assert_eq!(
+            p.check(&finding(None)).as_deref(),
+            Some("missing justification")
+        );
+        assert_eq!(
+            p.check(&finding(Some(""))).as_deref(),
+            Some("empty justification")
+        );
+    }
+
+    #[test]
+    fn too_short() {
+        assert!(policy().check(&finding(Some("legacy"))).is_some());
+    }
+
+    #[test]
+    fn forbidden_phrases_match_whole_words() {
+        let p = policy();
+        assert!(p.check(&finding(Some("TEMP workaround here"))).is_some());
+        assert!(p.check(&finding(Some("will Fix Later, promise"))).is_some());
+        assert!(p.check(&finding(Some("attempted upstream fix"))).is_none());
+    }
+
+    #[test]
+    fn age_policies_use_blame_date() {
+        let now = 1_700_000_000;
+        let blamed = |days_ago: i64, justification: Option<&str>| {
+            let mut f = finding(justification);
+            f.blame = Some(Blame {
+                commit: "0".repeat(40),
+                author: "Ada".into(),
+                author_mail: "ada@example.com".into(),
+                author_time: now - days_ago * SECONDS_PER_DAY,
+                date: String::new(),
+            });
+            f
+        };
+        let age = AgePolicy::new(&[
+            AgePolicyConfig {
+                name: "new-unjustified".into(),
+                younger_than_days: Some(7),
+                older_than_days: None,
+                unjustified: true,
+                action: AgeAction::Fail,
+            },
+            AgePolicyConfig {
+                name: "stale".into(),
+                younger_than_days: None,
+                older_than_days: Some(730),
+                unjustified: false,
+                action: AgeAction::Report,
+            },
+        ]);
+        let p = policy();
+        let names = |f: &Finding| -> Vec<String> {
+            age.check(f, &p, now).into_iter().map(|m| m.name).collect()
+        };
+
+        assert_eq!(names(&blamed(2, None)), vec!["new-unjustified"]);
+        assert!(names(&blamed(2, Some("third-party callback signature"))).is_empty());
+        assert!(names(&blamed(30, None)).is_empty());
+        assert_eq!(names(&blamed(1000, None)), vec!["stale"]);
+        assert!(names(&finding(None)).is_empty());
+
+        let mut result = ScanResult {
+            findings: vec![blamed(2, None), blamed(1000, None)],
+            ..Default::default()
+        };
+        age.apply(&mut result, &p, now);
+        assert_eq!(result.stats.age_failures, 1);
+    }
+
+    #[test]
+    fn apply_counts_unjustified() {
+        let mut result = ScanResult {
+            findings: vec![
+                finding(None),
+                finding(Some("third-party callback signature")),
+            ],
+            ..Default::default()
+        };
+        policy().apply(&mut result);
+        assert_eq!(result.stats.unjustified_count, 1);
+        assert!(result.findings[1].justification_issue.is_none());
+    }
+
+    fn eslint(line_number: usize, rules: &[&str]) -> Finding {
+        Finding {
+            line_number,
+            suppressed_rules: Some(rules.iter().map(|r| r.to_string()).collect()),
+            fingerprint: format!("{line_number:016x}"),
+            ..finding(None)
+        }
+    }
+
+    #[test]
+    fn rule_patterns_support_wildcards() {
+        assert!(matches("no-eval", "no-eval"));
+        assert!(!matches("no-eval", "no-eval-ish"));
+        assert!(matches("security/*", "security/detect-eval"));
+        assert!(!matches("security/*", "react/no-danger"));
+        assert!(matches("*-any", "@typescript-eslint/no-explicit-any"));
+        assert!(matches("a*b*c", "axxbyyc"));
+        assert!(!matches("a*b*c", "axxcyyb"));
+        assert!(!matches("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn rule_policy_denies_allows_and_enforces_quotas() {
+        let config: crate::config::Config = serde_yaml::from_str(
+            r#"
+policy:
+  eslint:
+    deny: [no-eval, "security/*"]
+    allow: [no-console]
+    quota: { no-any: 1 }
+  custom:
+    quota: { no-any: 1 }
+"#,
+        )
+        .unwrap();
+        let mut result = ScanResult {
+            findings: vec![
+                eslint(1, &["no-console"]),
+                eslint(2, &["no-console", "security/detect-eval"]),
+                eslint(3, &["no-any"]),
+                eslint(4, &["no-any"]),
+                finding(None),
+                // Another scout for the linter, under a policy of its own
+                Finding {
+                    scout_name: "custom".into(),
+                    ..eslint(6, &["no-any"])
+                },
+                // Blanket directives suppress the denied rules too
+                Finding {
+                    suppressed_rules: None,
+                    blanket: true,
+                    ..eslint(7, &[])
+                },
+            ],
+            ..Default::default()
+        };
+        let policy = RulePolicy::new(&config.policy);
+        policy.apply(&mut result);
+
+        let summary: Vec<(usize, &str)> = result
+            .findings
+            .iter()
+            .map(|f| (f.line_number, f.rule_id.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (2, "eslint-disable-next-line"),
+                (2, "policy-deny"),
+                (3, "eslint-disable-next-line"),
+                (4, "eslint-disable-next-line"),
+                (4, "policy-quota"),
+                (1, "eslint-disable-next-line"),
+                (6, "eslint-disable-next-line"),
+                (7, "eslint-disable-next-line"),
+                (7, "policy-deny"),
+                (7, "policy-deny"),
+            ]
+        );
+        let blanket: Vec<&str> = result.findings[8..]
+            .iter()
+            .map(|f| f.policy_violation.as_ref().unwrap().rule.as_str())
+            .collect();
+        assert_eq!(blanket, ["no-eval", "security/*"]);
+        let denied = result.findings[1].policy_violation.as_ref().unwrap();
+        assert_eq!(denied.rule, "security/detect-eval");
+        assert_eq!(
+            result.findings[4].policy_violation.as_ref().unwrap().quota,
+            Some(1)
+        );
+        assert_ne!(
+            result.findings[1].fingerprint,
+            result.findings[0].fingerprint
+        );
+        assert_eq!(result.stats.policy_violations, 4);
+        // Violations count once, under `policy_violations`
+        assert_eq!(result.stats.findings_count, 6);
+
+        // Applying again keeps the result stable
+        policy.apply(&mut result);
+        assert_eq!(result.stats.policy_violations, 4);
+    }
+}
@@ -0,0 +1,355 @@
+//! Lowering configured thresholds to the counts a scan now beats, editing the
+//! config file in place so that comments and formatting survive.
+
+use regex::Regex;
+
+use crate::config::Settings;
+use crate::error::{LintScoutError, Result};
+use crate::scanner::ScanResult;
+use crate::subtree::Usage;
+
+/// A configured threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Threshold {
+    Pass,
+    Blanket,
+    Density,
+    /// The budget at this index of `settings.budgets`.
+    Budget(usize),
+}
+
+/// A threshold with the count it limits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub threshold: Threshold,
+    pub name: String,
+    pub limit: f64,
+    pub current: f64,
+}
+
+impl Check {
+    pub fn regressed(&self) -> bool {
+        self.current > self.limit
+    }
+
+    pub fn improved(&self) -> bool {
+        self.current < self.limit
+    }
+}
+
+/// The configured thresholds checked against a scan.
+#[derive(Debug, Clone, Default)]
+pub struct Ratchet {
+    pub checks: Vec<Check>,
+}
+
+impl Ratchet {
+    /// Check every threshold set in `settings` against `result`, whose
+    /// budgets must already be applied. The root thresholds are checked
+    /// against `usage`, the findings outside nested configs with thresholds
+    /// of their own, as a plain run checks them; the thresholds of nested
+    /// configs are not ratcheted.
+    pub fn new(settings: &Settings, result: &ScanResult, usage: &Usage) -> Self {
+        let mut checks = Vec::new();
+        let mut check = |threshold, name: String, limit: f64, current: f64| {
+            checks.push(Check {
+                threshold,
+                name,
+                limit,
+                current,
+            })
+        };
+        if let Some(limit) = settings.pass_threshold {
+            check(
+                Threshold::Pass,
+                "pass_threshold".into(),
+                limit as f64,
+                usage.findings as f64,
+            );
+        }
+        if let Some(limit) = settings.blanket_threshold {
+            check(
+                Threshold::Blanket,
+                "blanket_threshold".into(),
+                limit as f64,
+                usage.blanket as f64,
+            );
+        }
+        if let (Some(limit), Some(per_kloc)) = (settings.density_threshold, usage.per_kloc) {
+            check(
+                Threshold::Density,
+                "density_threshold".into(),
+                limit,
+                per_kloc,
+            );
+        }
+        for (i, usage) in result.budgets.iter().enumerate() {
+            let name = match usage.scouts {
+                Some(ref scouts) => format!("budget {} [{}]", usage.path, scouts.join(", ")),
+                None => format!("budget {}", usage.path),
+            };
+            check(
+                Threshold::Budget(i),
+                name,
+                usage.max as f64,
+                usage.used as f64,
+            );
+        }
+        Ratchet { checks }
+    }
+
+    /// Whether any count went above its threshold.
+    pub fn regressed(&self) -> bool {
+        self.checks.iter().any(Check::regressed)
+    }
+
+    pub fn improved(&self) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(|c| c.improved())
+    }
+
+    /// Lower the improved thresholds in `yaml`, the text of the config file,
+    /// leaving every other line untouched.
+    pub fn rewrite(&self, yaml: &str) -> Result<String> {
+        let mut lines: Vec<String> = yaml.lines().map(String::from).collect();
+        for check in self.improved() {
+            let value = check.current.to_string();
+            let edited = match check.threshold {
+                Threshold::Pass => set_setting(&mut lines, "pass_threshold", &value),
+                Threshold::Blanket => set_setting(&mut lines, "blanket_threshold", &value),
+                Threshold::Density => set_setting(&mut lines, "density_threshold", &value),
+                Threshold::Budget(i) => set_budget(&mut lines, i, &value),
+            };
+            if !edited {
+                return Err(LintScoutError::Config(format!(
+                    "cannot find {} in the config file to lower it",
+                    check.name
+                )));
+            }
+        }
+        let mut text = lines.join("\n");
+        if yaml.ends_with('\n') {
+            text.push('\n');
+        }
+        Ok(text)
+    }
+
+    /// One line per threshold with its count; `written` tells whether
+    /// improvements were saved or only reported.
+    pub fn format(&self, written: bool) -> String {
+        let mut out = String::new();
+        for check in &self.checks {
+            let status = if check.regressed() {
+                "exceeded"
+            } else if check.improved() && written {
+                "lowered"
+            } else if check.improved() {
+                "can be lowered"
+            } else {
+                "unchanged"
+            };
+            out.push_str(&format!(
+                "{}: {} -> {} ({status})\n",
+                check.name, check.limit, check.current
+            ));
+        }
+        out
+    }
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Whether the line holds YAML rather than being blank or a comment.
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+fn is_item(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed == "-" || trimmed.starts_with("- ")
+}
+
+fn is_key(line: &str, key: &str) -> bool {
+    line.trim_start()
+        .strip_prefix(key)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+}
+
+/// The line of the mapping key `key` among the lines `start..end` at their
+/// outermost indentation, and the end of the lines nested under it.
+fn find_key(lines: &[String], start: usize, end: usize, key: &str) -> Option<(usize, usize)> {
+    let depth = indent(lines[start..end].iter().find(|l| is_content(l))?);
+    let line = (start..end).find(|&i| indent(&lines[i]) == depth && is_key(&lines[i], key))?;
+    let block_end = (line + 1..end)
+        .find(|&i| {
+            let l = &lines[i];
+            // Sequence items may sit at the same indentation as their key
+            is_content(l) && (indent(l) < depth || (indent(l) == depth && !is_item(l)))
+        })
+        .unwrap_or(end);
+    Some((line, block_end))
+}
+
+/// Replace the scalar value of `key` on `line`, keeping any comment.
+fn replace_value(line: &mut String, key: &str, value: &str) -> bool {
+    let pattern = format!(r"((?:^|[\s{{,-]){}:[ \t]*)[^\s,}}#]+", regex::escape(key));
+    let re = Regex::new(&pattern).expect("valid value pattern");
+    if !re.is_match(line) {
+        return false;
+    }
+    *line = re.replacen(line, 1, format!("${{1}}{value}")).into_owned();
+    true
+}
+
+fn set_setting(lines: &mut [String], key: &str, value: &str) -> bool {
+    let Some((settings, end)) = find_key(lines, 0, lines.len(), "settings") else {
+        return false;
+    };
+    if settings + 1 >= end {
+        return false;
+    }
+    match find_key(lines, settings + 1, end, key) {
+        Some((line, _)) => replace_value(&mut lines[line], key, value),
+        None => false,
+    }
+}
+
+fn set_budget(lines: &mut [String], index: usize, value: &str) -> bool {
+    let Some((settings, end)) = find_key(lines, 0, lines.len(), "settings") else {
+        return false;
+    };
+    if settings + 1 >= end {
+        return false;
+    }
+    let Some((budgets, end)) = find_key(lines, settings + 1, end, "budgets") else {
+        return false;
+    };
+    let items: Vec<usize> = (budgets + 1..end)
+        .filter(|&i| is_content(&lines[i]))
+        .collect();
+    let Some(depth) = items.first().map(|&i| indent(&lines[i])) else {
+        return false;
+    };
+    let items: Vec<usize> = items
+        .into_iter()
+        .filter(|&i| indent(&lines[i]) == depth && is_item(&lines[i]))
+        .collect();
+    let Some(&start) = items.get(index) else {
+        return false;
+    };
+    let item_end = items.get(index + 1).copied().unwrap_or(end);
+    (start..item_end).any(|i| replace_value(&mut lines[i], "max", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::BudgetUsage;
+    use crate::stats::ScanStats;
+
+    fn check(threshold: Threshold, limit: f64, current: f64) -> Check {
+        Check {
+            threshold,
+            name: String::new(),
+            limit,
+            current,
+        }
+    }
+
+    #[test]
+    fn checks_configured_thresholds() {
+        let settings: Settings = serde_yaml::from_str(
+            "pass_threshold: 10\nbudgets:\n  - path: legacy/\n    max: 4\n  - scouts: [eslint]\n    max: 1\n",
+        )
+        .unwrap();
+        let usage = |path: &str, scouts: Option<&str>, max, used| BudgetUsage {
+            path: path.into(),
+            scouts: scouts.map(|s| vec![s.to_string()]),
+            max,
+            used,
+            exceeded: used > max,
+        };
+        let result = ScanResult {
+            stats: ScanStats {
+                findings_count: 12,
+                ..ScanStats::default()
+            },
+            budgets: vec![
+                usage("legacy/", None, 4, 4),
+                usage("*", Some("eslint"), 1, 2),
+            ],
+            ..Default::default()
+        };
+        let usage = Usage {
+            findings: 7,
+            blanket: 3,
+            per_kloc: None,
+        };
+        let ratchet = Ratchet::new(&settings, &result, &usage);
+
+        let names: Vec<&str> = ratchet.checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["pass_threshold", "budget legacy/", "budget * [eslint]"]
+        );
+        assert!(ratchet.regressed());
+        assert_eq!(ratchet.improved().count(), 1);
+        assert_eq!(
+            ratchet.format(true),
+            "pass_threshold: 10 -> 7 (lowered)\nbudget legacy/: 4 -> 4 (unchanged)\nbudget * [eslint]: 1 -> 2 (exceeded)\n"
+        );
+    }
+
+    #[test]
+    fn rewrites_only_lowered_values() {
+        let yaml = "\
+# Suppression limits
+settings:
+  exclude: [vendor]
+  pass_threshold: 12   # lowered by ratchet
+  blanket_threshold: 3
+
+  density_threshold: 2.5
+  budgets:
+  # legacy code
+  - path: legacy/
+    max: 40
+  - { path: \"*.ts\", scouts: [eslint], max: 9 }
+  - scouts:
+      - mypy
+    max: 5
+policy:
+  eslint:
+    quota:
+      pass_threshold: 3
+";
+        let ratchet = Ratchet {
+            checks: vec![
+                check(Threshold::Pass, 12.0, 9.0),
+                check(Threshold::Blanket, 3.0, 3.0),
+                check(Threshold::Density, 2.5, 1.25),
+                check(Threshold::Budget(1), 9.0, 0.0),
+                check(Threshold::Budget(2), 5.0, 2.0),
+            ],
+        };
+        let expected = yaml
+            .replace("pass_threshold: 12 ", "pass_threshold: 9 ")
+            .replace("density_threshold: 2.5", "density_threshold: 1.25")
+            .replace("max: 9 }", "max: 0 }")
+            .replace("max: 5", "max: 2");
+        assert_eq!(ratchet.rewrite(yaml).unwrap(), expected);
+    }
+
+    #[test]
+    fn missing_threshold_is_an_error() {
+        let ratchet = Ratchet {
+            checks: vec![check(Threshold::Budget(1), 2.0, 1.0)],
+        };
+        let yaml = "settings:\n  budgets:\n    - path: a/\n      max: 2\n";
+        assert!(ratchet.rewrite(yaml).is_err());
+        assert!(ratchet.rewrite("pass_threshold: 3\n").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+use crate::rule;
+
+/// The lines covered by a block directive such as `eslint-disable` ...
+/// `eslint-enable`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuppressedRegion {
+    pub start_line: usize,
+    /// Line of the closing directive, or the last line of the file when the
+    /// region is never closed.
+    pub end_line: usize,
+    /// Number of lines between the opener and the closer (or end of file).
+    pub lines: usize,
+    pub terminated: bool,
+}
+
+struct OpenRegion {
+    key: usize,
+    group: String,
+    start_line: usize,
+    /// Rules still suppressed; `None` for a blanket opener.
+    rules: Option<Vec<String>>,
+}
+
+/// Pairs region openers with closers within a single file.
+///
+/// A closer with no rule list (or `all`) closes every open region of its
+/// group. A closer naming rules removes them from targeted regions, closing a
+/// region once none of its rules remain suppressed; blanket regions stay open
+/// until a blanket closer.
+#[derive(Default)]
+pub struct RegionTracker {
+    open: Vec<OpenRegion>,
+}
+
+impl RegionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a region identified by `key` (e.g. a finding index).
+    pub fn open(&mut self, key: usize, group: &str, start_line: usize, rules: Option<Vec<String>>) {
+        self.open.push(OpenRegion {
+            key,
+            group: group.to_string(),
+            start_line,
+            rules,
+        });
+    }
+
+    /// Apply a closer, returning the keys and regions it ended.
+    pub fn close(
+        &mut self,
+        group: &str,
+        line: usize,
+        rules: Option<&[String]>,
+    ) -> Vec<(usize, SuppressedRegion)> {
+        let closes_all = match rules {
+            None => true,
+            Some(r) => r.iter().any(|r| rule::is_catch_all(r)),
+        };
+        let mut closed = Vec::new();
+        self.open.retain_mut(|region| {
+            if region.group != group {
+                return true;
+            }
+            let done = if closes_all {
+                true
+            } else {
+                match (&mut region.rules, rules) {
+                    (Some(open_rules), Some(closing)) => {
+                        open_rules.retain(|r| !closing.contains(r));
+                        open_rules.is_empty()
+                    }
+                    _ => false,
+                }
+            };
+            if done {
+                closed.push((
+                    region.key,
+                    SuppressedRegion {
+                        start_line: region.start_line,
+                        end_line: line,
+                        lines: line.saturating_sub(region.start_line + 1),
+                        terminated: true,
+                    },
+                ));
+            }
+            !done
+        });
+        closed
+    }
+
+    /// End every region still open at the end of a file of `total_lines`.
+    pub fn finish(self, total_lines: usize) -> Vec<(usize, SuppressedRegion)> {
+        self.open
+            .into_iter()
+            .map(|region| {
+                (
+                    region.key,
+                    SuppressedRegion {
+                        start_line: region.start_line,
+                        end_line: total_lines,
+                        lines: total_lines.saturating_sub(region.start_line),
+                        terminated: false,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(names: &[&str]) -> Option<Vec<String>> {
+        Some(names.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn blanket_closer_ends_region() {
+        let mut t = RegionTracker::new();
+        t.open(0, "eslint", 3, None);
+        let closed = t.close("eslint", 8, None);
+        assert_eq!(closed.len(), 1);
+        assert_eq!(
+            closed[0].1,
+            SuppressedRegion {
+                start_line: 3,
+                end_line: 8,
+                lines: 4,
+                terminated: true,
+            }
+        );
+        assert!(t.finish(10).is_empty());
+    }
+
+    #[test]
+    fn targeted_closer_only_ends_matching_rules() {
+        let mut t = RegionTracker::new();
+        t.open(0, "eslint", 1, rules(&["no-console", "no-alert"]));
+        t.open(1, "eslint", 2, rules(&["no-eval"]));
+        assert!(t
+            .close("eslint", 5, rules(&["no-console"]).as_deref())
+            .is_empty());
+        let closed = t.close("eslint", 6, rules(&["no-alert"]).as_deref());
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].0, 0);
+        let rest = t.finish(9);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].0, 1);
+        assert!(!rest[0].1.terminated);
+        assert_eq!(rest[0].1.lines, 7);
+    }
+
+    #[test]
+    fn targeted_closer_leaves_blanket_region_open() {
+        let mut t = RegionTracker::new();
+        t.open(0, "rubocop", 1, None);
+        assert!(t
+            .close("rubocop", 4, rules(&["Style/Foo"]).as_deref())
+            .is_empty());
+        assert_eq!(t.close("rubocop", 5, rules(&["all"]).as_deref()).len(), 1);
+    }
+
+    #[test]
+    fn catch_all_closer_ignores_case() {
+        let mut t = RegionTracker::new();
+        t.open(0, "rubocop", 1, rules(&["Style/Foo"]));
+        assert_eq!(t.close("rubocop", 4, rules(&["All"]).as_deref()).len(), 1);
+    }
+
+    #[test]
+    fn groups_are_independent() {
+        let mut t = RegionTracker::new();
+        t.open(0, "eslint", 1, None);
+        assert!(t.close("oxlint", 2, None).is_empty());
+        assert_eq!(t.finish(3).len(), 1);
+    }
+}
@@ -1,6 +1,49 @@
 use regex::Regex;
+use serde::Deserialize;
 
 use crate::error::{LintScoutError, Result};
+use crate::scope::Scope;
+use crate::syntax::{Span, TokenKind};
+
+/// Where a rule's pattern must start for it to count as a match.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Placement {
+    /// Inside a comment, e.g. `// eslint-disable-line`.
+    #[default]
+    Comment,
+    /// In code outside strings and comments, e.g. attributes and annotations.
+    Code,
+}
+
+/// Whether a directive opens or closes a suppressed region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionRole {
+    Open,
+    Close,
+}
+
+/// Pairs opener and closer rules of the same group, e.g. `eslint-disable` and
+/// `eslint-enable`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionMarker {
+    pub role: RegionRole,
+    pub group: String,
+    /// An opener that follows code on the same line only covers that line,
+    /// as with RuboCop's trailing `# rubocop:disable`.
+    pub inline_if_trailing: bool,
+}
+
+/// Suppressed rule names that stand for every rule, e.g. `clippy::all` or
+/// `@SuppressWarnings("all")`.
+const CATCH_ALL_RULES: &[&str] = &["all"];
+
+/// Whether a suppressed rule name stands for every rule, in any case.
+pub fn is_catch_all(rule: &str) -> bool {
+    CATCH_ALL_RULES
+        .iter()
+        .any(|all| rule.eq_ignore_ascii_case(all))
+}
 
 #[derive(Debug, Clone)]
 pub struct Rule {
@@ -10,6 +53,16 @@ pub struct Rule {
     pub pattern_str: String,
     pub capture_pattern: Option<Regex>,
     pub capture_pattern_str: Option<String>,
+    /// Extracts the explanation attached to a directive (group 1), e.g. the
+    /// text after `--` in `// eslint-disable-line no-foo -- reason`.
+    pub justification_pattern: Option<Regex>,
+    pub justification_pattern_str: Option<String>,
+    pub placement: Placement,
+    pub region: Option<RegionMarker>,
+    pub scope: Scope,
+    /// Scope to use instead when the directive is alone on its line, e.g. a
+    /// standalone `//nolint` covers the following declaration.
+    pub standalone_scope: Option<Scope>,
 }
 
 impl Rule {
@@ -30,6 +83,12 @@ impl Rule {
             pattern_str,
             capture_pattern: None,
             capture_pattern_str: None,
+            justification_pattern: None,
+            justification_pattern_str: None,
+            placement: Placement::Comment,
+            region: None,
+            scope: Scope::SameLine,
+            standalone_scope: None,
         })
     }
 
@@ -43,16 +102,133 @@ impl Rule {
         Ok(self)
     }
 
+    pub fn with_justification(mut self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| LintScoutError::InvalidPattern {
+            pattern: pattern.to_string(),
+            source: e,
+        })?;
+        self.justification_pattern = Some(regex);
+        self.justification_pattern_str = Some(pattern.to_string());
+        Ok(self)
+    }
+
+    /// Require the pattern to start in code rather than in a comment.
+    pub fn in_code(mut self) -> Self {
+        self.placement = Placement::Code;
+        self
+    }
+
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn with_standalone_scope(mut self, scope: Scope) -> Self {
+        self.standalone_scope = Some(scope);
+        self
+    }
+
+    /// Mark this rule as opening a suppressed region of `group`.
+    pub fn opens_region(mut self, group: impl Into<String>) -> Self {
+        self.scope = Scope::Region;
+        self.region = Some(RegionMarker {
+            role: RegionRole::Open,
+            group: group.into(),
+            inline_if_trailing: false,
+        });
+        self
+    }
+
+    /// Mark this rule as closing a suppressed region of `group`.
+    pub fn closes_region(mut self, group: impl Into<String>) -> Self {
+        self.region = Some(RegionMarker {
+            role: RegionRole::Close,
+            group: group.into(),
+            inline_if_trailing: false,
+        });
+        self
+    }
+
+    /// Treat an opener that follows code on the same line as a single-line
+    /// suppression instead of a region.
+    pub fn inline_if_trailing(mut self) -> Self {
+        if let Some(ref mut marker) = self.region {
+            marker.inline_if_trailing = true;
+        }
+        self
+    }
+
+    /// The scope of a match, given whether it follows code on its line.
+    pub fn scope_for(&self, trailing: bool) -> Scope {
+        if let Some(ref marker) = self.region {
+            if marker.role == RegionRole::Open && marker.inline_if_trailing && trailing {
+                return Scope::SameLine;
+            }
+        }
+        match self.standalone_scope {
+            Some(standalone) if !trailing => standalone,
+            _ => self.scope,
+        }
+    }
+
     pub fn is_match(&self, line: &str) -> bool {
         self.pattern.is_match(line)
     }
 
+    /// Find the first match that starts in a span allowed by this rule's
+    /// placement, returning its byte offset in `line`.
+    pub fn find_in_spans(&self, line: &str, spans: &[Span]) -> Option<usize> {
+        let wanted = match self.placement {
+            Placement::Comment => TokenKind::Comment,
+            Placement::Code => TokenKind::Code,
+        };
+        spans.iter().filter(|s| s.kind == wanted).find_map(|span| {
+            self.pattern
+                .find_at(line, span.start)
+                .filter(|m| m.start() < span.end)
+                .map(|m| m.start())
+        })
+    }
+
+    /// Whether a match suppresses every rule: the directive can name specific
+    /// rules but did not, or it named a catch-all such as `all`.
+    pub fn is_blanket(&self, suppressed_rules: Option<&[String]>) -> bool {
+        match suppressed_rules {
+            Some(rules) => rules.iter().any(|r| is_catch_all(r)),
+            None => self.capture_pattern.is_some(),
+        }
+    }
+
+    /// Extract the justification text, with trailing comment closers removed.
+    /// Returns `Some("")` when the separator is present but the reason is
+    /// empty.
+    pub fn capture_justification(&self, line: &str) -> Option<String> {
+        let re = self.justification_pattern.as_ref()?;
+        let reason = re.captures(line)?.get(1)?.as_str().trim();
+        let reason = reason
+            .trim_end_matches("*/")
+            .trim_end_matches("-->")
+            .trim_end();
+        Some(reason.to_string())
+    }
+
     pub fn capture_suppressed_rules(&self, line: &str) -> Option<Vec<String>> {
         let cap_re = self.capture_pattern.as_ref()?;
         let caps = cap_re.captures(line)?;
         let group = caps.get(1)?;
-        let rules: Vec<String> = group
-            .as_str()
+        // Stop the rule list where a justification starts inside it, which
+        // may be right where the list would start, as in `disable -- reason`
+        let mut end = group.end();
+        if let Some(m) = self
+            .justification_pattern
+            .as_ref()
+            .and_then(|re| re.find_at(line, group.start()))
+        {
+            if m.start() < end {
+                end = m.start();
+            }
+        }
+        let rules: Vec<String> = line[group.start()..end]
             .split([',', ' '])
             .map(|s| s.trim())
             .filter(|s| !s.is_empty())
@@ -69,6 +245,7 @@ impl Rule {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::syntax::CommentSyntax;
 
     #[test]
     fn valid_pattern() {
@@ -115,6 +292,106 @@ mod tests {
         assert!(rule.capture_suppressed_rules("// eslint-disable").is_none());
     }
 
+    #[test]
+    fn find_in_spans_respects_placement() {
+        let line = r##"let s = "#[allow(x)]"; // #[allow(y)]"##;
+        let spans = &CommentSyntax::RUST.tokenize(line)[0];
+        let rule = Rule::new("t", "t", r"#\[allow").unwrap();
+        assert_eq!(rule.find_in_spans(line, spans), Some(26));
+
+        let rule = rule.in_code();
+        assert_eq!(rule.find_in_spans(line, spans), None);
+        let line = "#[allow(x)]";
+        let spans = &CommentSyntax::RUST.tokenize(line)[0];
+        assert_eq!(rule.find_in_spans(line, spans), Some(0));
+    }
+
+    #[test]
+    fn blanket_detection() {
+        let rule = Rule::new("noqa", "noqa", r"#\s*noqa")
+            .unwrap()
+            .with_capture(r"#\s*noqa:\s*(.+)")
+            .unwrap();
+        let bare = rule.capture_suppressed_rules("# noqa");
+        assert!(rule.is_blanket(bare.as_deref()));
+        let targeted = rule.capture_suppressed_rules("# noqa: E501");
+        assert!(!rule.is_blanket(targeted.as_deref()));
+        let all = rule.capture_suppressed_rules("# noqa: ALL");
+        assert!(rule.is_blanket(all.as_deref()));
+
+        // Directives that cannot name rules are not blanket
+        let rule = Rule::new("nopmd", "nopmd", r"NOPMD").unwrap();
+        assert!(!rule.is_blanket(None));
+    }
+
+    #[test]
+    fn justification_is_extracted_and_excluded_from_rules() {
+        let rule = Rule::new("t", "t", r"eslint-disable-next-line")
+            .unwrap()
+            .with_capture(r"eslint-disable-next-line\s+([^*]+)")
+            .unwrap()
+            .with_justification(r"--\s*(.*)")
+            .unwrap();
+        let line = "/* eslint-disable-next-line no-foo, no-bar -- legacy API */";
+        assert_eq!(
+            rule.capture_suppressed_rules(line),
+            Some(vec!["no-foo".into(), "no-bar".into()])
+        );
+        assert_eq!(rule.capture_justification(line), Some("legacy API".into()));
+        assert_eq!(
+            rule.capture_justification("// eslint-disable-next-line no-foo --"),
+            Some(String::new())
+        );
+        assert!(rule
+            .capture_justification("// eslint-disable-next-line no-foo")
+            .is_none());
+    }
+
+    #[test]
+    fn bare_directive_with_justification_stays_blanket() {
+        let rule = Rule::new("t", "t", r"eslint-disable-next-line")
+            .unwrap()
+            .with_capture(r"eslint-disable-next-line\s+([^*]+)")
+            .unwrap()
+            .with_justification(r"--\s*(.*)")
+            .unwrap();
+        let line = "// eslint-disable-next-line -- legacy reason";
+        let rules = rule.capture_suppressed_rules(line);
+        assert_eq!(rules, None);
+        assert!(rule.is_blanket(rules.as_deref()));
+        assert_eq!(
+            rule.capture_justification(line),
+            Some("legacy reason".into())
+        );
+
+        let noqa = Rule::new("noqa", "noqa", r"#\s*noqa")
+            .unwrap()
+            .with_capture(r"#\s*noqa:\s*(.+)")
+            .unwrap()
+            .with_justification(r"\s#\s*(.*)")
+            .unwrap();
+        assert_eq!(
+            noqa.capture_suppressed_rules("import os  # noqa: F401 # re-exported"),
+            Some(vec!["F401".into()])
+        );
+    }
+
+    #[test]
+    fn scope_depends_on_trailing() {
+        let nolint = Rule::new("t", "t", r"//nolint")
+            .unwrap()
+            .with_standalone_scope(Scope::Declaration);
+        assert_eq!(nolint.scope_for(true), Scope::SameLine);
+        assert_eq!(nolint.scope_for(false), Scope::Declaration);
+
+        let disable = Rule::new("t", "t", r"rubocop:disable")
+            .unwrap()
+            .opens_region("rubocop")
+            .inline_if_trailing();
+        assert_eq!(disable.scope_for(true), Scope::SameLine);
+        assert_eq!(disable.scope_for(false), Scope::Region);
+    }
+
     #[test]
     fn invalid_capture_pattern() {
         let result = Rule::new("test", "test", r"foo")
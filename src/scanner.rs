@@ -1,30 +1,153 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
 
-use ignore::overrides::OverrideBuilder;
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::WalkBuilder;
 use rayon::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::acknowledge;
+use crate::baseline::BaselineSummary;
+use crate::budget::BudgetUsage;
+use crate::catalog::Catalog;
+use crate::config::AgeAction;
+use crate::density::{top_level_directory, Density, FileLines, LineCounts};
+use crate::diff::DiffSummary;
 use crate::error::Result;
+use crate::exemption::{self, Exemption, ExemptionRule, LintedFile};
 use crate::finding::Finding;
+use crate::fingerprint;
+use crate::linter_config::{self, ConfigSuppression};
+use crate::orphan::{self, ConfiguredLinters};
+use crate::region::RegionTracker;
+use crate::rule::RegionRole;
+use crate::scope::Scope;
 use crate::scout::Scout;
 use crate::stats::ScanStats;
+use crate::subtree::{Layer, SubtreeUsage, Subtrees};
+use crate::syntax::{CommentSyntax, Span, TokenKind};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ScanResult {
     pub findings: Vec<Finding>,
     pub stats: ScanStats,
+    /// How findings compared to the baseline, when one was applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<BaselineSummary>,
+    /// Suppressions added and removed, when reporting against a diff.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<DiffSummary>,
+    /// Suppressions per 1000 lines, for scans that counted lines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub density: Option<Density>,
+    /// Usage of the configured per-path budgets.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub budgets: Vec<BudgetUsage>,
+    /// Scanned files that ignore files and per-file mappings exempt from a
+    /// linter or from some of its rules.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exemptions: Vec<Exemption>,
+    /// Nested configs setting thresholds, and the findings under them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtrees: Vec<SubtreeUsage>,
+    /// Findings approved by a `lintscout-ignore` comment, left out of
+    /// `findings` and the counts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acknowledged: Vec<Finding>,
     #[serde(skip)]
     pub errors: Vec<String>,
+    /// Linters configured under the scanned path, from the config files
+    /// the scan walked past.
+    #[serde(skip)]
+    pub configured: ConfiguredLinters,
+    /// Non-blank lines of the scanned files, from which `density` is kept
+    /// up to date as findings are filtered.
+    #[serde(skip)]
+    pub lines: LineCounts,
+}
+
+impl ScanResult {
+    /// Keep only findings matching `keep`, updating the finding counts.
+    pub fn retain(&mut self, keep: impl FnMut(&Finding) -> bool) {
+        self.findings.retain(keep);
+        self.recount();
+    }
+
+    /// Recompute the finding counts in `stats` from `findings`.
+    pub fn recount(&mut self) {
+        self.stats.findings_count = self
+            .findings
+            .iter()
+            .filter(|f| f.policy_violation.is_none())
+            .count() as u64;
+        self.stats.blanket_count = self.findings.iter().filter(|f| f.blanket).count() as u64;
+        self.stats.unjustified_count = self
+            .findings
+            .iter()
+            .filter(|f| f.justification_issue.is_some())
+            .count() as u64;
+        self.stats.age_failures = self
+            .findings
+            .iter()
+            .filter(|f| f.age_policies.iter().any(|m| m.action == AgeAction::Fail))
+            .count() as u64;
+        self.stats.policy_violations = self
+            .findings
+            .iter()
+            .filter(|f| f.policy_violation.is_some())
+            .count() as u64;
+        self.stats.expiry_failures = self
+            .findings
+            .iter()
+            .filter(|f| f.expiry.as_ref().is_some_and(|e| e.issue.is_some()))
+            .count() as u64;
+        self.stats.acknowledged_count = self.acknowledged.len() as u64;
+        self.stats.unknown_rules = self
+            .findings
+            .iter()
+            .map(|f| f.unknown_rules().count())
+            .sum::<usize>() as u64;
+        self.stats.orphaned_count = self.findings.iter().filter(|f| f.orphaned).count() as u64;
+        self.stats.config_count = self
+            .findings
+            .iter()
+            .filter(|f| f.scope == Scope::Config)
+            .count() as u64;
+        if !self.lines.files.is_empty() {
+            self.density = Some(Density::compute(&self.lines, &self.findings));
+        }
+    }
 }
 
 struct FileResult {
     findings: Vec<Finding>,
+    acknowledged: Vec<Finding>,
     errors: Vec<String>,
     scanned: bool,
     skipped: bool,
+    /// Finding path and line counts of a scanned file.
+    lines: Option<(String, FileLines)>,
+    /// The file and its linters, when scouts apply to it.
+    linted: Option<LintedFile>,
+    /// Exemptions read from a config file.
+    exemptions: Vec<ExemptionRule>,
+}
+
+impl FileResult {
+    fn unreadable(path: &Path, error: impl std::fmt::Display) -> Self {
+        FileResult {
+            findings: Vec::new(),
+            acknowledged: Vec::new(),
+            errors: vec![format!("{}: {}", path.display(), error)],
+            scanned: false,
+            skipped: true,
+            lines: None,
+            linted: None,
+            exemptions: Vec::new(),
+        }
+    }
 }
 
 pub struct Scanner {
@@ -32,6 +155,16 @@ pub struct Scanner {
     scouts: Vec<Scout>,
     exclude_patterns: Vec<String>,
     respect_gitignore: bool,
+    /// The config file the scan runs with, which does not apply as a nested
+    /// config when it lies under the root.
+    config_file: Option<PathBuf>,
+    /// Names the custom scouts of nested configs must have, when set.
+    only_scouts: Option<Vec<String>>,
+    /// Names of scouts disabled everywhere.
+    disabled_scouts: Vec<String>,
+    /// The root's path below the directory fingerprints are relative to,
+    /// when that is not the root itself.
+    fingerprint_prefix: Option<PathBuf>,
 }
 
 impl Scanner {
@@ -41,6 +174,10 @@ impl Scanner {
             scouts,
             exclude_patterns: Vec::new(),
             respect_gitignore: true,
+            config_file: None,
+            only_scouts: None,
+            disabled_scouts: Vec::new(),
+            fingerprint_prefix: None,
         }
     }
 
@@ -54,30 +191,123 @@ impl Scanner {
         self
     }
 
+    pub fn with_config_file(mut self, path: Option<PathBuf>) -> Self {
+        self.config_file = path;
+        self
+    }
+
+    /// Select the custom scouts of nested configs like the scanner's scouts
+    /// were selected.
+    pub fn with_scout_filter(mut self, only: Option<Vec<String>>, disabled: Vec<String>) -> Self {
+        self.only_scouts = only;
+        self.disabled_scouts = disabled;
+        self
+    }
+
+    /// Fingerprint paths relative to `dir`, such as the git toplevel, rather
+    /// than to the root, so that scans of a subdirectory match a baseline
+    /// written for the whole tree. Ignored unless `dir` contains the root.
+    pub fn with_fingerprint_root(mut self, dir: Option<PathBuf>) -> Self {
+        self.fingerprint_prefix = dir.and_then(|dir| {
+            let dir = dir.canonicalize().ok()?;
+            let root = self.root_path.canonicalize().ok()?;
+            Some(root.strip_prefix(dir).ok()?.to_path_buf())
+        });
+        self
+    }
+
+    /// The path of the file at `path` that its fingerprints hash.
+    fn fingerprint_path(&self, path: &Path, relative_path: &str) -> String {
+        let Some(ref prefix) = self.fingerprint_prefix else {
+            return relative_path.to_string();
+        };
+        let path = match path.strip_prefix(&self.root_path) {
+            // The root is the file itself
+            Ok(rel) if rel.as_os_str().is_empty() => prefix.clone(),
+            Ok(rel) => prefix.join(rel),
+            Err(_) => path.to_path_buf(),
+        };
+        path.to_string_lossy().replace('\\', "/")
+    }
+
+    /// Whether any scout applies to the file at `path`, or it is the config
+    /// file of a selected scout's linter. Nested configs are not consulted.
+    pub fn applies_to_file(&self, path: &Path) -> bool {
+        self.applies(path, &self.scouts_for(&[]))
+    }
+
+    fn applies(&self, path: &Path, scouts: &[&Scout]) -> bool {
+        scouts.iter().any(|s| s.applies_to_file(path)) || !config_scouts(scouts, path).is_empty()
+    }
+
+    /// The scouts under the nested configs of `chain`: the scanner's and the
+    /// custom scouts of the configs, nearer ones replacing scouts of the same
+    /// name, less those any of the configs disable.
+    fn scouts_for<'a>(&'a self, chain: &'a [Arc<Layer>]) -> Vec<&'a Scout> {
+        let mut scouts: Vec<&Scout> = self.scouts.iter().collect();
+        for scout in chain.iter().flat_map(|l| &l.scouts) {
+            if self
+                .only_scouts
+                .as_ref()
+                .is_some_and(|names| !names.contains(&scout.name))
+                || self.disabled_scouts.contains(&scout.name)
+            {
+                continue;
+            }
+            scouts.retain(|s| s.name != scout.name);
+            scouts.push(scout);
+        }
+        scouts.retain(|s| {
+            !chain
+                .iter()
+                .any(|l| l.config.settings.disable.scouts.contains(&s.name))
+        });
+        scouts
+    }
+
+    /// The nested configs that apply to the file at `path`.
+    fn chain(&self, path: &Path, subtrees: &Subtrees) -> Vec<Arc<Layer>> {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        subtrees.chain(dir.strip_prefix(&self.root_path).unwrap_or(dir))
+    }
+
     pub fn run(&self) -> Result<ScanResult> {
         let start = Instant::now();
         let mut stats = ScanStats::default();
         let mut errors = Vec::new();
 
+        let overrides = self.overrides(&mut stats, &mut errors);
+        let subtrees = Arc::new(Subtrees::new(
+            &self.root_path,
+            self.config_file.as_deref(),
+            overrides,
+        ));
+        let filter = Arc::clone(&subtrees);
+        let root = self.root_path.clone();
+
         let mut builder = WalkBuilder::new(&self.root_path);
-        builder.git_ignore(self.respect_gitignore);
-
-        if !self.exclude_patterns.is_empty() {
-            let mut overrides = OverrideBuilder::new(&self.root_path);
-            for pattern in &self.exclude_patterns {
-                let neg = format!("!{pattern}");
-                if let Err(e) = overrides.add(&neg) {
-                    errors.push(format!("invalid exclude pattern '{pattern}': {e}"));
-                    stats.errors_count += 1;
+        builder
+            .git_ignore(self.respect_gitignore)
+            .hidden(false)
+            // Hidden files are skipped, except for linter configs such as
+            // `.eslintrc.json`, and so are entries the nearest config excludes
+            .filter_entry(move |e| {
+                if e.depth() == 0 {
+                    return true;
                 }
-            }
-            if let Ok(built) = overrides.build() {
-                builder.overrides(built);
-            }
-        }
+                let name = e.file_name().to_string_lossy();
+                let is_file = e.file_type().is_some_and(|t| t.is_file());
+                let visible = !name.starts_with('.')
+                    || (is_file
+                        && (!linter_config::linters(&name).is_empty()
+                            || orphan::is_candidate(&name)));
+                let relative = e.path().strip_prefix(&root).unwrap_or(e.path());
+                visible && !filter.excluded(relative, !is_file)
+            });
 
         // Phase 1: Sequential walk to collect file paths
         let mut file_paths: Vec<PathBuf> = Vec::new();
+        let mut configured = ConfiguredLinters::default();
         let mut skipped_no_scout = 0u64;
 
         for entry in builder.build() {
@@ -96,9 +326,10 @@ impl Scanner {
             if !path.is_file() {
                 continue;
             }
+            configured.record_file(path);
 
-            let has_scout = self.scouts.iter().any(|s| s.applies_to_file(path));
-            if !has_scout {
+            let chain = self.chain(path, &subtrees);
+            if !self.applies(path, &self.scouts_for(&chain)) {
                 skipped_no_scout += 1;
                 continue;
             }
@@ -107,15 +338,129 @@ impl Scanner {
         }
 
         stats.files_skipped += skipped_no_scout;
+        if let Some(e) = subtrees.take_error() {
+            return Err(e);
+        }
 
         // Phase 2: Parallel read + regex match
         let file_results: Vec<FileResult> = file_paths
             .par_iter()
-            .map(|path| self.process_file(path))
+            .map(|path| self.process_file(path, &subtrees))
             .collect();
 
         // Phase 3: Sequential aggregation
+        Ok(Self::aggregate(
+            file_results,
+            stats,
+            errors,
+            subtrees.thresholds(),
+            configured,
+            start,
+        ))
+    }
+
+    /// Scan file contents supplied by the caller, such as blobs read from
+    /// git, instead of walking the filesystem. Paths are relative to the scan
+    /// root; exclude patterns still apply.
+    pub fn run_files(&self, files: Vec<(PathBuf, Vec<u8>)>) -> Result<ScanResult> {
+        self.run_supplied(files, true)
+    }
+
+    /// Scan file contents supplied by the caller, such as the lines a diff
+    /// removes, with the nested configs on the filesystem. Paths are
+    /// relative to the scan root; exclude patterns still apply.
+    pub fn run_contents(&self, files: Vec<(PathBuf, Vec<u8>)>) -> Result<ScanResult> {
+        self.run_supplied(files, false)
+    }
+
+    /// Scan supplied file contents, loading nested configs from among them
+    /// when `preload` is set.
+    fn run_supplied(&self, files: Vec<(PathBuf, Vec<u8>)>, preload: bool) -> Result<ScanResult> {
+        let start = Instant::now();
+        let mut stats = ScanStats::default();
+        let mut errors = Vec::new();
+        let overrides = self.overrides(&mut stats, &mut errors);
+        let mut subtrees = Subtrees::new(&self.root_path, self.config_file.as_deref(), overrides);
+        if preload {
+            subtrees.preload(&files);
+        }
+
+        let mut selected = Vec::new();
+        let mut configured = ConfiguredLinters::default();
+        for (relative, bytes) in files {
+            // Patterns such as `vendor` match any directory on the path
+            let excluded = relative
+                .ancestors()
+                .filter(|a| !a.as_os_str().is_empty())
+                .any(|a| subtrees.excluded(a, a != relative));
+            if excluded {
+                continue;
+            }
+            stats.files_walked += 1;
+            if let Some(name) = relative.file_name().and_then(|n| n.to_str()) {
+                if orphan::is_candidate(name) {
+                    configured.record(name, &String::from_utf8_lossy(&bytes));
+                }
+            }
+            let path = self.root_path.join(&relative);
+            let chain = self.chain(&path, &subtrees);
+            if !self.applies(&path, &self.scouts_for(&chain)) {
+                stats.files_skipped += 1;
+                continue;
+            }
+            selected.push((path, bytes));
+        }
+        if let Some(e) = subtrees.take_error() {
+            return Err(e);
+        }
+
+        let file_results: Vec<FileResult> = selected
+            .into_par_iter()
+            .map(|(path, bytes)| match String::from_utf8(bytes) {
+                Ok(content) => self.process_content(&path, &content, &subtrees),
+                Err(e) => FileResult::unreadable(&path, e),
+            })
+            .collect();
+
+        Ok(Self::aggregate(
+            file_results,
+            stats,
+            errors,
+            subtrees.thresholds(),
+            configured,
+            start,
+        ))
+    }
+
+    /// Build the exclude overrides, recording invalid patterns as errors.
+    fn overrides(&self, stats: &mut ScanStats, errors: &mut Vec<String>) -> Option<Override> {
+        if self.exclude_patterns.is_empty() {
+            return None;
+        }
+        let mut overrides = OverrideBuilder::new(&self.root_path);
+        for pattern in &self.exclude_patterns {
+            let neg = format!("!{pattern}");
+            if let Err(e) = overrides.add(&neg) {
+                errors.push(format!("invalid exclude pattern '{pattern}': {e}"));
+                stats.errors_count += 1;
+            }
+        }
+        overrides.build().ok()
+    }
+
+    fn aggregate(
+        file_results: Vec<FileResult>,
+        mut stats: ScanStats,
+        mut errors: Vec<String>,
+        subtrees: Vec<SubtreeUsage>,
+        configured: ConfiguredLinters,
+        start: Instant,
+    ) -> ScanResult {
         let mut findings = Vec::new();
+        let mut acknowledged = Vec::new();
+        let mut lines = LineCounts::default();
+        let mut linted = Vec::new();
+        let mut exemption_rules = Vec::new();
         for fr in file_results {
             if fr.scanned {
                 stats.files_scanned += 1;
@@ -123,76 +468,373 @@ impl Scanner {
             if fr.skipped {
                 stats.files_skipped += 1;
             }
-            stats.findings_count += fr.findings.len() as u64;
             stats.errors_count += fr.errors.len() as u64;
             findings.extend(fr.findings);
+            acknowledged.extend(fr.acknowledged);
             errors.extend(fr.errors);
+            lines.files.extend(fr.lines);
+            linted.extend(fr.linted);
+            exemption_rules.extend(fr.exemptions);
         }
 
         // Sort for deterministic output
-        findings.sort_by(|a, b| {
-            a.path
-                .cmp(&b.path)
-                .then(a.line_number.cmp(&b.line_number))
-                .then(a.scout_name.cmp(&b.scout_name))
-                .then(a.rule_id.cmp(&b.rule_id))
-        });
+        for list in [&mut findings, &mut acknowledged] {
+            list.sort_by(|a, b| {
+                a.path
+                    .cmp(&b.path)
+                    .then(a.line_number.cmp(&b.line_number))
+                    .then(a.scout_name.cmp(&b.scout_name))
+                    .then(a.rule_id.cmp(&b.rule_id))
+            });
+        }
 
         stats.duration_ms = start.elapsed().as_millis() as u64;
 
-        Ok(ScanResult {
+        let mut result = ScanResult {
             findings,
             stats,
+            baseline: None,
+            diff: None,
+            density: None,
+            budgets: Vec::new(),
+            exemptions: exemption::resolve(&exemption_rules, &linted),
+            subtrees,
+            acknowledged,
             errors,
-        })
-    }
-
-    fn process_file(&self, path: &PathBuf) -> FileResult {
-        let content = match fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(e) => {
-                return FileResult {
-                    findings: Vec::new(),
-                    errors: vec![format!("{}: {}", path.display(), e)],
-                    scanned: false,
-                    skipped: true,
-                };
-            }
+            configured,
+            lines,
         };
+        result.recount();
+        // An empty scan still reports a density of zero
+        if result.density.is_none() {
+            result.density = Some(Density::compute(&result.lines, &result.findings));
+        }
+        result
+    }
 
-        let matching_scouts: Vec<&Scout> = self
-            .scouts
+    fn process_file(&self, path: &Path, subtrees: &Subtrees) -> FileResult {
+        match fs::read_to_string(path) {
+            Ok(content) => self.process_content(path, &content, subtrees),
+            Err(e) => FileResult::unreadable(path, e),
+        }
+    }
+
+    fn process_content(&self, path: &Path, content: &str, subtrees: &Subtrees) -> FileResult {
+        let chain = self.chain(path, subtrees);
+        let scouts = self.scouts_for(&chain);
+        let matching_scouts: Vec<&Scout> = scouts
             .iter()
+            .copied()
             .filter(|s| s.applies_to_file(path))
             .collect();
 
         let file_path = path.display().to_string();
-        let mut findings = Vec::new();
+        let relative_path = match path.strip_prefix(&self.root_path) {
+            Ok(rel) if !rel.as_os_str().is_empty() => rel,
+            _ => path,
+        };
+        let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+        let fingerprint_path = self.fingerprint_path(path, &relative_path);
+        let lines: Vec<&str> = content.lines().collect();
+        let mut findings: Vec<Finding> = Vec::new();
+
+        // Tokenize once per distinct comment syntax among the matching scouts
+        let mut tokenized: Vec<(CommentSyntax, Vec<Vec<Span>>)> = Vec::new();
+        for scout in &matching_scouts {
+            if let Some(syntax) = scout.syntax_for(path) {
+                if !tokenized.iter().any(|(s, _)| *s == syntax) {
+                    tokenized.push((syntax, syntax.tokenize(content)));
+                }
+            }
+        }
+
+        let mut trackers: Vec<RegionTracker> = matching_scouts
+            .iter()
+            .map(|_| RegionTracker::new())
+            .collect();
+        let mut seen_code = vec![false; matching_scouts.len()];
+        // Region openers that precede any code become whole-file if unclosed
+        let mut leading_openers = Vec::new();
+
+        for (index, line_text) in lines.iter().copied().enumerate() {
+            let line_number = index + 1;
+            for (i, scout) in matching_scouts.iter().enumerate() {
+                let spans = scout
+                    .syntax_for(path)
+                    .and_then(|syntax| tokenized.iter().find(|(s, _)| *s == syntax))
+                    .map_or(&[][..], |(_, lines)| &lines[index][..]);
+                for (rule, start) in scout.match_line(line_text, spans) {
+                    let suppressed_rules = rule.capture_suppressed_rules(&line_text[start..]);
+                    let blanket = rule.is_blanket(suppressed_rules.as_deref());
+                    let justification = rule.capture_justification(&line_text[start..]);
+                    let trailing = follows_code(line_text, spans, start);
+                    let scope = rule.scope_for(trailing);
+                    let comment = spans
+                        .iter()
+                        .find(|s| s.kind == TokenKind::Comment && (s.start..s.end).contains(&start))
+                        .map_or(&line_text[start..], |s| &line_text[s.start..s.end]);
 
-        for (line_number, line_text) in content.lines().enumerate() {
-            let line_number = line_number + 1;
-            for scout in &matching_scouts {
-                for rule in scout.find_matches(line_text) {
-                    let suppressed_rules = rule.capture_suppressed_rules(line_text);
+                    if let Some(ref marker) = rule.region {
+                        if marker.role == RegionRole::Close {
+                            // Closers end a region rather than suppress anything
+                            for (key, region) in trackers[i].close(
+                                &marker.group,
+                                line_number,
+                                suppressed_rules.as_deref(),
+                            ) {
+                                findings[key].region = Some(region);
+                            }
+                            continue;
+                        }
+                        if !(marker.inline_if_trailing && trailing) {
+                            if !seen_code[i] && !trailing {
+                                leading_openers.push(findings.len());
+                            }
+                            trackers[i].open(
+                                findings.len(),
+                                &marker.group,
+                                line_number,
+                                suppressed_rules.clone(),
+                            );
+                        }
+                    }
+
+                    let rule_details = Catalog::builtin().details(
+                        &scout.linter,
+                        suppressed_rules.as_deref().unwrap_or_default(),
+                    );
                     findings.push(Finding {
                         path: file_path.clone(),
                         line_number,
                         line_text: line_text.to_string(),
+                        comment: comment.to_string(),
                         scout_name: scout.name.clone(),
                         linter: scout.linter.clone(),
                         rule_id: rule.id.clone(),
                         rule_description: rule.description.clone(),
+                        scope,
+                        blanket,
                         suppressed_rules,
+                        region: None,
+                        justification,
+                        justification_issue: None,
+                        fingerprint: fingerprint::fingerprint(
+                            &fingerprint_path,
+                            &scout.name,
+                            &rule.id,
+                            line_text,
+                            fingerprint::context(&lines, index),
+                        ),
+                        blame: None,
+                        age_policies: Vec::new(),
+                        policy_violation: None,
+                        expiry: None,
+                        acknowledgement: None,
+                        rule_details,
+                        orphaned: false,
+                        key_path: None,
+                        also_honored_by: Vec::new(),
                     });
                 }
+                if !seen_code[i] {
+                    seen_code[i] = if scout.syntax.is_some() {
+                        follows_code(line_text, spans, line_text.len())
+                    } else {
+                        !line_text.trim().is_empty()
+                    };
+                }
+            }
+        }
+
+        for tracker in trackers {
+            for (key, region) in tracker.finish(lines.len()) {
+                if leading_openers.contains(&key) {
+                    findings[key].scope = Scope::WholeFile;
+                }
+                findings[key].region = Some(region);
+            }
+        }
+
+        let mut errors = Vec::new();
+        let mut exemptions = Vec::new();
+        let config_scouts = config_scouts(&scouts, path);
+        if !config_scouts.is_empty() {
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            match linter_config::read(name, content) {
+                Ok(config) => {
+                    findings.extend(config.suppressions.into_iter().filter_map(|suppression| {
+                        let scout = config_scouts
+                            .iter()
+                            .find(|s| s.linter == suppression.linter)?;
+                        Some(config_finding(
+                            scout,
+                            suppression,
+                            name,
+                            &file_path,
+                            &fingerprint_path,
+                            &lines,
+                        ))
+                    }));
+                    let dir = Path::new(&relative_path)
+                        .parent()
+                        .map(|d| d.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    exemptions.extend(
+                        config
+                            .exemptions
+                            .into_iter()
+                            .filter(|e| config_scouts.iter().any(|s| s.linter == e.linter))
+                            .map(|exemption| ExemptionRule {
+                                path: file_path.clone(),
+                                dir: dir.clone(),
+                                exemption,
+                            }),
+                    );
+                }
+                Err(e) => errors.push(format!("{file_path}: {e}")),
+            }
+        }
+
+        let linted = (!matching_scouts.is_empty()).then(|| LintedFile {
+            path: file_path.clone(),
+            relative: relative_path.clone(),
+            linters: matching_scouts.iter().map(|s| s.linter.clone()).collect(),
+        });
+
+        let file_lines = FileLines {
+            lines: lines.iter().filter(|l| !l.trim().is_empty()).count() as u64,
+            directory: top_level_directory(Path::new(&relative_path)),
+            scouts: matching_scouts
+                .iter()
+                .map(|s| (s.name.clone(), s.language.clone()))
+                .collect(),
+        };
+
+        // Config files are read by no scout, so their comments are found
+        // with the polyglot syntax
+        let polyglot = findings
+            .iter()
+            .any(|f| f.scope == Scope::Config)
+            .then(|| CommentSyntax::POLYGLOT.tokenize(content));
+        let mut directives: Vec<(Option<CommentSyntax>, Vec<acknowledge::Directive>)> = Vec::new();
+        for syntax in matching_scouts.iter().map(|s| s.syntax_for(path)) {
+            if !directives.iter().any(|(s, _)| *s == syntax) {
+                let spans = syntax
+                    .and_then(|syntax| tokenized.iter().find(|(s, _)| *s == syntax))
+                    .map(|(_, spans)| &spans[..]);
+                directives.push((syntax, acknowledge::directives(&lines, spans)));
             }
         }
+        if let Some(ref spans) = polyglot {
+            let polyglot = Some(CommentSyntax::POLYGLOT);
+            if !directives.iter().any(|(s, _)| *s == polyglot) {
+                directives.push((polyglot, acknowledge::directives(&lines, Some(spans))));
+            }
+        }
+        let (findings, acknowledged) = acknowledge::partition(findings, |finding| {
+            let syntax = match matching_scouts
+                .iter()
+                .find(|s| s.name == finding.scout_name)
+            {
+                Some(scout) if finding.scope != Scope::Config => scout.syntax_for(path),
+                _ => Some(CommentSyntax::POLYGLOT),
+            };
+            directives
+                .iter()
+                .find(|(s, _)| *s == syntax)
+                .map_or(&[][..], |(_, d)| &d[..])
+        });
 
         FileResult {
             findings,
-            errors: Vec::new(),
+            acknowledged,
+            errors,
             scanned: true,
             skipped: false,
+            // Config files count towards density only when a scout reads
+            // them as code
+            lines: (!matching_scouts.is_empty()).then_some((file_path, file_lines)),
+            linted,
+            exemptions,
         }
     }
 }
+
+/// The scouts whose linters the config file at `path` may configure.
+fn config_scouts<'a>(scouts: &[&'a Scout], path: &Path) -> Vec<&'a Scout> {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let linters = linter_config::linters(name);
+    scouts
+        .iter()
+        .copied()
+        .filter(|s| linters.contains(&s.linter.as_str()))
+        .collect()
+}
+
+/// The finding for a suppression in the config file `name`.
+fn config_finding(
+    scout: &Scout,
+    suppression: ConfigSuppression,
+    name: &str,
+    file_path: &str,
+    fingerprint_path: &str,
+    lines: &[&str],
+) -> Finding {
+    let index = suppression.line.saturating_sub(1);
+    let line_text = lines.get(index).copied().unwrap_or_default();
+    let rule_details = Catalog::builtin().details(&scout.linter, &suppression.rules);
+    // Rules listed on one line share its text, so the key and rules tell
+    // their fingerprints apart
+    let mut parts = vec![suppression.key_path.as_str()];
+    parts.extend(suppression.rules.iter().map(String::as_str));
+    let fingerprint = fingerprint::derive(
+        &fingerprint::fingerprint(
+            fingerprint_path,
+            &scout.name,
+            suppression.rule_id,
+            line_text,
+            fingerprint::context(lines, index),
+        ),
+        &parts,
+    );
+    Finding {
+        path: file_path.to_string(),
+        line_number: suppression.line,
+        line_text: line_text.to_string(),
+        comment: linter_config::trailing_comment(name, line_text).unwrap_or_default(),
+        scout_name: scout.name.clone(),
+        linter: scout.linter.clone(),
+        rule_id: suppression.rule_id.to_string(),
+        rule_description: suppression.description.to_string(),
+        scope: Scope::Config,
+        blanket: suppression.rules.is_empty(),
+        suppressed_rules: Some(suppression.rules).filter(|r| !r.is_empty()),
+        region: None,
+        justification: linter_config::trailing_comment(name, line_text),
+        justification_issue: None,
+        fingerprint,
+        blame: None,
+        age_policies: Vec::new(),
+        policy_violation: None,
+        expiry: None,
+        acknowledgement: None,
+        rule_details,
+        orphaned: false,
+        key_path: Some(suppression.key_path),
+        also_honored_by: Vec::new(),
+    }
+}
+
+/// Whether non-whitespace code precedes byte offset `start` on the line.
+pub(crate) fn follows_code(line: &str, spans: &[Span], start: usize) -> bool {
+    spans
+        .iter()
+        .filter(|s| s.kind == TokenKind::Code && s.start < start)
+        .any(|s| !line[s.start..s.end.min(start)].trim().is_empty())
+}
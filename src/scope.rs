@@ -0,0 +1,77 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// What a suppression directive actually covers.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+    /// The line the directive is on, e.g. `// eslint-disable-line`.
+    #[default]
+    SameLine,
+    /// The following line or statement, e.g. `// eslint-disable-next-line`.
+    NextLine,
+    /// Lines between an opener and a closer, e.g. `/* eslint-disable */` ...
+    /// `/* eslint-enable */`.
+    Region,
+    /// The whole file, e.g. `// @ts-nocheck` or `#![allow(clippy::...)]`.
+    WholeFile,
+    /// The annotated item, e.g. `#[allow(clippy::...)]` or `@SuppressWarnings`.
+    Declaration,
+    /// Everywhere the linter runs, from its configuration file, e.g.
+    /// `"no-console": "off"` in `.eslintrc.json`.
+    Config,
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::SameLine => "same-line",
+            Scope::NextLine => "next-line",
+            Scope::Region => "region",
+            Scope::WholeFile => "whole-file",
+            Scope::Declaration => "declaration",
+            Scope::Config => "config",
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_kebab_case() {
+        assert_eq!(
+            serde_json::to_string(&Scope::WholeFile).unwrap(),
+            "\"whole-file\""
+        );
+        let s: Scope = serde_yaml::from_str("next-line").unwrap();
+        assert_eq!(s, Scope::NextLine);
+    }
+
+    #[test]
+    fn display_matches_serde() {
+        for scope in [
+            Scope::SameLine,
+            Scope::NextLine,
+            Scope::Region,
+            Scope::WholeFile,
+            Scope::Declaration,
+            Scope::Config,
+        ] {
+            assert_eq!(
+                serde_json::to_string(&scope).unwrap(),
+                format!("\"{scope}\"")
+            );
+        }
+    }
+}
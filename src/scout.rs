@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use crate::rule::Rule;
+use crate::syntax::{CommentSyntax, Span};
 
 #[derive(Debug, Clone)]
 pub struct Scout {
@@ -9,6 +10,9 @@ pub struct Scout {
     pub language: String,
     pub extensions: Vec<String>,
     pub rules: Vec<Rule>,
+    /// Comment syntax used to restrict matches to real comments. When `None`,
+    /// rules match anywhere on the raw line.
+    pub syntax: Option<CommentSyntax>,
 }
 
 impl Scout {
@@ -24,8 +28,38 @@ impl Scout {
         false
     }
 
+    /// The comment syntax of the file at `path`, in the dialect its
+    /// extension calls for.
+    pub fn syntax_for(&self, path: &Path) -> Option<CommentSyntax> {
+        self.syntax.map(|s| s.for_path(path))
+    }
+
+    /// Match a single line in isolation, tokenizing it with the scout's syntax.
     pub fn find_matches(&self, line: &str) -> Vec<&Rule> {
-        self.rules.iter().filter(|r| r.is_match(line)).collect()
+        let spans = match self.syntax {
+            Some(syntax) => syntax.tokenize(line).pop().unwrap_or_default(),
+            None => Vec::new(),
+        };
+        self.match_line(line, &spans)
+            .into_iter()
+            .map(|(rule, _)| rule)
+            .collect()
+    }
+
+    /// Match a line given its spans from the file-level tokenizer, returning
+    /// each matching rule with the byte offset where its match starts.
+    pub fn match_line(&self, line: &str, spans: &[Span]) -> Vec<(&Rule, usize)> {
+        if self.syntax.is_none() {
+            return self
+                .rules
+                .iter()
+                .filter_map(|r| r.pattern.find(line).map(|m| (r, m.start())))
+                .collect();
+        }
+        self.rules
+            .iter()
+            .filter_map(|r| r.find_in_spans(line, spans).map(|start| (r, start)))
+            .collect()
     }
 }
 
@@ -43,6 +77,7 @@ mod tests {
                 Rule::new("r1", "rule one", r"eslint-disable").unwrap(),
                 Rule::new("r2", "rule two", r"@ts-ignore").unwrap(),
             ],
+            syntax: None,
         }
     }
 
@@ -73,6 +108,24 @@ mod tests {
         assert_eq!(m[0].id, "r1");
     }
 
+    #[test]
+    fn find_matches_skips_strings_with_syntax() {
+        let mut s = test_scout();
+        s.syntax = Some(CommentSyntax::JAVASCRIPT);
+        assert!(s
+            .find_matches(r#"const msg = "use eslint-disable-next-line here";"#)
+            .is_empty());
+        assert_eq!(s.find_matches("foo(); // eslint-disable-line").len(), 1);
+    }
+
+    #[test]
+    fn match_line_reports_offset() {
+        let s = test_scout();
+        let m = s.match_line("x; // @ts-ignore", &[]);
+        assert_eq!(m.len(), 1);
+        assert_eq!(m[0].1, 6);
+    }
+
     #[test]
     fn find_matches_returns_empty_for_no_match() {
         let s = test_scout();
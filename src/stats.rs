@@ -1,11 +1,34 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Serialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ScanStats {
     pub files_walked: u64,
     pub files_scanned: u64,
     pub files_skipped: u64,
+    /// Findings less rule policy violations, which `policy_violations`
+    /// counts, so that each directive counts once.
     pub findings_count: u64,
+    pub blanket_count: u64,
+    pub unjustified_count: u64,
+    /// Findings matched by an age policy with the `fail` action.
+    pub age_failures: u64,
+    /// Findings reporting a denied rule or an exceeded quota.
+    pub policy_violations: u64,
+    /// Per-path budgets whose limit the findings exceed.
+    pub budgets_exceeded: u64,
+    /// Nested configs whose thresholds the findings under them exceed.
+    pub subtrees_exceeded: u64,
+    /// Findings that are expired or fail the ticket requirement.
+    pub expiry_failures: u64,
+    /// Findings approved by a `lintscout-ignore` comment.
+    pub acknowledged_count: u64,
+    /// Suppressed rule IDs missing from the rule catalog.
+    pub unknown_rules: u64,
+    /// Findings of linters not configured in the scanned tree.
+    pub orphaned_count: u64,
+    /// Findings for rules turned off in linter config files.
+    pub config_count: u64,
     pub errors_count: u64,
     pub duration_ms: u64,
 }
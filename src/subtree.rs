@@ -0,0 +1,619 @@
+//! Nested config files: a `.lintscout.yml` in a directory below the root
+//! config applies to that subtree. It replaces the excludes and thresholds of
+//! the configs above it, disables further scouts and adds custom scouts. The
+//! configs of a file are resolved as the scan reaches its directory; those
+//! between the root config and the scan root apply to the whole scan.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use ignore::overrides::{Override, OverrideBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Settings, SubtreeConfig, SubtreeSettings};
+use crate::density::{Density, LineCounts};
+use crate::error::{LintScoutError, Result};
+use crate::scanner::ScanResult;
+use crate::scout::Scout;
+
+/// Name of nested config files. A `lintscout.yml` is only read as the root
+/// config, so one kept for `--config` does not apply to its directory.
+pub const FILE_NAME: &str = ".lintscout.yml";
+
+/// A nested config, with the scouts and excludes it builds.
+pub struct Layer {
+    /// Directory of the config file relative to the scan root; empty at the
+    /// root and for configs above it.
+    pub dir: PathBuf,
+    /// The scan root relative to the directory of a config above it, which
+    /// its excludes are matched against; empty otherwise.
+    within: PathBuf,
+    /// Path of the config file, as in finding paths.
+    pub path: String,
+    pub config: SubtreeConfig,
+    pub scouts: Vec<Scout>,
+    excludes: Option<Override>,
+}
+
+impl Layer {
+    /// The layer of a config in `dir` under the scan `root`.
+    fn new(root: &Path, dir: &Path, config: SubtreeConfig) -> Result<Self> {
+        Self::build(root.join(dir), dir.to_path_buf(), PathBuf::new(), config)
+    }
+
+    /// The layer of a config above the scan `root`, `levels` directories up,
+    /// reached from the root through `within`.
+    fn above(root: &Path, levels: usize, within: &Path, config: SubtreeConfig) -> Result<Self> {
+        let mut config_dir = root.to_path_buf();
+        for _ in 0..levels {
+            match config_dir.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    config_dir.pop();
+                }
+                _ => config_dir.push(".."),
+            }
+        }
+        Self::build(config_dir, PathBuf::new(), within.to_path_buf(), config)
+    }
+
+    fn build(
+        config_dir: PathBuf,
+        dir: PathBuf,
+        within: PathBuf,
+        config: SubtreeConfig,
+    ) -> Result<Self> {
+        let path = config_dir.join(FILE_NAME);
+        let invalid = |e: LintScoutError| LintScoutError::ConfigLoad {
+            path: path.clone(),
+            source: Box::new(e),
+        };
+        let scouts = config.build_custom_scouts().map_err(invalid)?;
+        let excludes = match config.settings.exclude {
+            Some(ref patterns) => Some(excludes(&config_dir, patterns).map_err(invalid)?),
+            None => None,
+        };
+        Ok(Layer {
+            dir,
+            within,
+            path: path.display().to_string(),
+            config,
+            scouts,
+            excludes,
+        })
+    }
+}
+
+/// Exclude patterns relative to `dir`, as overrides that ignore them.
+fn excludes(dir: &Path, patterns: &[String]) -> Result<Override> {
+    let mut builder = OverrideBuilder::new(dir);
+    for pattern in patterns {
+        builder.add(&format!("!{pattern}")).map_err(|e| {
+            LintScoutError::Config(format!("invalid exclude pattern '{pattern}': {e}"))
+        })?;
+    }
+    builder
+        .build()
+        .map_err(|e| LintScoutError::Config(e.to_string()))
+}
+
+/// The nested configs of a scan: those between the root config and the scan
+/// root, and those under the scan root, loaded as their directories are
+/// reached.
+pub struct Subtrees {
+    root: PathBuf,
+    /// The config given on the command line or found by discovery, which is
+    /// not nested even when it lies under the root.
+    root_config: Option<PathBuf>,
+    /// Excludes of the root config or the command line.
+    excludes: Option<Override>,
+    /// Nested configs in the directories between the root config, or the
+    /// repository root without one, and the scan root, outermost first.
+    above: Vec<Arc<Layer>>,
+    /// Nested configs by directory relative to the root; `None` for
+    /// directories without one.
+    layers: Mutex<HashMap<PathBuf, Option<Arc<Layer>>>>,
+    /// Whether every config was supplied up front, as for scans of git
+    /// blobs, rather than read from the filesystem.
+    preloaded: bool,
+    /// The first config that failed to load.
+    error: Mutex<Option<LintScoutError>>,
+}
+
+impl Subtrees {
+    pub fn new(root: &Path, root_config: Option<&Path>, excludes: Option<Override>) -> Self {
+        let mut subtrees = Subtrees {
+            root: root.to_path_buf(),
+            root_config: root_config.and_then(|p| p.canonicalize().ok()),
+            excludes,
+            above: Vec::new(),
+            layers: Mutex::new(HashMap::new()),
+            preloaded: false,
+            error: Mutex::new(None),
+        };
+        subtrees.above = subtrees.load_above();
+        subtrees
+    }
+
+    /// Load the nested configs above the scan root. They are read from the
+    /// filesystem even for scans of git blobs, like the root config.
+    fn load_above(&self) -> Vec<Arc<Layer>> {
+        let Ok(root) = self.root.canonicalize() else {
+            return Vec::new();
+        };
+        let base = self
+            .root_config
+            .as_ref()
+            .and_then(|config| config.parent())
+            .filter(|dir| root.starts_with(dir))
+            .or_else(|| root.ancestors().find(|d| d.join(".git").exists()));
+        let Some(base) = base else {
+            return Vec::new();
+        };
+        let components: Vec<Component> = root
+            .strip_prefix(base)
+            .unwrap_or(&root)
+            .components()
+            .collect();
+        // The config of the scan root itself is loaded as a nested one
+        let mut above = Vec::new();
+        let mut dir = base.to_path_buf();
+        for (i, component) in components
+            .iter()
+            .enumerate()
+            .take(components.len().saturating_sub(1))
+        {
+            dir.push(component);
+            let path = dir.join(FILE_NAME);
+            if !path.is_file() || self.is_root_config(&path) {
+                continue;
+            }
+            let within: PathBuf = components[i + 1..].iter().collect();
+            let levels = components.len() - i - 1;
+            let loaded = SubtreeConfig::load(&path)
+                .and_then(|config| Layer::above(&self.root, levels, &within, config));
+            above.extend(self.record(loaded));
+        }
+        above
+    }
+
+    /// Load the configs among `files`, whose paths are relative to the root,
+    /// instead of reading them from the filesystem.
+    pub fn preload(&mut self, files: &[(PathBuf, Vec<u8>)]) {
+        self.preloaded = true;
+        for (relative, bytes) in files
+            .iter()
+            .filter(|(p, _)| p.file_name().is_some_and(|n| n == FILE_NAME))
+        {
+            if self.is_root_config(&self.root.join(relative)) {
+                continue;
+            }
+            let dir = relative.parent().unwrap_or(Path::new(""));
+            let loaded = String::from_utf8(bytes.clone())
+                .map_err(|e| LintScoutError::ConfigLoad {
+                    path: relative.clone(),
+                    source: Box::new(e),
+                })
+                .and_then(|content| SubtreeConfig::parse(relative, &content))
+                .and_then(|config| Layer::new(&self.root, dir, config));
+            let layer = self.record(loaded);
+            self.layers
+                .get_mut()
+                .unwrap()
+                .insert(dir.to_path_buf(), layer);
+        }
+    }
+
+    fn is_root_config(&self, path: &Path) -> bool {
+        self.root_config
+            .as_ref()
+            .is_some_and(|root| path.canonicalize().is_ok_and(|p| &p == root))
+    }
+
+    /// The loaded layer, recording the error of one that failed to load.
+    fn record(&self, loaded: Result<Layer>) -> Option<Arc<Layer>> {
+        match loaded {
+            Ok(layer) => Some(Arc::new(layer)),
+            Err(e) => {
+                self.error.lock().unwrap().get_or_insert(e);
+                None
+            }
+        }
+    }
+
+    /// The nested config of `dir`, relative to the root, if it has one.
+    fn layer(&self, dir: &Path) -> Option<Arc<Layer>> {
+        if let Some(layer) = self.layers.lock().unwrap().get(dir) {
+            return layer.clone();
+        }
+        let layer = if self.preloaded { None } else { self.read(dir) };
+        self.layers
+            .lock()
+            .unwrap()
+            .entry(dir.to_path_buf())
+            .or_insert(layer)
+            .clone()
+    }
+
+    fn read(&self, dir: &Path) -> Option<Arc<Layer>> {
+        let path = self.root.join(dir).join(FILE_NAME);
+        if !path.is_file() || self.is_root_config(&path) {
+            return None;
+        }
+        self.record(
+            SubtreeConfig::load(&path).and_then(|config| Layer::new(&self.root, dir, config)),
+        )
+    }
+
+    /// The nested configs that apply in `dir`, relative to the root,
+    /// outermost first.
+    pub fn chain(&self, dir: &Path) -> Vec<Arc<Layer>> {
+        let mut dirs: Vec<&Path> = dir.ancestors().collect();
+        dirs.reverse();
+        self.above
+            .iter()
+            .cloned()
+            .chain(dirs.into_iter().filter_map(|d| self.layer(d)))
+            .collect()
+    }
+
+    /// Whether the entry at `relative` is excluded by the nearest config
+    /// above it that sets excludes.
+    pub fn excluded(&self, relative: &Path, is_dir: bool) -> bool {
+        let chain = self.chain(relative.parent().unwrap_or(Path::new("")));
+        let (excludes, path) = match chain
+            .iter()
+            .rev()
+            .find(|l| l.config.settings.exclude.is_some())
+        {
+            Some(layer) => (
+                layer.excludes.as_ref(),
+                layer
+                    .within
+                    .join(relative.strip_prefix(&layer.dir).unwrap_or(relative)),
+            ),
+            None => (self.excludes.as_ref(), relative.to_path_buf()),
+        };
+        excludes.is_some_and(|o| o.matched(path, is_dir).is_ignore())
+    }
+
+    /// Take the error of the first config that failed to load.
+    pub fn take_error(&self) -> Option<LintScoutError> {
+        self.error.lock().unwrap().take()
+    }
+
+    /// The nested configs setting thresholds, by directory, with no usage
+    /// recorded yet.
+    pub fn thresholds(&self) -> Vec<SubtreeUsage> {
+        let layers = self.layers.lock().unwrap();
+        // Configs above the root come first, so that the stable sort keeps
+        // them ahead of the root's own
+        let mut usages: Vec<SubtreeUsage> = self
+            .above
+            .iter()
+            .chain(layers.values().flatten())
+            .filter(|l| Thresholds::from(&l.config.settings).is_set())
+            .map(|l| {
+                let thresholds = Thresholds::from(&l.config.settings);
+                SubtreeUsage {
+                    config: l.path.clone(),
+                    dir: self.root.join(&l.dir).display().to_string(),
+                    pass_threshold: thresholds.pass,
+                    blanket_threshold: thresholds.blanket,
+                    density_threshold: thresholds.density,
+                    usage: Usage::default(),
+                    exceeded: false,
+                }
+            })
+            .collect();
+        usages.sort_by(|a, b| a.dir.cmp(&b.dir));
+        usages
+    }
+}
+
+/// Limits on the findings in a tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Thresholds {
+    pub pass: Option<u64>,
+    pub blanket: Option<u64>,
+    pub density: Option<f64>,
+}
+
+impl From<&Settings> for Thresholds {
+    fn from(settings: &Settings) -> Self {
+        Thresholds {
+            pass: settings.pass_threshold,
+            blanket: settings.blanket_threshold,
+            density: settings.density_threshold,
+        }
+    }
+}
+
+impl From<&SubtreeSettings> for Thresholds {
+    fn from(settings: &SubtreeSettings) -> Self {
+        Thresholds {
+            pass: settings.pass_threshold,
+            blanket: settings.blanket_threshold,
+            density: settings.density_threshold,
+        }
+    }
+}
+
+impl Thresholds {
+    /// Each threshold of `self`, or else of `fallback`.
+    pub fn or(self, fallback: Thresholds) -> Thresholds {
+        Thresholds {
+            pass: self.pass.or(fallback.pass),
+            blanket: self.blanket.or(fallback.blanket),
+            density: self.density.or(fallback.density),
+        }
+    }
+
+    fn is_set(&self) -> bool {
+        self.pass.is_some() || self.blanket.is_some() || self.density.is_some()
+    }
+
+    /// Whether the findings exceed a threshold. Without a pass threshold, any
+    /// finding fails, unless a density threshold or `budgets` replace that
+    /// default.
+    pub fn exceeded(&self, usage: &Usage, budgets: bool) -> bool {
+        let count = match self.pass {
+            Some(threshold) => usage.findings > threshold,
+            None => self.density.is_none() && !budgets && usage.findings > 0,
+        };
+        let density = matches!(
+            (self.density, usage.per_kloc),
+            (Some(threshold), Some(per_kloc)) if per_kloc > threshold
+        );
+        let blanket = self.blanket.is_some_and(|t| usage.blanket > t);
+        count || density || blanket
+    }
+}
+
+/// The findings of a tree, checked against its thresholds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub findings: u64,
+    pub blanket: u64,
+    /// Suppressions per 1000 lines, for scans that counted lines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_kloc: Option<f64>,
+}
+
+/// A nested config setting thresholds, and the findings under it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubtreeUsage {
+    /// Path of the config file.
+    pub config: String,
+    /// Directory it applies to, as in finding paths.
+    pub dir: String,
+    /// The thresholds in effect, its own or inherited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pass_threshold: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blanket_threshold: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub density_threshold: Option<f64>,
+    /// Findings under the directory, less those under nested configs below
+    /// it that set thresholds of their own.
+    #[serde(flatten)]
+    pub usage: Usage,
+    pub exceeded: bool,
+}
+
+impl SubtreeUsage {
+    fn thresholds(&self) -> Thresholds {
+        Thresholds {
+            pass: self.pass_threshold,
+            blanket: self.blanket_threshold,
+            density: self.density_threshold,
+        }
+    }
+}
+
+/// Check the findings under each nested config setting thresholds against
+/// them, and return the usage of the rest, which the root thresholds cover.
+/// Thresholds a nested config leaves unset are inherited from the one above
+/// it; `overrides` from the command line take precedence over all of them.
+pub fn apply(
+    result: &mut ScanResult,
+    overrides: Thresholds,
+    root: Thresholds,
+    budgets: bool,
+) -> Usage {
+    let subtrees = &mut result.subtrees;
+    // Parents sort before their subdirectories
+    for i in 0..subtrees.len() {
+        let parent = (0..i)
+            .rev()
+            .find(|&j| Path::new(&subtrees[i].dir).starts_with(&subtrees[j].dir))
+            .map_or(root, |j| subtrees[j].thresholds());
+        let thresholds = overrides.or(subtrees[i].thresholds()).or(parent);
+        subtrees[i].pass_threshold = thresholds.pass;
+        subtrees[i].blanket_threshold = thresholds.blanket;
+        subtrees[i].density_threshold = thresholds.density;
+    }
+
+    // The nearest nested config with thresholds owns a path
+    let owner = |path: &str| {
+        subtrees
+            .iter()
+            .rposition(|s| Path::new(path).starts_with(&s.dir))
+    };
+    let mut findings: Vec<Vec<_>> = vec![Vec::new(); subtrees.len() + 1];
+    // Rule policy violations repeat the directive they report
+    for f in result
+        .findings
+        .iter()
+        .filter(|f| f.policy_violation.is_none())
+    {
+        findings[owner(&f.path).map_or(0, |i| i + 1)].push(f.clone());
+    }
+    let mut lines: Vec<LineCounts> = vec![LineCounts::default(); subtrees.len() + 1];
+    for (path, file) in &result.lines.files {
+        lines[owner(path).map_or(0, |i| i + 1)]
+            .files
+            .insert(path.clone(), file.clone());
+    }
+
+    let mut usages = findings.iter().zip(&lines).map(|(findings, lines)| Usage {
+        findings: findings.len() as u64,
+        blanket: findings.iter().filter(|f| f.blanket).count() as u64,
+        per_kloc: result
+            .density
+            .as_ref()
+            .map(|_| Density::compute(lines, findings).per_kloc),
+    });
+    let root_usage = usages.next().unwrap_or_default();
+    for (subtree, usage) in subtrees.iter_mut().zip(usages) {
+        subtree.exceeded = subtree.thresholds().exceeded(&usage, budgets);
+        subtree.usage = usage;
+    }
+    result.stats.subtrees_exceeded = subtrees.iter().filter(|s| s.exceeded).count() as u64;
+    root_usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn resolves_nested_configs_per_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, ".lintscout.yml", "settings:\n  exclude: [build]\n");
+        write(
+            root,
+            "pkg/.lintscout.yml",
+            "settings:\n  exclude: []\n  pass_threshold: 3\n",
+        );
+        write(
+            root,
+            "pkg/deep/.lintscout.yml",
+            "settings:\n  disable:\n    scouts: [eslint]\n",
+        );
+        // Only read as a root config
+        write(
+            root,
+            "pkg/other/lintscout.yml",
+            "settings:\n  exclude: []\n",
+        );
+
+        let root_excludes = excludes(root, &["vendor".to_string()]).unwrap();
+        let subtrees = Subtrees::new(
+            root,
+            Some(&root.join(".lintscout.yml")),
+            Some(root_excludes),
+        );
+        let chain: Vec<PathBuf> = subtrees
+            .chain(Path::new("pkg/deep/src"))
+            .iter()
+            .map(|l| l.dir.clone())
+            .collect();
+        assert_eq!(chain, [PathBuf::from("pkg"), PathBuf::from("pkg/deep")]);
+
+        assert!(subtrees.excluded(Path::new("vendor"), true));
+        assert!(subtrees.excluded(Path::new("app/vendor"), true));
+        assert!(!subtrees.excluded(Path::new("pkg/vendor"), true));
+        assert!(!subtrees.excluded(Path::new("build"), true));
+        assert_eq!(subtrees.chain(Path::new("pkg/other")).len(), 1);
+
+        let usages = subtrees.thresholds();
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].pass_threshold, Some(3));
+        assert!(subtrees.take_error().is_none());
+    }
+
+    #[test]
+    fn applies_configs_above_the_scan_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, ".lintscout.yml", "settings: {}\n");
+        write(
+            root,
+            "pkg/.lintscout.yml",
+            "settings:\n  exclude: [sub/generated]\n  pass_threshold: 2\n",
+        );
+        write(root, "pkg/sub/.lintscout.yml", "settings: {}\n");
+
+        let scan_root = root.join("pkg/sub");
+        let subtrees = Subtrees::new(&scan_root, Some(&root.join(".lintscout.yml")), None);
+        let chain = subtrees.chain(Path::new("src"));
+        let paths: Vec<&str> = chain.iter().map(|l| l.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                root.join("pkg/.lintscout.yml").display().to_string(),
+                scan_root.join(".lintscout.yml").display().to_string(),
+            ]
+        );
+        assert!(chain.iter().all(|l| l.dir == Path::new("")));
+        assert!(subtrees.excluded(Path::new("generated"), true));
+        assert!(!subtrees.excluded(Path::new("src/generated"), true));
+
+        let usages = subtrees.thresholds();
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].pass_threshold, Some(2));
+        assert!(subtrees.take_error().is_none());
+
+        // Without a root config, the walk starts at the repository root
+        std::fs::create_dir(root.join(".git")).unwrap();
+        let subtrees = Subtrees::new(&scan_root, None, None);
+        assert_eq!(subtrees.chain(Path::new("src")).len(), 2);
+    }
+
+    #[test]
+    fn preloads_configs_and_reports_errors() {
+        let files = vec![
+            (PathBuf::from("a/lintscout.yml"), b"settings: {}\n".to_vec()),
+            (
+                PathBuf::from("a/.lintscout.yml"),
+                b"settings:\n  pass_threshold: 1\n".to_vec(),
+            ),
+            (PathBuf::from("b/.lintscout.yml"), b"scouts: [1]\n".to_vec()),
+        ];
+        let mut subtrees = Subtrees::new(Path::new("/nonexistent"), None, None);
+        subtrees.preload(&files);
+        let usages = subtrees.thresholds();
+        assert_eq!(usages[0].pass_threshold, Some(1));
+        assert!(subtrees.chain(Path::new("c")).is_empty());
+        let error = subtrees.take_error().unwrap().to_string();
+        assert!(error.contains("b/.lintscout.yml"), "{error}");
+    }
+
+    #[test]
+    fn thresholds_fall_back_and_fail() {
+        let usage = |findings, blanket, per_kloc| Usage {
+            findings,
+            blanket,
+            per_kloc,
+        };
+        let pass = Thresholds {
+            pass: Some(2),
+            ..Thresholds::default()
+        };
+        let inherited = Thresholds::default().or(pass);
+        assert_eq!(inherited.pass, Some(2));
+        assert!(!pass.exceeded(&usage(2, 2, None), false));
+        assert!(pass.exceeded(&usage(3, 0, None), false));
+
+        let none = Thresholds::default();
+        assert!(none.exceeded(&usage(1, 0, None), false));
+        assert!(!none.exceeded(&usage(1, 0, None), true));
+
+        let density = Thresholds {
+            density: Some(5.0),
+            blanket: Some(0),
+            ..Thresholds::default()
+        };
+        assert!(!density.exceeded(&usage(4, 0, Some(5.0)), false));
+        assert!(density.exceeded(&usage(4, 0, Some(5.5)), false));
+        assert!(density.exceeded(&usage(1, 1, Some(1.0)), false));
+    }
+}
@@ -0,0 +1,575 @@
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Code,
+    Comment,
+    String,
+}
+
+/// A byte range within a single line and what kind of token it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub kind: TokenKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockComment {
+    pub open: &'static str,
+    pub close: &'static str,
+    pub nested: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringLiteral {
+    pub open: &'static str,
+    pub close: &'static str,
+    pub escape: bool,
+    pub multiline: bool,
+    /// Only treat the opener as a literal if it closes within this many bytes
+    /// on the same line and holds a single character or escape. Used for char
+    /// literals so that Rust lifetimes or apostrophes are not mistaken for
+    /// strings.
+    pub max_len: Option<usize>,
+}
+
+/// Comment and string syntax of a language, used to decide which parts of a
+/// line are real comments and which are code or string literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentSyntax {
+    pub name: &'static str,
+    pub line_comments: &'static [&'static str],
+    /// Text after which a line comment marker is code, such as the `$` of
+    /// shell's `$#` and `${#arr[@]}`.
+    pub code_before_comment: &'static [&'static str],
+    pub block_comments: &'static [BlockComment],
+    pub strings: &'static [StringLiteral],
+}
+
+const fn block(open: &'static str, close: &'static str) -> BlockComment {
+    BlockComment {
+        open,
+        close,
+        nested: false,
+    }
+}
+
+const fn nested_block(open: &'static str, close: &'static str) -> BlockComment {
+    BlockComment {
+        open,
+        close,
+        nested: true,
+    }
+}
+
+const fn quoted(delim: &'static str) -> StringLiteral {
+    StringLiteral {
+        open: delim,
+        close: delim,
+        escape: true,
+        multiline: false,
+        max_len: None,
+    }
+}
+
+const fn multiline(delim: &'static str) -> StringLiteral {
+    StringLiteral {
+        open: delim,
+        close: delim,
+        escape: true,
+        multiline: true,
+        max_len: None,
+    }
+}
+
+const fn raw(open: &'static str, close: &'static str) -> StringLiteral {
+    StringLiteral {
+        open,
+        close,
+        escape: false,
+        multiline: true,
+        max_len: None,
+    }
+}
+
+const fn char_literal() -> StringLiteral {
+    StringLiteral {
+        open: "'",
+        close: "'",
+        escape: true,
+        multiline: false,
+        max_len: Some(12),
+    }
+}
+
+const C_BLOCK: BlockComment = block("/*", "*/");
+const HTML_BLOCK: BlockComment = block("<!--", "-->");
+
+impl CommentSyntax {
+    pub const C: CommentSyntax = CommentSyntax {
+        name: "c",
+        line_comments: &["//"],
+        code_before_comment: &[],
+        block_comments: &[C_BLOCK],
+        strings: &[quoted("\""), char_literal()],
+    };
+
+    /// JavaScript and TypeScript, including the HTML comments used in Vue and
+    /// Svelte templates.
+    pub const JAVASCRIPT: CommentSyntax = CommentSyntax {
+        name: "javascript",
+        line_comments: &["//"],
+        code_before_comment: &[],
+        block_comments: &[C_BLOCK, HTML_BLOCK],
+        strings: &[quoted("\""), quoted("'"), multiline("`")],
+    };
+
+    pub const RUST: CommentSyntax = CommentSyntax {
+        name: "rust",
+        line_comments: &["//"],
+        code_before_comment: &[],
+        block_comments: &[nested_block("/*", "*/")],
+        strings: &[
+            raw("r##\"", "\"##"),
+            raw("r#\"", "\"#"),
+            raw("r\"", "\""),
+            multiline("\""),
+            char_literal(),
+        ],
+    };
+
+    pub const GO: CommentSyntax = CommentSyntax {
+        name: "go",
+        line_comments: &["//"],
+        code_before_comment: &[],
+        block_comments: &[C_BLOCK],
+        strings: &[raw("`", "`"), quoted("\""), char_literal()],
+    };
+
+    /// Java and Kotlin, including text blocks and raw strings.
+    pub const JAVA: CommentSyntax = CommentSyntax {
+        name: "java",
+        line_comments: &["//"],
+        code_before_comment: &[],
+        block_comments: &[C_BLOCK],
+        strings: &[multiline("\"\"\""), quoted("\""), char_literal()],
+    };
+
+    pub const SWIFT: CommentSyntax = CommentSyntax {
+        name: "swift",
+        line_comments: &["//"],
+        code_before_comment: &[],
+        block_comments: &[nested_block("/*", "*/")],
+        strings: &[multiline("\"\"\""), quoted("\"")],
+    };
+
+    pub const PHP: CommentSyntax = CommentSyntax {
+        name: "php",
+        line_comments: &["//", "#"],
+        code_before_comment: &[],
+        block_comments: &[C_BLOCK],
+        strings: &[multiline("\""), multiline("'")],
+    };
+
+    pub const CSS: CommentSyntax = CommentSyntax {
+        name: "css",
+        line_comments: &[],
+        code_before_comment: &[],
+        block_comments: &[C_BLOCK, HTML_BLOCK],
+        strings: &[quoted("\""), quoted("'")],
+    };
+
+    /// SCSS, Sass and Less, which add `//` line comments to CSS.
+    pub const SCSS: CommentSyntax = CommentSyntax {
+        name: "scss",
+        line_comments: &["//"],
+        code_before_comment: &[],
+        block_comments: &[C_BLOCK, HTML_BLOCK],
+        strings: &[quoted("\""), quoted("'")],
+    };
+
+    /// Python, where docstrings and triple-quoted strings are skipped.
+    pub const PYTHON: CommentSyntax = CommentSyntax {
+        name: "python",
+        line_comments: &["#"],
+        code_before_comment: &[],
+        block_comments: &[],
+        strings: &[
+            multiline("\"\"\""),
+            multiline("'''"),
+            quoted("\""),
+            quoted("'"),
+        ],
+    };
+
+    /// Hash-commented languages such as Ruby, shell and Dockerfile.
+    pub const HASH: CommentSyntax = CommentSyntax {
+        name: "hash",
+        line_comments: &["#"],
+        code_before_comment: &["$", "${"],
+        block_comments: &[],
+        strings: &[quoted("\""), quoted("'")],
+    };
+
+    /// Mixed-language files where any common comment style may appear, such as
+    /// the CSS, HTML, Markdown and YAML files Prettier formats.
+    pub const POLYGLOT: CommentSyntax = CommentSyntax {
+        name: "polyglot",
+        line_comments: &["//", "#"],
+        code_before_comment: &[],
+        block_comments: &[C_BLOCK, HTML_BLOCK],
+        strings: &[quoted("\""), quoted("'"), quoted("`")],
+    };
+
+    pub const ALL: &'static [CommentSyntax] = &[
+        Self::C,
+        Self::JAVASCRIPT,
+        Self::RUST,
+        Self::GO,
+        Self::JAVA,
+        Self::SWIFT,
+        Self::PHP,
+        Self::CSS,
+        Self::SCSS,
+        Self::PYTHON,
+        Self::HASH,
+        Self::POLYGLOT,
+    ];
+
+    pub fn by_name(name: &str) -> Option<CommentSyntax> {
+        Self::ALL.iter().find(|s| s.name == name).copied()
+    }
+
+    /// The dialect of this syntax in the file at `path`, such as SCSS for the
+    /// `.scss` and `.less` files a CSS scout reads.
+    pub fn for_path(self, path: &Path) -> CommentSyntax {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        match (self.name, extension) {
+            ("css", "scss" | "sass" | "less") => Self::SCSS,
+            _ => self,
+        }
+    }
+
+    /// Split every line of `content` into code, comment and string spans.
+    /// Lines are split the same way as `str::lines`.
+    pub fn tokenize(&self, content: &str) -> Vec<Vec<Span>> {
+        let mut tokenizer = Tokenizer::new(*self);
+        content.lines().map(|l| tokenizer.next_line(l)).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Code,
+    Block { index: usize, depth: usize },
+    Str { index: usize },
+}
+
+/// Line-by-line tokenizer that carries block comment and multi-line string
+/// state across lines.
+pub struct Tokenizer {
+    syntax: CommentSyntax,
+    state: State,
+}
+
+impl Tokenizer {
+    pub fn new(syntax: CommentSyntax) -> Self {
+        Tokenizer {
+            syntax,
+            state: State::Code,
+        }
+    }
+
+    pub fn next_line(&mut self, line: &str) -> Vec<Span> {
+        let mut spans = Vec::new();
+        let mut seg_start = 0;
+        let mut i = 0;
+
+        while i < line.len() {
+            let rest = &line[i..];
+            match self.state {
+                State::Code => {
+                    if self
+                        .syntax
+                        .line_comments
+                        .iter()
+                        .any(|m| rest.starts_with(m))
+                        && !self
+                            .syntax
+                            .code_before_comment
+                            .iter()
+                            .any(|c| line[..i].ends_with(c))
+                    {
+                        push_span(&mut spans, seg_start, i, TokenKind::Code);
+                        push_span(&mut spans, i, line.len(), TokenKind::Comment);
+                        return spans;
+                    }
+                    if let Some(index) = self
+                        .syntax
+                        .block_comments
+                        .iter()
+                        .position(|b| rest.starts_with(b.open))
+                    {
+                        push_span(&mut spans, seg_start, i, TokenKind::Code);
+                        seg_start = i;
+                        i += self.syntax.block_comments[index].open.len();
+                        self.state = State::Block { index, depth: 1 };
+                        continue;
+                    }
+                    if let Some(index) = self.string_opener(rest) {
+                        push_span(&mut spans, seg_start, i, TokenKind::Code);
+                        seg_start = i;
+                        i += self.syntax.strings[index].open.len();
+                        self.state = State::Str { index };
+                        continue;
+                    }
+                    i += char_len(rest);
+                }
+                State::Block { index, depth } => {
+                    let b = self.syntax.block_comments[index];
+                    if b.nested && rest.starts_with(b.open) {
+                        self.state = State::Block {
+                            index,
+                            depth: depth + 1,
+                        };
+                        i += b.open.len();
+                    } else if rest.starts_with(b.close) {
+                        i += b.close.len();
+                        if depth == 1 {
+                            push_span(&mut spans, seg_start, i, TokenKind::Comment);
+                            seg_start = i;
+                            self.state = State::Code;
+                        } else {
+                            self.state = State::Block {
+                                index,
+                                depth: depth - 1,
+                            };
+                        }
+                    } else {
+                        i += char_len(rest);
+                    }
+                }
+                State::Str { index } => {
+                    let s = self.syntax.strings[index];
+                    if s.escape && rest.starts_with('\\') {
+                        i += 1;
+                        if i < line.len() {
+                            i += char_len(&line[i..]);
+                        }
+                    } else if rest.starts_with(s.close) {
+                        i += s.close.len();
+                        push_span(&mut spans, seg_start, i, TokenKind::String);
+                        seg_start = i;
+                        self.state = State::Code;
+                    } else {
+                        i += char_len(rest);
+                    }
+                }
+            }
+        }
+
+        let kind = match self.state {
+            State::Code => TokenKind::Code,
+            State::Block { .. } => TokenKind::Comment,
+            State::Str { .. } => TokenKind::String,
+        };
+        push_span(&mut spans, seg_start, line.len(), kind);
+
+        if let State::Str { index } = self.state {
+            if !self.syntax.strings[index].multiline {
+                self.state = State::Code;
+            }
+        }
+
+        spans
+    }
+
+    fn string_opener(&self, rest: &str) -> Option<usize> {
+        self.syntax.strings.iter().position(|s| {
+            if !rest.starts_with(s.open) {
+                return false;
+            }
+            match s.max_len {
+                Some(max) => {
+                    let body = &rest[s.open.len()..];
+                    let limit = floor_char_boundary(body, max);
+                    literal_closes_within(&body[..limit], s)
+                }
+                None => true,
+            }
+        })
+    }
+}
+
+/// Whether a bounded literal closes within `body`. Unescaped literals must hold
+/// exactly one character, so `'a'` is a char but `'a>(x: &'` is not.
+fn literal_closes_within(body: &str, s: &StringLiteral) -> bool {
+    if s.escape && body.starts_with('\\') {
+        return body[1..].find(s.close).is_some_and(|i| i > 0);
+    }
+    let first = char_len(body);
+    body.len() > first && body[first..].starts_with(s.close)
+}
+
+fn floor_char_boundary(s: &str, max: usize) -> usize {
+    let mut i = max.min(s.len());
+    while !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+fn char_len(s: &str) -> usize {
+    s.chars().next().map_or(1, char::len_utf8)
+}
+
+fn push_span(spans: &mut Vec<Span>, start: usize, end: usize, kind: TokenKind) {
+    if start < end {
+        spans.push(Span { start, end, kind });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(line_spans: &[Span], line: &str) -> Vec<(TokenKind, String)> {
+        line_spans
+            .iter()
+            .map(|s| (s.kind, line[s.start..s.end].to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn line_comment_after_code() {
+        let line = "const x = 1; // eslint-disable-line";
+        let spans = CommentSyntax::JAVASCRIPT.tokenize(line);
+        assert_eq!(
+            kinds(&spans[0], line),
+            vec![
+                (TokenKind::Code, "const x = 1; ".into()),
+                (TokenKind::Comment, "// eslint-disable-line".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn comment_marker_inside_string_is_not_a_comment() {
+        let line = r#"const msg = "// eslint-disable-next-line";"#;
+        let spans = CommentSyntax::JAVASCRIPT.tokenize(line);
+        assert!(spans[0].iter().all(|s| s.kind != TokenKind::Comment));
+    }
+
+    #[test]
+    fn block_comment_spans_lines() {
+        let content = "a /* one\ntwo\nthree */ b";
+        let spans = CommentSyntax::C.tokenize(content);
+        assert_eq!(spans[1][0].kind, TokenKind::Comment);
+        let last = "three */ b";
+        assert_eq!(
+            kinds(&spans[2], last),
+            vec![
+                (TokenKind::Comment, "three */".into()),
+                (TokenKind::Code, " b".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_docstring_is_skipped() {
+        let content = "def f():\n    \"\"\"Use # noqa\n    here\"\"\"\n    x = 1  # noqa";
+        let spans = CommentSyntax::PYTHON.tokenize(content);
+        assert!(spans[1].iter().all(|s| s.kind != TokenKind::Comment));
+        assert_eq!(spans[2][0].kind, TokenKind::String);
+        assert_eq!(spans[3].last().unwrap().kind, TokenKind::Comment);
+    }
+
+    #[test]
+    fn rust_lifetimes_are_not_strings() {
+        let line = "fn f<'a>(x: &'a str) {} // NOTE";
+        let spans = CommentSyntax::RUST.tokenize(line);
+        assert_eq!(spans[0].last().unwrap().kind, TokenKind::Comment);
+        assert!(spans[0].iter().all(|s| s.kind != TokenKind::String));
+    }
+
+    #[test]
+    fn rust_raw_string_with_quotes() {
+        let line = r##"let s = r#"a " // b"#; // c"##;
+        let spans = CommentSyntax::RUST.tokenize(line);
+        let k = kinds(&spans[0], line);
+        assert_eq!(k[1], (TokenKind::String, r##"r#"a " // b"#"##.into()));
+        assert_eq!(k.last().unwrap(), &(TokenKind::Comment, "// c".into()));
+    }
+
+    #[test]
+    fn nested_block_comments() {
+        let line = "/* a /* b */ c */ d";
+        let spans = CommentSyntax::RUST.tokenize(line);
+        assert_eq!(
+            kinds(&spans[0], line),
+            vec![
+                (TokenKind::Comment, "/* a /* b */ c */".into()),
+                (TokenKind::Code, " d".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_quote_stays_in_string() {
+        let line = r#"s = "a \" # b"  # c"#;
+        let spans = CommentSyntax::PYTHON.tokenize(line);
+        assert_eq!(spans[0].last().unwrap().kind, TokenKind::Comment);
+        assert_eq!(&line[spans[0].last().unwrap().start..], "# c");
+    }
+
+    #[test]
+    fn single_line_string_resets_at_end_of_line() {
+        let content = "s = 'unterminated\n# noqa";
+        let spans = CommentSyntax::HASH.tokenize(content);
+        assert_eq!(spans[1][0].kind, TokenKind::Comment);
+    }
+
+    #[test]
+    fn shell_length_expansions_are_code() {
+        let line = "echo $# ${#arr[@]} shellcheck disable=SC2086  # shellcheck disable=SC2034";
+        let spans = CommentSyntax::HASH.tokenize(line);
+        let comments: Vec<(TokenKind, String)> = kinds(&spans[0], line)
+            .into_iter()
+            .filter(|(k, _)| *k == TokenKind::Comment)
+            .collect();
+        assert_eq!(
+            comments,
+            vec![(TokenKind::Comment, "# shellcheck disable=SC2034".into())]
+        );
+    }
+
+    #[test]
+    fn css_has_no_line_comments() {
+        let line = "a { background: url(http://x/stylelint-disable-line.png); } // b";
+        let spans = CommentSyntax::CSS.tokenize(line);
+        assert!(spans[0].iter().all(|s| s.kind != TokenKind::Comment));
+
+        let scss = CommentSyntax::CSS.for_path(Path::new("a.scss"));
+        assert_eq!(scss, CommentSyntax::SCSS);
+        let spans = scss.tokenize(line);
+        assert_eq!(spans[0].last().unwrap().kind, TokenKind::Comment);
+        assert_eq!(
+            CommentSyntax::CSS.for_path(Path::new("a.vue")),
+            CommentSyntax::CSS
+        );
+    }
+
+    #[test]
+    fn lookup_by_name() {
+        assert_eq!(
+            CommentSyntax::by_name("python"),
+            Some(CommentSyntax::PYTHON)
+        );
+        assert!(CommentSyntax::by_name("cobol").is_none());
+    }
+}
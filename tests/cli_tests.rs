@@ -159,3 +159,1705 @@ fn scan_fixtures_sarif_output() {
         .unwrap()
         .is_empty());
 }
+
+#[test]
+fn directives_in_strings_are_not_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.js"),
+        "const msg = \"use eslint-disable-next-line here\";\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("mod.py"),
+        "def f():\n    \"\"\"Add # noqa to silence it.\"\"\"\n    return 1\n",
+    )
+    .unwrap();
+    cmd()
+        .args([dir.path().to_str().unwrap(), "--format", "count"])
+        .assert()
+        .success()
+        .stdout("0");
+}
+
+#[test]
+fn block_directives_are_paired_into_regions() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.js"),
+        "/* eslint-disable no-console */\nconsole.log(1);\nconsole.log(2);\n/* eslint-enable no-console */\n/* eslint-disable */\nfoo();\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("app.rb"),
+        "x = 1 # rubocop:disable Style/Foo\n# rubocop:enable Style/Foo\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("app.swift"),
+        "// swiftlint:disable force_cast\n// swiftlint:enable:next force_cast\nlet a = b as! C\n",
+    )
+    .unwrap();
+    let output = cmd()
+        .args([dir.path().to_str().unwrap(), "--format", "json"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 4);
+    assert!(findings
+        .iter()
+        .all(|f| !f["rule_id"].as_str().unwrap().ends_with("enable")));
+
+    let closed = &findings[0]["region"];
+    assert_eq!(closed["start_line"], 1);
+    assert_eq!(closed["end_line"], 4);
+    assert_eq!(closed["lines"], 2);
+    assert_eq!(closed["terminated"], true);
+
+    let open = &findings[1]["region"];
+    assert_eq!(open["end_line"], 6);
+    assert_eq!(open["terminated"], false);
+
+    // A trailing rubocop:disable only covers its own line
+    assert!(findings[2]["region"].is_null());
+
+    // swiftlint:enable:next re-enables one line, not the rest of the file
+    let swift = &findings[3]["region"];
+    assert_eq!(swift["end_line"], 3);
+    assert_eq!(swift["terminated"], false);
+}
+
+#[test]
+fn scope_filter() {
+    let output = cmd()
+        .args([
+            "tests/fixtures",
+            "--scope",
+            "whole-file",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let findings = json["findings"].as_array().unwrap();
+    assert!(!findings.is_empty());
+    assert!(findings.iter().all(|f| f["scope"] == "whole-file"));
+    assert_eq!(
+        json["stats"]["findings_count"].as_u64().unwrap(),
+        findings.len() as u64
+    );
+}
+
+#[test]
+fn unclosed_leading_region_is_whole_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.js"),
+        "/* eslint-disable */\nfoo();\n// eslint-disable-next-line no-alert\nalert(1);\n",
+    )
+    .unwrap();
+    let output = cmd()
+        .args([dir.path().to_str().unwrap(), "--format", "json"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings[0]["scope"], "whole-file");
+    assert_eq!(findings[1]["scope"], "next-line");
+}
+
+#[test]
+fn blanket_suppressions_are_flagged() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("mod.py"),
+        "import os  # noqa\nimport sys  # noqa: F401\nx = f()  # type: ignore[attr-defined]\n",
+    )
+    .unwrap();
+    let output = cmd()
+        .args([
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--pass-threshold",
+            "10",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings[0]["blanket"], true);
+    assert_eq!(findings[1]["blanket"], false);
+    assert_eq!(findings[2]["blanket"], false);
+    assert_eq!(json["stats"]["blanket_count"], 1);
+
+    cmd()
+        .args([
+            dir.path().to_str().unwrap(),
+            "--pass-threshold",
+            "10",
+            "--fail-on-blanket",
+        ])
+        .assert()
+        .failure()
+        .code(1);
+}
+
+#[test]
+fn require_justification() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.js"),
+        "// eslint-disable-next-line no-eval -- sandboxed plugin loader\neval(a);\n// eslint-disable-next-line no-eval\neval(b);\n",
+    )
+    .unwrap();
+    let output = cmd()
+        .args([
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--pass-threshold",
+            "10",
+            "--require-justification",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings[0]["justification"], "sandboxed plugin loader");
+    assert!(findings[0].get("justification_issue").is_none());
+    assert_eq!(findings[1]["justification_issue"], "missing justification");
+    assert_eq!(json["stats"]["unjustified_count"], 1);
+
+    cmd()
+        .args([dir.path().to_str().unwrap(), "--pass-threshold", "10"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn baseline_reports_only_new_findings() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    let baseline = dir.path().join("baseline.json");
+    std::fs::write(
+        src.join("app.py"),
+        "import sys\nimport os  # noqa: F401\n\ndef f():\n    return g()  # type: ignore\n",
+    )
+    .unwrap();
+
+    cmd()
+        .args([
+            "baseline",
+            src.to_str().unwrap(),
+            "--write",
+            baseline.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    // Shift the known findings down, add a new one and remove another
+    std::fs::write(
+        src.join("app.py"),
+        "\"\"\"Docs.\"\"\"\n\nimport sys\nimport os  # noqa: F401\n\ndef f():\n    return 1\n\nimport re  # noqa: F401\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args([
+            src.to_str().unwrap(),
+            "--baseline",
+            baseline.to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["line_number"], 9);
+    assert_eq!(json["baseline"]["matched"], 1);
+    let gone = json["baseline"]["disappeared"].as_array().unwrap();
+    assert_eq!(gone.len(), 1);
+    assert_eq!(gone[0]["line_text"], "return g()  # type: ignore");
+
+    cmd()
+        .args([
+            src.to_str().unwrap(),
+            "--baseline",
+            baseline.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Baseline: 1 known, 1 disappeared"));
+
+    // Entries outside the selected scopes were not looked for
+    let output = cmd()
+        .args([
+            src.to_str().unwrap(),
+            "--baseline",
+            baseline.to_str().unwrap(),
+            "--scope",
+            "whole-file",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["baseline"]["disappeared"], serde_json::json!([]));
+}
+
+#[test]
+fn baseline_written_at_the_root_matches_subdirectory_scans() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    std::fs::write(src.join("a.py"), "import os  # noqa: F401\n").unwrap();
+    cmd()
+        .current_dir(dir.path())
+        .args(["baseline", ".", "--write", "baseline.json"])
+        .assert()
+        .success();
+
+    for (cwd, args) in [
+        (
+            dir.path().to_path_buf(),
+            ["src", "--baseline", "baseline.json"],
+        ),
+        (src.clone(), [".", "--baseline", "../baseline.json"]),
+    ] {
+        cmd()
+            .current_dir(cwd)
+            .args(args)
+            .args(["--pass-threshold", "0"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Baseline: 1 known, 0 disappeared"));
+    }
+}
+
+#[test]
+fn directories_named_like_subcommands_are_scanned_as_dot_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("baseline")).unwrap();
+    std::fs::write(dir.path().join("baseline/app.py"), "import os  # noqa\n").unwrap();
+
+    // A bare name runs the subcommand
+    cmd()
+        .current_dir(dir.path())
+        .arg("baseline")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--write"));
+    cmd()
+        .current_dir(dir.path())
+        .arg("./baseline")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("baseline/app.py:1 [flake8:noqa]"));
+    cmd()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("./<dir>"));
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .status()
+        .expect("git runs");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+#[test]
+fn diff_base_reports_added_and_removed() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import os  # noqa: F401\nimport sys  # noqa: F401\n",
+    )
+    .unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "init"]);
+
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import os  # noqa: F401\nimport sys\nx = f()  # type: ignore\ny = g()  # pyright: ignore\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args([
+            dir.path().to_str().unwrap(),
+            "--diff-base",
+            "HEAD",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let lines: Vec<u64> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["line_number"].as_u64().unwrap())
+        .collect();
+    assert_eq!(lines, vec![3, 4]);
+    assert_eq!(json["diff"]["added"], 2);
+    let removed = json["diff"]["removed"].as_array().unwrap();
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0]["path"], "app.py");
+    assert_eq!(removed[0]["line_number"], 2);
+
+    cmd()
+        .args([dir.path().to_str().unwrap(), "--diff-base", "no-such-ref"])
+        .assert()
+        .code(2);
+
+    // Removed lines are filtered like scanned ones
+    let write = |path: &str, content: &str| {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    };
+    write(".lintscout.yml", "settings:\n  exclude: [vendor]\n");
+    write(
+        "pkg/.lintscout.yml",
+        "settings:\n  disable:\n    scouts: [mypy]\n",
+    );
+    write("vendor/lib.py", "import os  # noqa\n");
+    write("pkg/lib.py", "x = f()  # type: ignore\n");
+    write(
+        "app.py",
+        "import os  # noqa: F401  # lintscout-ignore\nimport sys  # noqa: F401\n",
+    );
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "more"]);
+    write("vendor/lib.py", "import os\n");
+    write("pkg/lib.py", "x = f()\n");
+    write("app.py", "import os\nimport sys\n");
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args(["--diff-base", "HEAD", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let removed = json["diff"]["removed"].as_array().unwrap();
+    assert_eq!(removed.len(), 1, "{removed:?}");
+    assert_eq!(removed[0]["path"], "app.py");
+    assert_eq!(removed[0]["line_number"], 2);
+}
+
+#[test]
+fn diff_file_from_stdin() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.js"),
+        "// eslint-disable-next-line no-eval\neval(a);\n",
+    )
+    .unwrap();
+    let diff = "\
+--- a/app.js
++++ b/app.js
+@@ -1 +1 @@
+-eval(a); // eslint-disable-line no-eval
++// eslint-disable-next-line no-eval
+";
+    cmd()
+        .current_dir(dir.path())
+        .args([".", "--diff-file", "-"])
+        .write_stdin(diff)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("app.js:1 [eslint:"))
+        .stdout(predicate::str::contains(
+            "Diff against -: 1 added, 1 removed",
+        ))
+        .stdout(predicate::str::contains(
+            "removed: app.js:1 [eslint:eslint-disable-line]",
+        ));
+}
+
+#[test]
+fn staged_scans_index_content() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(dir.path().join("staged.py"), "import os  # noqa: F401\n").unwrap();
+    std::fs::write(dir.path().join("partial.py"), "x = f()  # type: ignore\n").unwrap();
+    git(dir.path(), &["add", "staged.py", "partial.py"]);
+    // Unstaged edits: one suppression removed, one added to an untracked file
+    std::fs::write(dir.path().join("partial.py"), "x = f()\n").unwrap();
+    std::fs::write(dir.path().join("unstaged.py"), "y = g()  # noqa\n").unwrap();
+
+    let output = cmd()
+        .args([dir.path().to_str().unwrap(), "--staged", "--format", "json"])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let mut paths: Vec<String> = json["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["path"].as_str().unwrap().to_string())
+        .collect();
+    paths.sort();
+    assert_eq!(paths.len(), 2);
+    assert!(paths[0].ends_with("partial.py"));
+    assert!(paths[1].ends_with("staged.py"));
+    assert_eq!(json["stats"]["files_walked"], 2);
+}
+
+#[test]
+fn install_hook_writes_pre_commit() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    cmd()
+        .current_dir(dir.path())
+        .arg("install-hook")
+        .assert()
+        .success();
+    let hook = dir.path().join(".git/hooks/pre-commit");
+    let script = std::fs::read_to_string(&hook).unwrap();
+    assert!(script.contains("lintscout --staged"));
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("install-hook")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--force"));
+    cmd()
+        .current_dir(dir.path())
+        .args(["install-hook", "--force"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn rev_scans_a_bare_clone() {
+    let dir = tempfile::tempdir().unwrap();
+    let work = dir.path().join("work");
+    std::fs::create_dir(&work).unwrap();
+    git(&work, &["init", "-q"]);
+    std::fs::write(work.join(".gitignore"), "generated/\n").unwrap();
+    std::fs::create_dir(work.join("generated")).unwrap();
+    std::fs::create_dir(work.join("vendor")).unwrap();
+    std::fs::write(work.join("app.py"), "import os  # noqa: F401\n").unwrap();
+    std::fs::write(work.join("generated/api.py"), "x = 1  # noqa\n").unwrap();
+    std::fs::write(work.join("vendor/lib.py"), "y = 1  # noqa\n").unwrap();
+    git(&work, &["add", "."]);
+    git(&work, &["add", "-f", "generated/api.py"]);
+    git(&work, &["commit", "-q", "-m", "release"]);
+    git(&work, &["tag", "v1.0.0"]);
+    // Later history should not affect the tag
+    std::fs::write(work.join("app.py"), "import os\n").unwrap();
+    git(&work, &["commit", "-q", "-am", "cleanup"]);
+
+    let bare = dir.path().join("bare.git");
+    git(
+        dir.path(),
+        &[
+            "clone",
+            "-q",
+            "--bare",
+            work.to_str().unwrap(),
+            bare.to_str().unwrap(),
+        ],
+    );
+
+    let output = cmd()
+        .args([
+            bare.to_str().unwrap(),
+            "--rev",
+            "v1.0.0",
+            "--format",
+            "json",
+            "--exclude",
+            "vendor",
+        ])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0]["path"].as_str().unwrap().ends_with("app.py"));
+
+    cmd()
+        .args([bare.to_str().unwrap(), "--rev", "v1.0.0", "--no-gitignore"])
+        .args(["--exclude", "vendor", "--format", "count"])
+        .assert()
+        .stdout("2");
+
+    cmd()
+        .args([bare.to_str().unwrap(), "--rev", "HEAD"])
+        .args(["--exclude", "vendor"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn blame_groups_by_author_and_applies_age_policies() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(dir.path().join("old.py"), "import os  # noqa: F401\n").unwrap();
+    git(dir.path(), &["add", "old.py"]);
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir.path())
+        .args([
+            "-c",
+            "user.name=Old Timer",
+            "-c",
+            "user.email=old@example.com",
+        ])
+        .args(["commit", "-q", "-m", "old"])
+        .env("GIT_AUTHOR_DATE", "2015-06-01T12:00:00Z")
+        .status()
+        .unwrap();
+    assert!(status.success());
+    std::fs::write(dir.path().join("new.py"), "x = f()  # type: ignore\n").unwrap();
+    git(dir.path(), &["add", "new.py"]);
+    git(dir.path(), &["commit", "-q", "-m", "new"]);
+    std::fs::write(
+        dir.path().join(".lintscout.yml"),
+        "settings:\n  pass_threshold: 10\n  age_policies:\n    - name: new-unjustified\n      younger_than_days: 7\n      unjustified: true\n      action: fail\n    - name: stale\n      older_than_days: 730\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--blame", "--format", "json"])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let findings = json["findings"].as_array().unwrap();
+    let new = findings.iter().find(|f| f["path"] == "./new.py").unwrap();
+    let old = findings.iter().find(|f| f["path"] == "./old.py").unwrap();
+    assert_eq!(new["blame"]["author"], "test");
+    assert_eq!(new["age_policies"][0]["name"], "new-unjustified");
+    assert_eq!(old["blame"]["author_mail"], "old@example.com");
+    assert_eq!(old["blame"]["date"], "2015-06-01");
+    assert_eq!(old["age_policies"][0]["action"], "report");
+    assert_eq!(json["stats"]["age_failures"], 1);
+
+    cmd()
+        .current_dir(dir.path())
+        .args([".", "--blame"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "== Old Timer <old@example.com>: 1 ==",
+        ))
+        .stdout(predicate::str::contains("[age: stale]"));
+
+    // Without blame, age policies do not apply
+    cmd().current_dir(dir.path()).arg(".").assert().success();
+}
+
+#[test]
+fn staged_blame_follows_the_index() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(dir.path().join("app.py"), "import os  # noqa\n").unwrap();
+    git(dir.path(), &["add", "app.py"]);
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir.path())
+        .args([
+            "-c",
+            "user.name=Old Timer",
+            "-c",
+            "user.email=old@example.com",
+        ])
+        .args(["commit", "-q", "-m", "old"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    // The staged line 2 is the committed one; in the working tree it is new
+    std::fs::write(dir.path().join("app.py"), "x = 1\nimport os  # noqa\n").unwrap();
+    git(dir.path(), &["add", "app.py"]);
+    std::fs::write(
+        dir.path().join("app.py"),
+        "y = 2\nz = 3\nimport os  # noqa\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--staged", "--blame", "--format", "json"])
+        .assert()
+        .failure()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let finding = &json["findings"][0];
+    assert_eq!(finding["line_number"], 2);
+    assert_eq!(finding["blame"]["author"], "Old Timer");
+    assert_eq!(json["stats"]["errors_count"], 0);
+}
+
+#[test]
+fn history_samples_suppression_counts() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    let commit_at = |date: &str, message: &str| {
+        git(dir.path(), &["add", "-A"]);
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(["commit", "-q", "-m", message])
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/app.py"), "import os  # noqa: F401\n").unwrap();
+    commit_at("2024-01-03T12:00:00Z", "first");
+    std::fs::write(dir.path().join("setup.py"), "x = f()  # type: ignore\n").unwrap();
+    commit_at("2024-01-10T12:00:00Z", "second");
+    std::fs::write(dir.path().join("src/app.py"), "import os\n").unwrap();
+    commit_at("2024-01-20T12:00:00Z", "third");
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args(["history", "--since", "2024-01-01", "--until", "2024-01-22"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let csv = String::from_utf8(output).unwrap();
+    let totals: Vec<_> = csv
+        .lines()
+        .filter(|l| l.contains(",total,"))
+        .map(|l| (&l[..10], l.rsplit(',').next().unwrap()))
+        .collect();
+    // 2024-01-01 predates the first commit
+    assert_eq!(
+        totals,
+        [
+            ("2024-01-08", "1"),
+            ("2024-01-15", "2"),
+            ("2024-01-22", "1")
+        ]
+    );
+    assert!(csv.starts_with("date,commit,dimension,key,count\n"));
+    assert!(csv.contains(",directory,src,1\n"));
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args(["history", "--since", "2024-01-15", "--every", "month"])
+        .args(["--until", "2024-02-15", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let samples = json.as_array().unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0]["date"], "2024-01-15");
+    assert_eq!(samples[0]["by_directory"]["src"], 1);
+    assert_eq!(samples[0]["by_directory"]["."], 1);
+    assert_eq!(samples[1]["total"], 1);
+    assert_eq!(samples[1]["by_scout"]["mypy"], 1);
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["history", "--since", "2024-02-30"])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+fn compare_reports_added_removed_and_moved() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src).unwrap();
+    let app = src.join("app.py");
+    std::fs::write(&app, "import os  # noqa: F401\nimport sys  # noqa: F401\n").unwrap();
+    let report = |name: &str| {
+        let output = cmd()
+            .args([src.to_str().unwrap(), "--format", "json"])
+            .output()
+            .unwrap();
+        let path = dir.path().join(name);
+        std::fs::write(&path, output.stdout).unwrap();
+        path
+    };
+    let old = report("old.json");
+    std::fs::write(
+        &app,
+        "\"\"\"App.\"\"\"\n\nimport os  # noqa: F401\nx = f()  # type: ignore\ny = g()  # type: ignore\n",
+    )
+    .unwrap();
+    let new = report("new.json");
+
+    cmd()
+        .args(["compare", old.to_str().unwrap(), new.to_str().unwrap()])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("Findings: 2 -> 3 (+1)"))
+        .stdout(predicate::str::contains("Added: 2"))
+        .stdout(predicate::str::contains("Removed: 1"))
+        .stdout(predicate::str::contains("Moved: 1"))
+        .stdout(predicate::str::contains("mypy: 0 -> 2 (+2)"));
+
+    let output = cmd()
+        .args(["compare", new.to_str().unwrap(), old.to_str().unwrap()])
+        .args(["--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["net"], -1);
+    assert_eq!(json["moved"][0]["from_line"], 3);
+    assert_eq!(json["moved"][0]["finding"]["line_number"], 1);
+
+    cmd()
+        .args(["compare", old.to_str().unwrap(), new.to_str().unwrap()])
+        .args(["--format", "markdown"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("| mypy | 0 | 2 | +2 |"));
+
+    cmd()
+        .args(["compare", old.to_str().unwrap(), "missing.json"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn compare_reads_reports_from_earlier_releases() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import os  # noqa\nx = 1  # type: ignore[attr-defined]\n",
+    )
+    .unwrap();
+    // Written by 0.2.0, before scope, blanket and fingerprints were reported
+    let old = dir.path().join("old.json");
+    std::fs::write(
+        &old,
+        r#"{
+  "findings": [
+    {
+      "path": "./app.py",
+      "line_number": 1,
+      "line_text": "import os  # noqa",
+      "scout_name": "flake8",
+      "linter": "flake8",
+      "rule_id": "noqa",
+      "rule_description": "Flake8 noqa directive"
+    },
+    {
+      "path": "./app.py",
+      "line_number": 2,
+      "line_text": "x = 1  # type: ignore[attr-defined]",
+      "scout_name": "mypy",
+      "linter": "mypy",
+      "rule_id": "type-ignore",
+      "rule_description": "Mypy type ignore directive",
+      "suppressed_rules": [
+        "attr-defined"
+      ]
+    }
+  ],
+  "stats": {
+    "files_walked": 2,
+    "files_scanned": 1,
+    "files_skipped": 0,
+    "findings_count": 2,
+    "errors_count": 0,
+    "duration_ms": 4
+  }
+}
+"#,
+    )
+    .unwrap();
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "json"])
+        .output()
+        .unwrap();
+    let new = dir.path().join("new.json");
+    std::fs::write(&new, output.stdout).unwrap();
+
+    cmd()
+        .args(["compare", old.to_str().unwrap(), new.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Findings: 2 -> 2 (0)"))
+        .stdout(predicate::str::contains("Unchanged: 2"));
+}
+
+#[test]
+fn hotspots_rank_by_churn_and_density() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/busy.py"), "import os  # noqa\n").unwrap();
+    std::fs::write(
+        dir.path().join("quiet.py"),
+        "import os  # noqa\n\nx = 1\ny = 2\n",
+    )
+    .unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "initial"]);
+    for n in 0..2 {
+        std::fs::write(
+            dir.path().join("src/busy.py"),
+            format!("import os  # noqa\nv = {n}\n"),
+        )
+        .unwrap();
+        git(dir.path(), &["commit", "-q", "-am", "change"]);
+    }
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args(["hotspots", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let files = json["files"].as_array().unwrap();
+    assert_eq!(files[0]["path"], "src/busy.py");
+    assert_eq!(files[0]["commits"], 3);
+    assert_eq!(files[0]["loc"], 2);
+    assert_eq!(files[1]["path"], "quiet.py");
+    assert_eq!(files[1]["loc"], 3);
+    assert_eq!(json["directories"][0]["path"], "src");
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["hotspots", "--limit", "1", "--since", "2999-01-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Score"))
+        .stdout(predicate::str::contains("quiet.py").not());
+}
+
+#[test]
+fn density_threshold_gates_on_suppressions_per_kloc() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    let mut code = String::from("import os  # noqa: F401\n\n");
+    for n in 0..399 {
+        code.push_str(&format!("v{n} = {n}\n"));
+    }
+    std::fs::write(dir.path().join("src/app.py"), code).unwrap();
+    std::fs::write(dir.path().join("tool.py"), "x = f()  # type: ignore\n").unwrap();
+
+    let output = cmd()
+        .args([dir.path().to_str().unwrap(), "--format", "json"])
+        .args(["--density-threshold", "5"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let density = &json["density"];
+    assert_eq!(density["lines"], 401);
+    assert_eq!(density["per_kloc"], 4.99);
+    assert_eq!(density["by_language"]["python"]["findings"], 2);
+    assert_eq!(density["by_scout"]["mypy"]["per_kloc"], 2.49);
+    assert_eq!(density["by_directory"]["src"]["per_kloc"], 2.5);
+    assert_eq!(density["by_directory"]["."]["per_kloc"], 1000.0);
+
+    cmd()
+        .arg(dir.path())
+        .args(["--density-threshold", "4"])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "Density: 4.99 per KLOC (401 lines)",
+        ))
+        .stdout(predicate::str::contains(
+            "  directory src: 2.50 per KLOC (1 in 400 lines)",
+        ));
+
+    std::fs::write(
+        dir.path().join(".lintscout.yml"),
+        "settings:\n  density_threshold: 10\n",
+    )
+    .unwrap();
+    cmd().current_dir(dir.path()).arg(".").assert().success();
+}
+
+#[test]
+fn rule_policy_reports_denied_and_over_quota_rules() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.js"),
+        "// eslint-disable-next-line no-console\nconsole.log(1);\n\
+         // eslint-disable-next-line no-eval\neval(x);\n\
+         // eslint-disable-next-line no-any\nf(a);\n\
+         // eslint-disable-next-line no-any\nf(b);\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join(".lintscout.yml"),
+        "settings:\n  pass_threshold: 100\npolicy:\n  eslint:\n    deny: [no-eval]\n    allow: [no-console]\n    quota: { no-any: 1 }\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg(".")
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("[policy: no-eval denied]"))
+        .stdout(predicate::str::contains("[policy: no-any over quota of 1]"))
+        .stdout(predicate::str::contains("Findings: 3\n"))
+        .stdout(predicate::str::contains("Policy violations: 2"))
+        .stdout(predicate::str::contains("no-console").not());
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "sarif"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let sarif: serde_json::Value = serde_json::from_slice(&output).expect("valid SARIF");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    let denied = results
+        .iter()
+        .find(|r| r["ruleId"] == "eslint/policy-deny")
+        .unwrap();
+    assert_eq!(denied["properties"]["policy_violation"]["rule"], "no-eval");
+    assert!(denied["message"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("'no-eval'"));
+
+    cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "count"])
+        .assert()
+        .stdout("3");
+}
+
+#[test]
+fn budgets_limit_suppressions_per_path() {
+    let dir = tempfile::tempdir().unwrap();
+    for sub in ["legacy", "services/payments"] {
+        std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+    }
+    std::fs::write(
+        dir.path().join("legacy/old.py"),
+        "import os  # noqa\nimport sys  # noqa\n",
+    )
+    .unwrap();
+    let config = "settings:\n  budgets:\n    - path: legacy/\n      max: 500\n    - path: services/payments/\n      max: 0\n    - path: legacy/\n      scouts: [mypy]\n      max: 0\n";
+    std::fs::write(dir.path().join(".lintscout.yml"), config).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg(".")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Budgets: 0 of 3 exceeded"))
+        .stdout(predicate::str::contains("  legacy/: 2 of 500\n"));
+
+    std::fs::write(
+        dir.path().join("services/payments/charge.py"),
+        "x = f()  # type: ignore\n",
+    )
+    .unwrap();
+    cmd()
+        .current_dir(dir.path())
+        .arg(".")
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("Budgets: 1 of 3 exceeded"))
+        .stdout(predicate::str::contains(
+            "  services/payments/: 1 of 0 (exceeded)",
+        ))
+        .stdout(predicate::str::contains("  legacy/ [mypy]: 0 of 0\n"));
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["budgets"][1]["used"], 1);
+    assert_eq!(json["budgets"][1]["exceeded"], true);
+    assert_eq!(json["stats"]["budgets_exceeded"], 1);
+}
+
+#[test]
+fn ratchet_lowers_beaten_thresholds() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("legacy")).unwrap();
+    std::fs::write(
+        dir.path().join("legacy/old.py"),
+        "import os  # noqa\nimport sys  # noqa: F401\n",
+    )
+    .unwrap();
+    let config = "# limits only go down\nsettings:\n  pass_threshold: 5  # total\n  budgets:\n    - path: legacy/\n      max: 2\n    - scouts: [flake8]\n      max: 4\n";
+    let path = dir.path().join(".lintscout.yml");
+    std::fs::write(&path, config).unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .env_remove("CI")
+        .args(["ratchet", "--check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "pass_threshold: 5 -> 2 (can be lowered)",
+        ));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), config);
+
+    cmd()
+        .current_dir(dir.path())
+        .env_remove("CI")
+        .arg("ratchet")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pass_threshold: 5 -> 2 (lowered)"))
+        .stdout(predicate::str::contains(
+            "budget legacy/: 2 -> 2 (unchanged)",
+        ))
+        .stdout(predicate::str::contains(
+            "budget * [flake8]: 4 -> 2 (lowered)",
+        ));
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        config
+            .replace("pass_threshold: 5", "pass_threshold: 2")
+            .replace("max: 4", "max: 2")
+    );
+
+    std::fs::write(dir.path().join("legacy/new.py"), "x = 1  # noqa\n").unwrap();
+    cmd()
+        .current_dir(dir.path())
+        .env("CI", "true")
+        .arg("ratchet")
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "pass_threshold: 2 -> 3 (exceeded)",
+        ));
+
+    // Findings under a nested config with thresholds count towards those
+    std::fs::create_dir(dir.path().join("pkg")).unwrap();
+    std::fs::write(
+        dir.path().join("pkg/.lintscout.yml"),
+        "settings:\n  pass_threshold: 10\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("pkg/a.py"),
+        "import os  # noqa\nimport sys  # noqa\n",
+    )
+    .unwrap();
+    std::fs::remove_file(dir.path().join("legacy/new.py")).unwrap();
+    cmd()
+        .current_dir(dir.path())
+        .env("CI", "true")
+        .arg("ratchet")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "pass_threshold: 2 -> 2 (unchanged)",
+        ))
+        .stdout(predicate::str::contains(
+            "budget * [flake8]: 2 -> 4 (exceeded)",
+        ));
+}
+
+#[test]
+fn expired_and_unticketed_suppressions_fail() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import os  # noqa: F401 until 2020-01-01\nimport sys  # noqa: F401 TODO(OPS-7) until 2999-12-31\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join(".lintscout.yml"),
+        "settings:\n  pass_threshold: 10\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg(".")
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicate::str::contains("[expiry: expired on 2020-01-01]"))
+        .stdout(predicate::str::contains("[until 2999-12-31, OPS-7]"))
+        .stdout(predicate::str::contains("Expiry failures: 1"));
+
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import sys  # noqa: F401 TODO(OPS-7) until 2999-12-31\nimport re  # noqa: F401\nmsg = \"until 2020-01-01\"  # noqa: E501\nimport json  # noqa: F401 until 2999-12-31\n",
+    )
+    .unwrap();
+    // The date in the string is not the directive's
+    cmd().current_dir(dir.path()).arg(".").assert().success();
+
+    std::fs::write(
+        dir.path().join(".lintscout.yml"),
+        "settings:\n  pass_threshold: 10\n  expiry:\n    require_ticket: true\n    ticket_pattern: 'JIRA-\\d+'\n",
+    )
+    .unwrap();
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    // A future date stands in for the ticket
+    assert_eq!(json["stats"]["expiry_failures"], 3);
+    assert_eq!(json["findings"][0]["expiry"]["ticket"], "OPS-7");
+    assert_eq!(json["findings"][0]["expiry"]["expires"], "2999-12-31");
+    assert_eq!(
+        json["findings"][0]["expiry"]["issue"],
+        "ticket 'OPS-7' does not match 'JIRA-\\d+'"
+    );
+    assert_eq!(
+        json["findings"][1]["expiry"]["issue"],
+        "missing ticket reference"
+    );
+    assert_eq!(
+        json["findings"][2]["expiry"]["issue"],
+        "missing ticket reference"
+    );
+    assert_eq!(json["findings"][3]["expiry"]["expires"], "2999-12-31");
+    assert!(json["findings"][3]["expiry"].get("issue").is_none());
+}
+
+#[test]
+fn lintscout_ignore_acknowledges_findings() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import os  # noqa: F401  # lintscout-ignore flake8/F401 -- re-exported\n# lintscout-ignore-next-line mypy\nx = f()  # type: ignore\ny = g()  # type: ignore\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args([".", "--pass-threshold", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Findings: 1\n"))
+        .stdout(predicate::str::contains("Acknowledged: 2\n"));
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["stats"]["findings_count"], 1);
+    assert_eq!(json["stats"]["acknowledged_count"], 2);
+    assert_eq!(json["findings"][0]["line_number"], 4);
+    assert_eq!(
+        json["acknowledged"][0]["acknowledgement"]["reason"],
+        "re-exported"
+    );
+    assert_eq!(json["acknowledged"][1]["acknowledgement"]["line"], 2);
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "sarif"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let sarif: serde_json::Value = serde_json::from_slice(&output).expect("valid SARIF");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].get("suppressions").is_none());
+    assert_eq!(results[1]["suppressions"][0]["kind"], "inSource");
+    assert_eq!(
+        results[1]["suppressions"][0]["justification"],
+        "re-exported"
+    );
+}
+
+#[test]
+fn unknown_suppressed_rules_are_flagged() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "x = 1  # noqa: E50l\ny = 2  # noqa: E501\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("a.js"),
+        "// eslint-disable-next-line no-unused-var, react/jsx-key\nfoo();\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args([".", "--pass-threshold", "10"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[unknown rule E50l, did you mean E501?]",
+        ))
+        .stdout(predicate::str::contains(
+            "[unknown rule no-unused-var, did you mean no-unused-vars?]",
+        ))
+        .stdout(predicate::str::contains("Unknown rules: 2\n"));
+
+    cmd()
+        .current_dir(dir.path())
+        .args([".", "--pass-threshold", "10", "--fail-on-unknown-rules"])
+        .assert()
+        .code(1);
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "json", "--pass-threshold", "10"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["stats"]["unknown_rules"], 2);
+    // Plugin rules are not catalogued
+    assert_eq!(
+        json["findings"][0]["rule_details"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+    let known = &json["findings"][2]["rule_details"][0];
+    assert_eq!(known["known"], true);
+    assert_eq!(known["description"], "Line too long");
+    assert_eq!(
+        known["docs_url"],
+        "https://www.flake8rules.com/rules/E501.html"
+    );
+}
+
+#[test]
+fn directives_of_unconfigured_linters_are_orphaned() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(
+        dir.path().join("pyproject.toml"),
+        "[project]\nname = \"app\"\n\n[tool.pylint.format]\nmax-line-length = 100\n",
+    )
+    .unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("src/app.py"),
+        "x = 1  # pylint: disable=invalid-name\nimport os  # noqa: F401\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("src/a.js"),
+        "foo(); // jshint ignore:line\n",
+    )
+    .unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "init"]);
+
+    // The config above the scanned directory counts
+    cmd()
+        .current_dir(dir.path())
+        .args(["src", "--pass-threshold", "5"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[flake8:noqa] Flake8 noqa directive (suppresses: F401) [same-line] [also honored by ruff] [orphaned]",
+        ))
+        .stdout(predicate::str::contains("invalid-name) [same-line]\n"))
+        .stdout(predicate::str::contains(
+            "Orphaned: 2 (not configured: flake8, jshint)\n",
+        ));
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["src", "--pass-threshold", "5", "--fail-on-orphaned"])
+        .assert()
+        .code(1);
+
+    // A linter configured at the revision scanned is not orphaned
+    std::fs::write(dir.path().join(".jshintrc"), "{}\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "jshint"]);
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([
+            "src",
+            "--rev",
+            "HEAD",
+            "--format",
+            "json",
+            "--pass-threshold",
+            "5",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["stats"]["orphaned_count"], 1);
+    assert!(json["findings"][0].get("orphaned").is_none());
+    assert!(json["findings"][1].get("orphaned").is_none());
+    assert_eq!(json["findings"][2]["orphaned"], true);
+    assert_eq!(json["findings"][2]["linter"], "flake8");
+
+    // Configs in excluded directories do not count
+    std::fs::create_dir(dir.path().join("src/third_party")).unwrap();
+    std::fs::write(dir.path().join("src/third_party/.flake8"), "[flake8]\n").unwrap();
+    cmd()
+        .current_dir(dir.path())
+        .args(["src", "--pass-threshold", "5", "--exclude", "third_party"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Orphaned: 1 (not configured: flake8)\n",
+        ));
+    cmd()
+        .current_dir(dir.path())
+        .args(["src", "--pass-threshold", "5"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Orphaned:").not());
+}
+
+#[test]
+fn rules_disabled_in_linter_configs_are_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join(".eslintrc.json"),
+        "{\n  \"rules\": {\n    \"no-console\": \"off\", // CLI tool\n    \"eqeqeq\": \"error\"\n  }\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("pyproject.toml"),
+        "[tool.ruff.lint]\nignore = [\n    \"E501\",\n    \"E741\",\n]\n\n[tool.mypy]\nignore_errors = true\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("tsconfig.json"),
+        "{ \"compilerOptions\": { \"strict\": false } }\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join(".prettierrc.json"), "{}\n").unwrap();
+    std::fs::write(dir.path().join("app.js"), "console.log(1);\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args(["--pass-threshold", "10"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[eslint:config-disable] ESLint rule turned off in config (suppresses: no-console) [config rules.no-console]",
+        ))
+        .stdout(predicate::str::contains(
+            "pyproject.toml:4 [ruff:config-disable] Ruff rule ignored in config (suppresses: E741) [config tool.ruff.lint.ignore]",
+        ))
+        .stdout(predicate::str::contains(
+            "[mypy:config-disable] mypy errors ignored in config [config tool.mypy.ignore_errors] [blanket]",
+        ))
+        .stdout(predicate::str::contains(
+            "tsconfig.json:1 [typescript:config-disable]",
+        ))
+        .stdout(predicate::str::contains("Config suppressions: 5\n"))
+        .stdout(predicate::str::contains("eqeqeq").not());
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([
+            "--scouts",
+            "eslint",
+            "--scope",
+            "config",
+            "--format",
+            "json",
+            "--pass-threshold",
+            "10",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["stats"]["config_count"], 1);
+    let finding = &json["findings"][0];
+    assert_eq!(finding["line_number"], 3);
+    assert_eq!(finding["scope"], "config");
+    assert_eq!(finding["key_path"], "rules.no-console");
+    assert_eq!(finding["justification"], "CLI tool");
+}
+
+#[test]
+fn files_exempted_by_ignore_files_are_listed() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(".eslintignore"), "# old code\nlegacy/\n").unwrap();
+    std::fs::write(
+        dir.path().join("setup.cfg"),
+        "[flake8]\nper-file-ignores =\n    tests/*: E501\n",
+    )
+    .unwrap();
+    for path in ["legacy", "src", "tests"] {
+        std::fs::create_dir(dir.path().join(path)).unwrap();
+    }
+    for path in ["legacy/a.js", "legacy/b.js", "src/app.js"] {
+        std::fs::write(dir.path().join(path), "run();\n").unwrap();
+    }
+    std::fs::write(dir.path().join("tests/test_app.py"), "assert True\n").unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Exemptions: 2 files exempted from eslint, 1 file with E501 disabled\n",
+        ))
+        .stdout(predicate::str::contains(
+            "  2 files exempted from eslint by ./.eslintignore:2 `legacy/`\n    ./legacy/a.js\n    ./legacy/b.js\n",
+        ))
+        .stdout(predicate::str::contains(
+            "  1 file with E501 disabled (flake8) by ./setup.cfg:3 `tests/*`\n    ./tests/test_app.py\n",
+        ));
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args(["--scouts", "flake8", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    let exemptions = json["exemptions"].as_array().unwrap();
+    assert_eq!(exemptions.len(), 1);
+    assert_eq!(exemptions[0]["linter"], "flake8");
+    assert_eq!(exemptions[0]["rule"], "E501");
+    assert_eq!(exemptions[0]["files"][0], "./tests/test_app.py");
+    assert_eq!(exemptions[0]["sources"][0]["line"], 3);
+}
+
+#[test]
+fn shared_directives_are_attributed_to_configured_linters() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("ruff.toml"), "line-length = 100\n").unwrap();
+    std::fs::write(dir.path().join("pyrightconfig.json"), "{}\n").unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import os  # noqa: F401\nx: int = \"a\"  # type: ignore\n",
+    )
+    .unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .args([".", "--pass-threshold", "5"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "./app.py:1 [ruff:noqa] noqa directive (suppresses: F401) [same-line] [also honored by flake8]\n",
+        ))
+        .stdout(predicate::str::contains(
+            "./app.py:2 [pyright:type-ignore] type: ignore directive [same-line] [blanket] [also honored by mypy]\n",
+        ))
+        .stdout(predicate::str::contains("Orphaned").not());
+
+    let output = cmd()
+        .current_dir(dir.path())
+        .args([".", "--format", "json", "--pass-threshold", "5"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["stats"]["findings_count"], 2);
+    assert_eq!(json["findings"][0]["linter"], "ruff");
+    assert_eq!(json["findings"][0]["scout_name"], "flake8");
+    assert_eq!(json["findings"][0]["also_honored_by"][0], "flake8");
+    assert_eq!(json["findings"][1]["also_honored_by"][0], "mypy");
+}
+
+#[test]
+fn nested_configs_apply_to_their_subtree() {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    let write = |path: &str, content: &str| {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    };
+    write(
+        ".lintscout.yml",
+        "settings:\n  exclude: [generated]\n  pass_threshold: 10\n",
+    );
+    write("app.py", "import os  # noqa\n");
+    write("generated/gen.py", "x = 1  # noqa\n");
+    write(
+        "pkg/.lintscout.yml",
+        r#"settings:
+  exclude: []
+  pass_threshold: 1
+  disable:
+    scouts: [mypy]
+scouts:
+  - name: hack
+    extensions: [py]
+    rules:
+      - id: hack
+        description: HACK marker
+        pattern: "HACK"
+"#,
+    );
+    write("pkg/generated/gen.py", "x = 1  # noqa\n");
+    write("pkg/lib.py", "y = 2  # type: ignore  # HACK\n");
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+
+    // The config is found above the working directory
+    cmd()
+        .current_dir(dir.path().join("docs"))
+        .arg("..")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("../app.py:1"))
+        .stdout(predicate::str::contains("../generated/gen.py").not())
+        .stdout(predicate::str::contains("../pkg/generated/gen.py:1"))
+        .stdout(predicate::str::contains("../pkg/lib.py:1 [custom:hack] HACK marker"))
+        .stdout(predicate::str::contains("[mypy:type-ignore]").not())
+        .stdout(predicate::str::contains(
+            "Subtrees: 1 of 1 exceeded\n  ../pkg (../pkg/.lintscout.yml): 2 of 1 findings (exceeded)\n",
+        ));
+
+    // Command-line thresholds take precedence
+    let output = cmd()
+        .current_dir(dir.path().join("docs"))
+        .args(["..", "--pass-threshold", "5", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+    assert_eq!(json["subtrees"][0]["pass_threshold"], 5);
+    assert_eq!(json["subtrees"][0]["findings"], 2);
+    assert_eq!(json["subtrees"][0]["exceeded"], false);
+
+    // Configs above the scan root apply; a plain lintscout.yml is not nested
+    write(
+        "pkg/sub/util.py",
+        "x = 1  # noqa\ny = 2  # type: ignore  # HACK\n",
+    );
+    write(
+        "pkg/sub/lintscout.yml",
+        "settings:\n  disable:\n    scouts: [flake8]\n",
+    );
+    cmd()
+        .current_dir(dir.path())
+        .arg("pkg/sub")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("pkg/sub/util.py:1 [flake8:noqa]"))
+        .stdout(predicate::str::contains("pkg/sub/util.py:2 [custom:hack]"))
+        .stdout(predicate::str::contains("[mypy:type-ignore]").not())
+        .stdout(predicate::str::contains(
+            "Subtrees: 1 of 1 exceeded\n  pkg/sub/ (pkg/.lintscout.yml): 2 of 1 findings (exceeded)\n",
+        ));
+
+    // Broken nested configs fail the scan
+    write("pkg/.lintscout.yml", "scouts: 1\n");
+    cmd()
+        .current_dir(dir.path())
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("pkg/.lintscout.yml"));
+}